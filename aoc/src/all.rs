@@ -0,0 +1,226 @@
+pub mod jobs {
+
+    use common::Part;
+    use std::fmt;
+    use std::str::FromStr;
+
+    /// One part of a day, written as `15.2`
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+    pub struct Job {
+        pub day: u8,
+        pub part: Part,
+    }
+
+    impl fmt::Display for Job {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}.{}", self.day, self.part)
+        }
+    }
+
+    impl FromStr for Job {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let invalid = || format!("invalid part {}, expected day.part as 15.2!", s);
+            let (day, part) = s.split_once('.').ok_or_else(invalid)?;
+            let day: u8 = day.parse().map_err(|_| invalid())?;
+            let part: u8 = part.parse().map_err(|_| invalid())?;
+            if !crate::DAYS.contains(&day) {
+                return Err(format!("there is no day {}!", day));
+            }
+            let part = Part::try_from(part).map_err(|e| e.to_string())?;
+            Ok(Job { day, part })
+        }
+    }
+
+    /// Parts left out unless asked for, day15's part 2 searching sixteen
+    /// trillion locations for more than ten minutes on a single core
+    pub const SLOW: [Job; 1] = [Job {
+        day: 15,
+        part: Part::Two,
+    }];
+
+    // both parts of each day, leaving out the skipped ones
+    pub fn jobs(days: &[u8], skip: &[Job]) -> Vec<Job> {
+        days.iter()
+            .flat_map(|&day| [Part::One, Part::Two].map(|part| Job { day, part }))
+            .filter(|job| !skip.contains(job))
+            .collect()
+    }
+}
+
+pub mod outcome {
+
+    use crate::all::jobs::Job;
+    use crate::check::fixtures::Fixture;
+    use crate::ledger::hash::input_hash;
+    use crate::ledger::store::{Ledger, Verdict};
+    use rayon::prelude::*;
+    use std::error::Error;
+    use std::fmt;
+    use std::path::Path;
+    use std::time::{Duration, Instant};
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Status {
+        // same as the verified answer
+        Verified,
+        // same as an answer not verified yet
+        Recorded,
+        // no answer recorded for the input
+        New,
+        // differs from an answer not verified yet
+        Changed,
+        // differs from the verified answer
+        Mismatch,
+        // the input could not be read or the solution failed
+        Error,
+    }
+
+    impl Status {
+        pub fn from_verdict(verdict: &Verdict) -> Status {
+            match verdict {
+                Verdict::New => Status::New,
+                Verdict::Same { verified: true } => Status::Verified,
+                Verdict::Same { verified: false } => Status::Recorded,
+                Verdict::Differs { verified: true, .. } => Status::Mismatch,
+                Verdict::Differs {
+                    verified: false, ..
+                } => Status::Changed,
+            }
+        }
+
+        pub fn is_failure(&self) -> bool {
+            matches!(self, Status::Mismatch | Status::Error)
+        }
+    }
+
+    impl fmt::Display for Status {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Status::Verified => write!(f, "verified"),
+                Status::Recorded => write!(f, "recorded"),
+                Status::New => write!(f, "new"),
+                Status::Changed => write!(f, "changed"),
+                Status::Mismatch => write!(f, "MISMATCH"),
+                Status::Error => write!(f, "ERROR"),
+            }
+        }
+    }
+
+    /// What running one part of a day on its actual input gave
+    #[derive(Clone, Debug)]
+    pub struct Outcome {
+        pub job: Job,
+        // the answer, or the error message if the part failed
+        pub answer: Result<String, String>,
+        // time taken to read, parse and solve
+        pub wall: Duration,
+        pub status: Status,
+    }
+
+    fn solve(root: &Path, job: Job) -> Result<(String, String), Box<dyn Error>> {
+        let input = crate::read_input(&Fixture::Input.files(root, job.day))?;
+        let timed = crate::solve(job.day, job.part, &input)?;
+        Ok((input_hash(&input), timed.parts[0].value.clone()))
+    }
+
+    pub fn run(root: &Path, job: Job, ledger: &Ledger) -> Outcome {
+        let start = Instant::now();
+        let solved = solve(root, job);
+        let wall = start.elapsed();
+        match solved {
+            Ok((hash, answer)) => {
+                let verdict = ledger.verdict(job.day, job.part.into(), &hash, &answer);
+                Outcome {
+                    job,
+                    answer: Ok(answer),
+                    wall,
+                    status: Status::from_verdict(&verdict),
+                }
+            }
+            Err(e) => Outcome {
+                job,
+                answer: Err(e.to_string()),
+                wall,
+                status: Status::Error,
+            },
+        }
+    }
+
+    // run the jobs side by side, the outcomes are in the order of the jobs
+    pub fn run_all(root: &Path, jobs: &[Job], ledger: &Ledger) -> Vec<Outcome> {
+        jobs.par_iter().map(|job| run(root, *job, ledger)).collect()
+    }
+}
+
+pub mod table {
+
+    use crate::all::outcome::Outcome;
+    use std::time::Duration;
+
+    const HEADER: [&str; 5] = ["day", "part", "answer", "wall", "status"];
+
+    fn wall(elapsed: Duration) -> String {
+        format!("{:.1?}", elapsed)
+    }
+
+    // the answer column, answers spanning several lines (day10's screen)
+    // continue on the following rows
+    fn answer(outcome: &Outcome) -> Vec<String> {
+        match &outcome.answer {
+            Ok(answer) => answer.lines().map(|line| line.to_string()).collect(),
+            Err(e) => vec![format!("error: {}", e)],
+        }
+    }
+
+    /// Summary of the outcomes, one row per part
+    pub fn render(outcomes: &[Outcome]) -> String {
+        let rows: Vec<[Vec<String>; 5]> = outcomes
+            .iter()
+            .map(|outcome| {
+                [
+                    vec![outcome.job.day.to_string()],
+                    vec![outcome.job.part.to_string()],
+                    answer(outcome),
+                    vec![wall(outcome.wall)],
+                    vec![outcome.status.to_string()],
+                ]
+            })
+            .collect();
+
+        let mut widths = HEADER.map(|title| title.len());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = cell.iter().map(|line| line.len()).fold(*width, usize::max);
+            }
+        }
+
+        let line = |cells: [&str; 5]| {
+            let mut line = String::new();
+            for (idx, (cell, width)) in cells.iter().zip(widths).enumerate() {
+                if idx > 0 {
+                    line += "  ";
+                }
+                // numbers and times are right aligned
+                match idx {
+                    0 | 1 | 3 => line += &format!("{:>width$}", cell, width = width),
+                    _ => line += &format!("{:<width$}", cell, width = width),
+                }
+            }
+            line.trim_end().to_string() + "\n"
+        };
+
+        let mut table = line(HEADER);
+        let rule = widths.map(|width| "-".repeat(width));
+        table += &line(rule.each_ref().map(String::as_str));
+        for row in &rows {
+            let height = row.iter().map(|cell| cell.len()).max().unwrap_or(1);
+            for idx in 0..height {
+                let cell = |column: usize| row[column].get(idx).map_or("", |x| x.as_str());
+                table += &line([cell(0), cell(1), cell(2), cell(3), cell(4)]);
+            }
+        }
+        table
+    }
+}
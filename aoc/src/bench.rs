@@ -0,0 +1,180 @@
+pub mod history {
+
+    use fs_err as fs;
+    use serde::{Deserialize, Serialize};
+    use std::error::Error;
+    use std::path::{Path, PathBuf};
+
+    const HISTORY_FILE: &str = "bench_history.toml";
+
+    /// Median time of one phase of a day, in nanoseconds
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub struct Entry {
+        pub day: u8,
+        // "parse", "part1" or "part2"
+        pub phase: String,
+        pub nanos: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub struct Run {
+        // seconds since the unix epoch
+        pub timestamp: u64,
+        pub label: Option<String>,
+        pub iterations: u32,
+        #[serde(default)]
+        pub result: Vec<Entry>,
+    }
+
+    /// All previous benchmark runs, oldest first
+    #[derive(Serialize, Deserialize, Default, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub struct History {
+        #[serde(default)]
+        pub run: Vec<Run>,
+    }
+
+    impl History {
+        pub fn filename(root: &Path) -> PathBuf {
+            root.join(HISTORY_FILE)
+        }
+
+        // there is no history before the first run
+        pub fn load(root: &Path) -> Result<History, Box<dyn Error>> {
+            let filename = History::filename(root);
+            if !filename.exists() {
+                return Ok(History::default());
+            }
+            let contents = fs::read_to_string(&filename)?;
+            toml::from_str(&contents)
+                .map_err(|e| format!("invalid {}: {}", filename.display(), e).into())
+        }
+
+        pub fn save(&self, root: &Path) -> Result<(), Box<dyn Error>> {
+            fs::write(History::filename(root), toml::to_string(self)?)?;
+            Ok(())
+        }
+
+        // most recent run that measured the phase, runs may cover different days
+        pub fn previous(&self, day: u8, phase: &str) -> Option<&Entry> {
+            self.run
+                .iter()
+                .rev()
+                .flat_map(|run| run.result.iter())
+                .find(|entry| entry.day == day && entry.phase == phase)
+        }
+    }
+}
+
+pub mod timing {
+
+    use crate::bench::history::Entry;
+    use crate::check::answers::DayAnswers;
+    use crate::check::fixtures::Fixture;
+    use common::Part;
+    use std::error::Error;
+    use std::path::Path;
+    use std::time::Duration;
+
+    fn median(mut samples: Vec<Duration>) -> Duration {
+        samples.sort();
+        samples[samples.len() / 2]
+    }
+
+    // time parsing and each part of a day on its actual input. Only the parts
+    // with a recorded answer are run, so that a fast but wrong rewrite
+    // does not go unnoticed and unfinished parts are skipped.
+    pub fn bench(root: &Path, day: u8, iterations: u32) -> Result<Vec<Entry>, Box<dyn Error>> {
+        let solver = crate::solver(day).ok_or_else(|| format!("no solution for day {}!", day))?;
+        let answers = DayAnswers::load(root, day)?;
+        let expected = answers.get(Fixture::Input);
+        let input = crate::read_input(&Fixture::Input.files(root, day))?;
+
+        let parts: Vec<Part> = [Part::One, Part::Two]
+            .into_iter()
+            .filter(|part| expected.get(*part).is_some())
+            .collect();
+
+        let mut parse: Vec<Duration> = Vec::new();
+        let mut part_times: Vec<Vec<Duration>> = vec![Vec::new(); parts.len()];
+        for _ in 0..iterations.max(1) {
+            let timed = solver.timed(&input, &parts)?;
+            parse.push(timed.parse);
+            for (idx, answer) in timed.parts.into_iter().enumerate() {
+                if expected.get(answer.part) != Some(answer.value.as_str()) {
+                    return Err(
+                        format!("day {} part {} gave a wrong answer!", day, answer.part).into(),
+                    );
+                }
+                part_times[idx].push(answer.elapsed);
+            }
+        }
+
+        let mut entries = vec![Entry {
+            day,
+            phase: "parse".to_string(),
+            nanos: median(parse).as_nanos() as u64,
+        }];
+        for (part, times) in parts.iter().zip(part_times) {
+            entries.push(Entry {
+                day,
+                phase: format!("part{}", part),
+                nanos: median(times).as_nanos() as u64,
+            });
+        }
+        Ok(entries)
+    }
+}
+
+pub mod report {
+
+    use crate::bench::history::{Entry, History};
+    use std::fmt;
+    use std::time::Duration;
+
+    /// A measured phase next to the previous measurement of the same phase
+    pub struct Comparison<'a> {
+        pub entry: &'a Entry,
+        pub previous: Option<&'a Entry>,
+    }
+
+    impl<'a> Comparison<'a> {
+        pub fn new(entry: &'a Entry, history: &'a History) -> Comparison<'a> {
+            Comparison {
+                entry,
+                previous: history.previous(entry.day, &entry.phase),
+            }
+        }
+    }
+
+    impl fmt::Display for Comparison<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let now = Duration::from_nanos(self.entry.nanos);
+            write!(
+                f,
+                "day {:>2} {:<6} {:>12}",
+                self.entry.day,
+                self.entry.phase,
+                format!("{:.2?}", now)
+            )?;
+            if let Some(previous) = self.previous {
+                let before = Duration::from_nanos(previous.nanos);
+                let change = if previous.nanos == 0 {
+                    0.0
+                } else {
+                    100.0 * (self.entry.nanos as f64 - previous.nanos as f64)
+                        / previous.nanos as f64
+                };
+                write!(
+                    f,
+                    "  previous {:>12} {:>+8.1}%",
+                    format!("{:.2?}", before),
+                    change
+                )?;
+            }
+            Ok(())
+        }
+    }
+}
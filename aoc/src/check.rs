@@ -0,0 +1,205 @@
+pub mod fixtures {
+
+    use std::fmt;
+    use std::path::{Path, PathBuf};
+
+    /// Input a day's answers are checked against.
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Fixture {
+        // worked example from the puzzle description, kept in test.txt
+        Example,
+        // actual puzzle input, kept in input.txt
+        Input,
+    }
+
+    impl fmt::Display for Fixture {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Fixture::Example => write!(f, "example"),
+                Fixture::Input => write!(f, "input"),
+            }
+        }
+    }
+
+    impl Fixture {
+        pub fn files(&self, root: &Path, day: u8) -> Vec<PathBuf> {
+            let dir = root.join(format!("day{}", day));
+            match (self, day) {
+                (Fixture::Example, _) => vec![dir.join("test.txt")],
+                // day5 keeps the starting stack configuration separately
+                (Fixture::Input, 5) => vec![dir.join("start.txt"), dir.join("input.txt")],
+                (Fixture::Input, _) => vec![dir.join("input.txt")],
+            }
+        }
+    }
+}
+
+pub mod answers {
+
+    use crate::check::fixtures::Fixture;
+    use common::Part;
+    use fs_err as fs;
+    use serde::Deserialize;
+    use std::error::Error;
+    use std::path::Path;
+
+    const ANSWERS_FILE: &str = "answers.toml";
+
+    /// Expected answers for both parts, a missing part is not checked.
+    #[derive(Deserialize, Default, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub struct Expected {
+        pub part1: Option<String>,
+        pub part2: Option<String>,
+    }
+
+    impl Expected {
+        pub fn get(&self, part: Part) -> Option<&str> {
+            match part {
+                Part::One => self.part1.as_deref(),
+                Part::Two => self.part2.as_deref(),
+            }
+        }
+    }
+
+    /// Contents of a day's answers.toml
+    #[derive(Deserialize, Default, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub struct DayAnswers {
+        #[serde(default)]
+        pub example: Expected,
+        #[serde(default)]
+        pub input: Expected,
+    }
+
+    impl DayAnswers {
+        // a day without an answers file has nothing to check
+        pub fn load(root: &Path, day: u8) -> Result<DayAnswers, Box<dyn Error>> {
+            let filename = root.join(format!("day{}", day)).join(ANSWERS_FILE);
+            if !filename.exists() {
+                return Ok(DayAnswers::default());
+            }
+            let contents = fs::read_to_string(&filename)?;
+            toml::from_str(&contents)
+                .map_err(|e| format!("invalid {}: {}", filename.display(), e).into())
+        }
+
+        pub fn get(&self, fixture: Fixture) -> &Expected {
+            match fixture {
+                Fixture::Example => &self.example,
+                Fixture::Input => &self.input,
+            }
+        }
+    }
+}
+
+pub mod report {
+
+    use crate::check::answers::DayAnswers;
+    use crate::check::fixtures::Fixture;
+    use common::{Answer, Part};
+    use std::error::Error;
+    use std::fmt;
+    use std::path::Path;
+    use std::time::Duration;
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Status {
+        Pass,
+        Fail,
+        // no expected answer has been recorded
+        Unchecked,
+    }
+
+    impl fmt::Display for Status {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Status::Pass => write!(f, "pass"),
+                Status::Fail => write!(f, "FAIL"),
+                Status::Unchecked => write!(f, "unchecked"),
+            }
+        }
+    }
+
+    pub struct Outcome {
+        pub day: u8,
+        pub part: Part,
+        pub fixture: Fixture,
+        // the answer, or the error message if the solution failed. Parts
+        // without an expected answer are not run.
+        pub answer: Option<Result<Answer, String>>,
+        pub expected: Option<String>,
+        // time taken to parse the input for this part
+        pub parse: Duration,
+    }
+
+    impl Outcome {
+        pub fn status(&self) -> Status {
+            match (&self.answer, &self.expected) {
+                (_, None) => Status::Unchecked,
+                (Some(Ok(answer)), Some(expected)) if answer.value == *expected => Status::Pass,
+                (_, Some(_)) => Status::Fail,
+            }
+        }
+    }
+
+    impl fmt::Display for Outcome {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "day {:>2} part {} {:<7} {:<9} ",
+                self.day,
+                self.part,
+                self.fixture,
+                self.status()
+            )?;
+            match &self.answer {
+                Some(Ok(answer)) => write!(f, "{}", answer.value.replace('\n', "\n    "))?,
+                Some(Err(e)) => write!(f, "error: {}", e)?,
+                None => write!(f, "-")?,
+            }
+            if self.status() == Status::Fail {
+                if let Some(expected) = &self.expected {
+                    write!(f, " (expected {})", expected.replace('\n', "\n    "))?;
+                }
+            }
+            Ok(())
+        }
+    }
+
+    // run both parts of a day on a fixture and compare them with the
+    // recorded answers
+    pub fn check(root: &Path, day: u8, fixture: Fixture) -> Result<Vec<Outcome>, Box<dyn Error>> {
+        let solver = match fixture {
+            Fixture::Example => crate::example_solver(day),
+            Fixture::Input => crate::solver(day),
+        }
+        .ok_or_else(|| format!("no solution for day {}!", day))?;
+
+        let answers = DayAnswers::load(root, day)?;
+        let input = crate::read_input(&fixture.files(root, day))?;
+
+        Ok([Part::One, Part::Two]
+            .into_iter()
+            .map(|part| {
+                let expected = answers.get(fixture).get(part).map(|x| x.to_string());
+                let timed = expected
+                    .as_ref()
+                    .map(|_| solver.timed(&input, &[part]).map_err(|e| e.to_string()));
+                let parse = match &timed {
+                    Some(Ok(timed)) => timed.parse,
+                    _ => Duration::ZERO,
+                };
+                let answer = timed.map(|timed| timed.map(|mut timed| timed.parts.remove(0)));
+                Outcome {
+                    day,
+                    part,
+                    fixture,
+                    answer,
+                    expected,
+                    parse,
+                }
+            })
+            .collect())
+    }
+}
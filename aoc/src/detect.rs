@@ -0,0 +1,272 @@
+pub mod choice {
+
+    use std::fmt;
+    use std::str::FromStr;
+
+    /// Day given on the command line, or `auto` to tell it from the input
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum DayChoice {
+        Day(u8),
+        Auto,
+    }
+
+    impl fmt::Display for DayChoice {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                DayChoice::Day(day) => write!(f, "{}", day),
+                DayChoice::Auto => write!(f, "auto"),
+            }
+        }
+    }
+
+    impl FromStr for DayChoice {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "auto" => Ok(DayChoice::Auto),
+                _ => s
+                    .parse::<u8>()
+                    .ok()
+                    .filter(|day| crate::DAYS.contains(day))
+                    .map(DayChoice::Day)
+                    .ok_or_else(|| format!("invalid day {}, expected 1 to 16 or auto!", s)),
+            }
+        }
+    }
+}
+
+pub mod signature {
+
+    use std::error::Error;
+
+    // share of the lines that must look right for an input to be taken as
+    // that of a day, a few odd lines are left for the parser to report
+    const MIN_SCORE: f64 = 0.9;
+
+    /// A day an input looks like, with the share of its lines that look
+    /// like those of the day
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub struct Candidate {
+        pub day: u8,
+        pub score: f64,
+    }
+
+    // what the lines of a day look like, and what the whole input and its
+    // non empty lines must hold
+    struct Signature {
+        day: u8,
+        line: fn(&str) -> bool,
+        whole: fn(&str, &[&str]) -> bool,
+    }
+
+    fn any(_: &str, _: &[&str]) -> bool {
+        true
+    }
+
+    fn int(s: &str) -> bool {
+        s.strip_prefix('-').unwrap_or(s).parse::<u64>().is_ok()
+    }
+
+    fn uint(s: &str) -> bool {
+        !s.starts_with('+') && s.parse::<u64>().is_ok()
+    }
+
+    // every line of the same length, more than one cell
+    fn rectangular(lines: &[&str]) -> bool {
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        width > 1 && lines.iter().all(|line| line.chars().count() == width)
+    }
+
+    fn range_pair(line: &str) -> bool {
+        let range = |s: &str| s.split_once('-').is_some_and(|(a, b)| uint(a) && uint(b));
+        line.split_once(',')
+            .is_some_and(|(a, b)| range(a) && range(b))
+    }
+
+    fn crate_drawing(line: &str) -> bool {
+        line.chars()
+            .all(|x| x == ' ' || x == '[' || x == ']' || x.is_ascii_alphanumeric())
+            && (line.contains('[') || line.trim().split(' ').all(|x| x.is_empty() || uint(x)))
+    }
+
+    fn crate_move(line: &str) -> bool {
+        let words: Vec<&str> = line.split(' ').collect();
+        matches!(
+            words.as_slice(),
+            ["move", n, "from", a, "to", b] if uint(n) && uint(a) && uint(b)
+        )
+    }
+
+    fn terminal(line: &str) -> bool {
+        line.starts_with("$ cd ")
+            || line == "$ ls"
+            || line.starts_with("dir ")
+            || line.split_once(' ').is_some_and(|(size, _)| uint(size))
+    }
+
+    fn monkey_note(line: &str) -> bool {
+        let line = line.trim_start();
+        [
+            "Monkey ",
+            "Starting items:",
+            "Operation:",
+            "Test:",
+            "If true:",
+            "If false:",
+        ]
+        .iter()
+        .any(|start| line.starts_with(start))
+    }
+
+    fn path(line: &str) -> bool {
+        line.split(" -> ").all(|pair| {
+            pair.split_once(',')
+                .is_some_and(|(x, y)| uint(x) && uint(y))
+        })
+    }
+
+    fn packet(line: &str) -> bool {
+        line.starts_with('[')
+            && line.ends_with(']')
+            && line
+                .chars()
+                .all(|x| x == '[' || x == ']' || x == ',' || x.is_ascii_digit())
+    }
+
+    fn heightmap(_: &str, lines: &[&str]) -> bool {
+        let count =
+            |symbol: char| -> usize { lines.iter().map(|line| line.matches(symbol).count()).sum() };
+        rectangular(lines) && count('S') == 1 && count('E') == 1
+    }
+
+    // the most telling signatures first, an input looking like several
+    // days is taken as the first of them
+    const SIGNATURES: [Signature; 16] = [
+        Signature {
+            day: 15,
+            line: |line| line.starts_with("Sensor at x="),
+            whole: any,
+        },
+        Signature {
+            day: 16,
+            line: |line| line.starts_with("Valve ") && line.contains(" has flow rate="),
+            whole: any,
+        },
+        Signature {
+            day: 11,
+            line: monkey_note,
+            whole: |_, lines| lines[0].starts_with("Monkey "),
+        },
+        Signature {
+            day: 7,
+            line: terminal,
+            whole: |_, lines| lines[0].starts_with("$ "),
+        },
+        Signature {
+            day: 10,
+            line: |line| line == "noop" || line.strip_prefix("addx ").is_some_and(int),
+            whole: any,
+        },
+        Signature {
+            day: 5,
+            line: |line| crate_move(line) || crate_drawing(line),
+            whole: |_, lines| lines.iter().any(|line| crate_move(line)),
+        },
+        Signature {
+            day: 14,
+            line: path,
+            whole: |_, lines| lines.iter().any(|line| line.contains(" -> ")),
+        },
+        Signature {
+            day: 13,
+            line: packet,
+            whole: any,
+        },
+        Signature {
+            day: 4,
+            line: range_pair,
+            whole: any,
+        },
+        Signature {
+            day: 2,
+            line: |line| matches!(line.as_bytes(), [b'A'..=b'C', b' ', b'X'..=b'Z']),
+            whole: any,
+        },
+        Signature {
+            day: 9,
+            line: |line| {
+                line.split_once(' ')
+                    .is_some_and(|(dir, n)| ["R", "L", "U", "D"].contains(&dir) && uint(n))
+            },
+            whole: any,
+        },
+        Signature {
+            day: 12,
+            line: |line| {
+                line.chars()
+                    .all(|x| x.is_ascii_lowercase() || x == 'S' || x == 'E')
+            },
+            whole: heightmap,
+        },
+        Signature {
+            day: 8,
+            line: |line| line.chars().all(|x| x.is_ascii_digit()),
+            // the rows of a map follow each other, unlike groups of numbers
+            whole: |input, lines| rectangular(lines) && !input.trim().contains("\n\n"),
+        },
+        Signature {
+            day: 1,
+            line: uint,
+            whole: any,
+        },
+        Signature {
+            day: 6,
+            line: |line| line.chars().all(|x| x.is_ascii_lowercase()),
+            whole: |_, lines| lines.len() == 1,
+        },
+        Signature {
+            day: 3,
+            line: |line| {
+                line.len().is_multiple_of(2) && line.chars().all(|x| x.is_ascii_alphabetic())
+            },
+            whole: any,
+        },
+    ];
+
+    /// Days the input looks like, the likeliest first
+    pub fn candidates(input: &str) -> Vec<Candidate> {
+        let lines: Vec<&str> = input
+            .lines()
+            .map(|line| line.trim_end_matches('\r'))
+            .filter(|line| !line.is_empty())
+            .collect();
+        if lines.is_empty() {
+            return Vec::new();
+        }
+
+        let mut candidates: Vec<Candidate> = SIGNATURES
+            .iter()
+            .filter(|signature| (signature.whole)(input, &lines))
+            .map(|signature| {
+                let matching = lines.iter().filter(|line| (signature.line)(line)).count();
+                Candidate {
+                    day: signature.day,
+                    score: matching as f64 / lines.len() as f64,
+                }
+            })
+            .filter(|candidate| candidate.score >= MIN_SCORE)
+            .collect();
+        // a stable sort keeps the order of the signatures for equal scores
+        candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+        candidates
+    }
+
+    /// Day the input is most likely for
+    pub fn detect(input: &str) -> Result<u8, Box<dyn Error>> {
+        candidates(input)
+            .first()
+            .map(|candidate| candidate.day)
+            .ok_or_else(|| "cannot tell which day the input is for!".into())
+    }
+}
@@ -0,0 +1,61 @@
+pub mod pictures {
+
+    use common::{Part, Solution};
+    use grid::Grid;
+    use std::error::Error;
+
+    /// Days whose state can be drawn
+    pub const DAYS: [u8; 4] = [8, 10, 12, 14];
+
+    // the state of the day as pictures, step by step where a solution has
+    // steps. Every `every` step is kept, and the last picture is always the
+    // final state.
+    pub fn frames(
+        day: u8,
+        part: Part,
+        input: &str,
+        every: usize,
+    ) -> Result<Vec<Grid<char>>, Box<dyn Error>> {
+        match day {
+            8 => {
+                let tree_matrix = day8::Day8.parse(input)?;
+                Ok(vec![match part {
+                    Part::One => day8::visibility_picture(&tree_matrix),
+                    Part::Two => day8::treescore_picture(&tree_matrix),
+                }])
+            }
+            // the screen is the same for both parts
+            10 => {
+                let instructions = day10::Day10::default().parse(input)?;
+                Ok(keep_every(day10::screens(&instructions), every))
+            }
+            12 => {
+                let signalgraph = day12::Day12.parse(input)?;
+                let route = match part {
+                    Part::One => day12::shortest_route(&signalgraph)?,
+                    Part::Two => day12::shortest_route_any_start(&signalgraph)?,
+                };
+                let frames = (1..=route.len())
+                    .map(|length| day12::route_picture(&signalgraph, &route, length))
+                    .collect();
+                Ok(keep_every(frames, every))
+            }
+            14 => {
+                let canvas = day14::Day14::default().parse(input)?;
+                Ok(day14::sand_frames(&canvas, part == Part::Two, every))
+            }
+            _ => Err(format!("no pictures for day {}!", day).into()),
+        }
+    }
+
+    fn keep_every(frames: Vec<Grid<char>>, every: usize) -> Vec<Grid<char>> {
+        let every = every.max(1);
+        let last = frames.len().saturating_sub(1);
+        frames
+            .into_iter()
+            .enumerate()
+            .filter(|(idx, _)| (idx + 1) % every == 0 || *idx == last)
+            .map(|(_, frame)| frame)
+            .collect()
+    }
+}
@@ -0,0 +1,570 @@
+pub mod shape {
+
+    /// Size of a generated input. What each value counts depends on the day,
+    /// see `inputs::describe`. Unset values default to about the size of
+    /// the actual puzzle input.
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Shape {
+        pub size: Option<usize>,
+        pub width: Option<usize>,
+        pub height: Option<usize>,
+    }
+
+    impl Shape {
+        pub fn size_or(&self, default: usize) -> usize {
+            self.size.unwrap_or(default)
+        }
+
+        pub fn width_or(&self, default: usize) -> usize {
+            self.width.unwrap_or(default)
+        }
+
+        pub fn height_or(&self, default: usize) -> usize {
+            self.height.unwrap_or(default)
+        }
+    }
+}
+
+pub mod inputs {
+
+    use crate::generate::shape::Shape;
+    use rand::seq::SliceRandom;
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::HashSet;
+    use std::error::Error;
+    use std::fmt::Write;
+
+    const LOWERCASE: &str = "abcdefghijklmnopqrstuvwxyz";
+    const UPPERCASE: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    // what the values of a `Shape` count for a day
+    pub fn describe(day: u8) -> Option<&'static str> {
+        match day {
+            1 => Some("size: elves"),
+            2 => Some("size: rounds"),
+            3 => Some("size: rucksacks, rounded up to whole groups of three"),
+            4 => Some("size: pairs of assignments"),
+            5 => Some("size: moves, width: stacks (2 to 9), height: crates per stack"),
+            6 => Some("size: length of the datastream"),
+            7 => Some("size: directories"),
+            8 => Some("width x height: map of trees"),
+            9 => Some("size: moves of the head"),
+            10 => Some("size: instructions, enough for 240 cycles at least"),
+            11 => Some("size: monkeys (4 to 15)"),
+            12 => Some("width x height: heightmap, width + height at least 27"),
+            13 => Some("size: pairs of packets"),
+            14 => Some("size: paths of rock, width x height: area around the source"),
+            15 => Some("size: sensors, width: extent of the field"),
+            16 => Some("size: valves with a flow rate"),
+            _ => None,
+        }
+    }
+
+    /// A valid puzzle input for the day. The same seed and shape always give
+    /// the same input.
+    pub fn generate(day: u8, shape: Shape, seed: u64) -> Result<String, Box<dyn Error>> {
+        let mut rng = ChaCha8Rng::seed_from_u64(seed);
+        let rng = &mut rng;
+        match day {
+            1 => Ok(calories(rng, shape)),
+            2 => Ok(strategy_guide(rng, shape)),
+            3 => Ok(rucksacks(rng, shape)),
+            4 => Ok(assignments(rng, shape)),
+            5 => crates(rng, shape),
+            6 => Ok(datastream(rng, shape)),
+            7 => Ok(terminal_output(rng, shape)),
+            8 => Ok(tree_map(rng, shape)),
+            9 => Ok(rope_moves(rng, shape)),
+            10 => Ok(program(rng, shape)),
+            11 => troop(rng, shape),
+            12 => heightmap(rng, shape),
+            13 => Ok(packets(rng, shape)),
+            14 => Ok(rock_paths(rng, shape)),
+            15 => Ok(sensor_field(rng, shape)),
+            16 => valve_scan(rng, shape),
+            _ => Err(format!("no generator for day {}!", day).into()),
+        }
+    }
+
+    fn pick(rng: &mut ChaCha8Rng, letters: &str) -> char {
+        let letters = letters.as_bytes();
+        letters[rng.gen_range(0..letters.len())] as char
+    }
+
+    fn calories(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        let elves: Vec<String> = (0..shape.size_or(250))
+            .map(|_| {
+                let items: Vec<String> = (0..rng.gen_range(1..=14))
+                    .map(|_| rng.gen_range(1000..=60000).to_string())
+                    .collect();
+                items.join("\n")
+            })
+            .collect();
+        elves.join("\n\n") + "\n"
+    }
+
+    fn strategy_guide(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        let mut input = String::new();
+        for _ in 0..shape.size_or(2500) {
+            let _ = writeln!(input, "{} {}", pick(rng, "ABC"), pick(rng, "XYZ"));
+        }
+        input
+    }
+
+    // each rucksack has one item in both compartments and each group of
+    // three has one item, the badge, in all of its rucksacks
+    fn rucksacks(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        let mut input = String::new();
+        for _ in 0..shape.size_or(300).div_ceil(3) {
+            let mut letters: Vec<char> = LOWERCASE.chars().chain(UPPERCASE.chars()).collect();
+            letters.shuffle(rng);
+            let badge = letters[0];
+            for elf in 0..3 {
+                let shared = letters[1 + elf];
+                // 16 letters only this rucksack uses, half for each compartment
+                let own = &letters[4 + 16 * elf..4 + 16 * (elf + 1)];
+                let (left_items, right_items) = own.split_at(8);
+
+                let length = rng.gen_range(4..=16);
+                let mut left: Vec<char> = (0..length - 1)
+                    .map(|_| *left_items.choose(rng).expect("compartment has items"))
+                    .collect();
+                let mut right: Vec<char> = (0..length - 1)
+                    .map(|_| *right_items.choose(rng).expect("compartment has items"))
+                    .collect();
+                left.push(shared);
+                right.push(shared);
+                // the badge replaces an item of one of the compartments
+                let compartment = if rng.gen_bool(0.5) {
+                    &mut left
+                } else {
+                    &mut right
+                };
+                compartment[0] = badge;
+                left.shuffle(rng);
+                right.shuffle(rng);
+                let _ = writeln!(
+                    input,
+                    "{}{}",
+                    left.iter().collect::<String>(),
+                    right.iter().collect::<String>()
+                );
+            }
+        }
+        input
+    }
+
+    fn assignments(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        let mut input = String::new();
+        let range = |rng: &mut ChaCha8Rng| {
+            let start = rng.gen_range(1..=99);
+            (start, rng.gen_range(start..=99))
+        };
+        for _ in 0..shape.size_or(1000) {
+            let (a, b) = range(rng);
+            let (c, d) = range(rng);
+            let _ = writeln!(input, "{}-{},{}-{}", a, b, c, d);
+        }
+        input
+    }
+
+    // Moves never empty a stack, so that every stack has a crate on top.
+    // There are more crates than stacks, so some stack can always be moved
+    // from.
+    fn crates(rng: &mut ChaCha8Rng, shape: Shape) -> Result<String, Box<dyn Error>> {
+        let num_stacks = shape.width_or(9);
+        if !(2..=9).contains(&num_stacks) {
+            return Err("day 5 takes 2 to 9 stacks!".into());
+        }
+        let height = shape.height_or(8).max(2);
+        let mut stacks: Vec<Vec<char>> = (0..num_stacks)
+            .map(|idx| {
+                let lowest = if idx == 0 { 2 } else { 1 };
+                (0..rng.gen_range(lowest..=height))
+                    .map(|_| pick(rng, UPPERCASE))
+                    .collect()
+            })
+            .collect();
+
+        let tallest = stacks.iter().map(|x| x.len()).max().unwrap_or(0);
+        let mut input = String::new();
+        for level in (0..tallest).rev() {
+            let row: Vec<String> = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(elem) => format!("[{}]", elem),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            let _ = writeln!(input, "{}", row.join(" "));
+        }
+        let labels: Vec<String> = (1..=num_stacks).map(|x| format!(" {} ", x)).collect();
+        let _ = writeln!(input, "{}", labels.join(" "));
+        input.push('\n');
+
+        for _ in 0..shape.size_or(500) {
+            let candidates: Vec<usize> = (0..num_stacks).filter(|x| stacks[*x].len() > 1).collect();
+            let src = *candidates.choose(rng).expect("a stack has two crates");
+            let mut dst = rng.gen_range(0..num_stacks - 1);
+            if dst >= src {
+                dst += 1;
+            }
+            let quantity = rng.gen_range(1..stacks[src].len());
+            let split = stacks[src].len() - quantity;
+            let moved = stacks[src].split_off(split);
+            stacks[dst].extend(moved);
+            let _ = writeln!(input, "move {} from {} to {}", quantity, src + 1, dst + 1);
+        }
+        Ok(input)
+    }
+
+    // mostly a few repeating letters, with a run of 14 different letters
+    // towards the end for both markers
+    fn datastream(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        let length = shape.size_or(4096).max(14);
+        let mut stream: Vec<char> = (0..length).map(|_| pick(rng, "abc")).collect();
+        let mut marker: Vec<char> = LOWERCASE.chars().collect();
+        marker.shuffle(rng);
+        let start = rng.gen_range((length - 14) * 3 / 4..=length - 14);
+        stream[start..start + 14].copy_from_slice(&marker[..14]);
+        stream.into_iter().collect::<String>() + "\n"
+    }
+
+    // A depth first walk of a random directory tree. The files take up
+    // between 45000000 and 65000000 of the 70000000 disk, so that part 2
+    // needs to delete a directory.
+    fn terminal_output(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        // subdirectories of each directory, the root is directory 0
+        let num_dirs = shape.size_or(200).max(1);
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); num_dirs];
+        for dir in 1..num_dirs {
+            children[rng.gen_range(0..dir)].push(dir);
+        }
+
+        let mut files: Vec<Vec<u64>> = (0..num_dirs)
+            .map(|_| {
+                (0..rng.gen_range(1..=5))
+                    .map(|_| rng.gen_range(1000..=300000))
+                    .collect()
+            })
+            .collect();
+        let total: u64 = files.iter().flatten().sum();
+        let used: u64 = rng.gen_range(45000000..=65000000);
+        for size in files.iter_mut().flatten() {
+            *size = (*size * used / total).max(1);
+        }
+
+        let mut input = String::from("$ cd /\n");
+        list_directory(rng, &children, &files, 0, &mut input);
+        input
+    }
+
+    fn list_directory(
+        rng: &mut ChaCha8Rng,
+        children: &[Vec<usize>],
+        files: &[Vec<u64>],
+        dir: usize,
+        input: &mut String,
+    ) {
+        input.push_str("$ ls\n");
+        for child in &children[dir] {
+            let _ = writeln!(input, "dir d{}", child);
+        }
+        for (file, size) in files[dir].iter().enumerate() {
+            let ext = ["", ".txt", ".dat", ".log"]
+                .choose(rng)
+                .expect("extensions");
+            let _ = writeln!(input, "{} f{}{}", size, file, ext);
+        }
+        for child in &children[dir] {
+            let _ = writeln!(input, "$ cd d{}", child);
+            list_directory(rng, children, files, *child, input);
+            input.push_str("$ cd ..\n");
+        }
+    }
+
+    fn tree_map(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        let mut input = String::new();
+        for _ in 0..shape.height_or(99).max(1) {
+            let row: String = (0..shape.width_or(99).max(1))
+                .map(|_| pick(rng, "0123456789"))
+                .collect();
+            let _ = writeln!(input, "{}", row);
+        }
+        input
+    }
+
+    fn rope_moves(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        let mut input = String::new();
+        for _ in 0..shape.size_or(2000) {
+            let _ = writeln!(input, "{} {}", pick(rng, "RLUD"), rng.gen_range(1..=20));
+        }
+        input
+    }
+
+    fn program(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        let mut input = String::new();
+        let mut cycles = 0;
+        let mut register: i32 = 1;
+        let mut count = 0;
+        while count < shape.size_or(146) || cycles < 240 {
+            if rng.gen_bool(0.3) {
+                input.push_str("noop\n");
+                cycles += 1;
+            } else {
+                // keep the sprite around the screen
+                let v = if register < 0 {
+                    rng.gen_range(1..=10)
+                } else if register > 39 {
+                    rng.gen_range(-10..=-1)
+                } else {
+                    rng.gen_range(-10..=10)
+                };
+                register += v;
+                let _ = writeln!(input, "addx {}", v);
+                cycles += 2;
+            }
+            count += 1;
+        }
+        input
+    }
+
+    const PRIMES: [u64; 15] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47];
+
+    // The worry levels stay in range: the product of the divisors fits in
+    // 64 bits so that squaring a worry level fits in 128 bits, only monkey
+    // 0 squares and no monkey throws to it, and the other multiplications
+    // are cancelled by the relief of part 1.
+    fn troop(rng: &mut ChaCha8Rng, shape: Shape) -> Result<String, Box<dyn Error>> {
+        let num_monkeys = shape.size_or(8);
+        if !(4..=PRIMES.len()).contains(&num_monkeys) {
+            return Err(format!("day 11 takes 4 to {} monkeys!", PRIMES.len()).into());
+        }
+        let mut divisors = PRIMES[..num_monkeys].to_vec();
+        divisors.shuffle(rng);
+
+        let mut monkeys: Vec<String> = Vec::with_capacity(num_monkeys);
+        for (id, divisor) in divisors.iter().enumerate() {
+            let items: Vec<String> = (0..rng.gen_range(1..=8))
+                .map(|_| rng.gen_range(50..=99).to_string())
+                .collect();
+            let operation = match (id, rng.gen_range(0..3)) {
+                (0, _) => "old * old".to_string(),
+                (_, 0) => format!("old * {}", rng.gen_range(2..=3)),
+                (_, _) => format!("old + {}", rng.gen_range(1..=8)),
+            };
+            let mut targets: Vec<usize> = (1..num_monkeys).filter(|x| *x != id).collect();
+            targets.shuffle(rng);
+            monkeys.push(format!(
+                "Monkey {}:
+  Starting items: {}
+  Operation: new = {}
+  Test: divisible by {}
+    If true: throw to monkey {}
+    If false: throw to monkey {}
+",
+                id,
+                items.join(", "),
+                operation,
+                divisor,
+                targets[0],
+                targets[1]
+            ));
+        }
+        Ok(monkeys.join("\n"))
+    }
+
+    // The elevation rises evenly from S in the top left corner to E in the
+    // bottom right corner, with random dips. A random path through the map
+    // is left without dips, so that E can always be reached.
+    fn heightmap(rng: &mut ChaCha8Rng, shape: Shape) -> Result<String, Box<dyn Error>> {
+        let width = shape.width_or(150);
+        let height = shape.height_or(41);
+        if width + height < 27 {
+            return Err("day 12 needs a heightmap with width + height of at least 27!".into());
+        }
+        let steps = width + height - 2;
+        let elevation = |x: usize, y: usize| (x + y) * 25 / steps;
+
+        let mut map: Vec<Vec<u8>> = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| elevation(x, y).saturating_sub(rng.gen_range(0..=3)) as u8)
+                    .collect()
+            })
+            .collect();
+
+        let (mut x, mut y) = (0, 0);
+        map[y][x] = elevation(x, y) as u8;
+        while (x, y) != (width - 1, height - 1) {
+            if y == height - 1 || (x < width - 1 && rng.gen_bool(0.5)) {
+                x += 1;
+            } else {
+                y += 1;
+            }
+            map[y][x] = elevation(x, y) as u8;
+        }
+
+        let mut input = String::new();
+        for (y, row) in map.iter().enumerate() {
+            for (x, elem) in row.iter().enumerate() {
+                input.push(match (x, y) {
+                    (0, 0) => 'S',
+                    _ if (x, y) == (width - 1, height - 1) => 'E',
+                    _ => (b'a' + elem) as char,
+                });
+            }
+            input.push('\n');
+        }
+        Ok(input)
+    }
+
+    fn packet(rng: &mut ChaCha8Rng, depth: u32) -> String {
+        let elems: Vec<String> = (0..rng.gen_range(0..=5))
+            .map(|_| {
+                if depth < 4 && rng.gen_bool(0.3) {
+                    packet(rng, depth + 1)
+                } else {
+                    rng.gen_range(0..=10).to_string()
+                }
+            })
+            .collect();
+        format!("[{}]", elems.join(","))
+    }
+
+    // packets are all different, so that every pair has an order
+    fn packets(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        let mut seen: HashSet<String> = HashSet::from(["[[2]]".to_string(), "[[6]]".to_string()]);
+        let mut pairs: Vec<String> = Vec::new();
+        while pairs.len() < shape.size_or(150) * 2 {
+            let packet = packet(rng, 0);
+            if seen.insert(packet.clone()) {
+                pairs.push(packet);
+            }
+        }
+        let pairs: Vec<String> = pairs.chunks(2).map(|pair| pair.join("\n")).collect();
+        pairs.join("\n\n") + "\n"
+    }
+
+    // paths of horizontal and vertical lines below the source at 500,0
+    fn rock_paths(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        let half_width = (shape.width_or(100) / 2).max(1) as u32;
+        let height = shape.height_or(170).max(2) as u32;
+        let mut input = String::new();
+        for _ in 0..shape.size_or(150) {
+            let mut x = rng.gen_range(500 - half_width..=500 + half_width);
+            let mut y = rng.gen_range(1..=height);
+            let mut points = vec![format!("{},{}", x, y)];
+            for segment in 0..rng.gen_range(1..=5) {
+                if segment % 2 == 0 {
+                    x = rng.gen_range(
+                        x.saturating_sub(8).max(500 - half_width)..=(x + 8).min(500 + half_width),
+                    );
+                } else {
+                    y = rng.gen_range(y.saturating_sub(8).max(1)..=(y + 8).min(height));
+                }
+                points.push(format!("{},{}", x, y));
+            }
+            let _ = writeln!(input, "{}", points.join(" -> "));
+        }
+        input
+    }
+
+    // sensors and beacons are scattered over the field, each sensor
+    // reporting the beacon closest to it
+    fn sensor_field(rng: &mut ChaCha8Rng, shape: Shape) -> String {
+        let extent = shape.width_or(4000000).max(1) as i64;
+        let num_sensors = shape.size_or(30);
+        let point = |rng: &mut ChaCha8Rng| (rng.gen_range(0..=extent), rng.gen_range(0..=extent));
+        let sensors: Vec<(i64, i64)> = (0..num_sensors).map(|_| point(rng)).collect();
+        let beacons: Vec<(i64, i64)> = (0..num_sensors.div_ceil(2).max(1))
+            .map(|_| point(rng))
+            .collect();
+
+        let mut input = String::new();
+        for sensor in sensors {
+            let distance =
+                |beacon: &(i64, i64)| sensor.0.abs_diff(beacon.0) + sensor.1.abs_diff(beacon.1);
+            let beacon = beacons
+                .iter()
+                .filter(|beacon| **beacon != sensor)
+                .min_by_key(|beacon| distance(beacon));
+            if let Some(beacon) = beacon {
+                let _ = writeln!(
+                    input,
+                    "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                    sensor.0, sensor.1, beacon.0, beacon.1
+                );
+            }
+        }
+        input
+    }
+
+    // AA and the valves with a flow rate are joined by tunnels through
+    // valves without one. Every valve can be reached from AA.
+    fn valve_scan(rng: &mut ChaCha8Rng, shape: Shape) -> Result<String, Box<dyn Error>> {
+        let num_active = shape.size_or(15);
+        let num_valves = 1 + num_active * 4;
+        if num_valves > 26 * 26 {
+            return Err("day 16 has too few valve names for that many valves!".into());
+        }
+        let mut names: Vec<String> = UPPERCASE
+            .chars()
+            .flat_map(|a| UPPERCASE.chars().map(move |b| format!("{}{}", a, b)))
+            .filter(|name| name != "AA")
+            .collect();
+        names.shuffle(rng);
+        names.truncate(num_valves - 1);
+        names.insert(0, "AA".to_string());
+
+        let flow_rates: Vec<u32> = (0..num_valves)
+            .map(|idx| {
+                if idx > 0 && idx <= num_active {
+                    rng.gen_range(1..=25)
+                } else {
+                    0
+                }
+            })
+            .collect();
+
+        // a random spanning tree and a few more tunnels
+        let mut tunnels: Vec<Vec<usize>> = vec![Vec::new(); num_valves];
+        let mut order: Vec<usize> = (1..num_valves).collect();
+        order.shuffle(rng);
+        let mut joined: Vec<usize> = vec![0];
+        for valve in order {
+            let other = *joined.choose(rng).expect("AA is joined");
+            tunnels[valve].push(other);
+            tunnels[other].push(valve);
+            joined.push(valve);
+        }
+        for _ in 0..num_valves / 4 {
+            let a = rng.gen_range(0..num_valves);
+            let b = rng.gen_range(0..num_valves);
+            if a != b && !tunnels[a].contains(&b) {
+                tunnels[a].push(b);
+                tunnels[b].push(a);
+            }
+        }
+
+        let mut input = String::new();
+        for valve in 0..num_valves {
+            let others: Vec<&str> = tunnels[valve].iter().map(|x| names[*x].as_str()).collect();
+            let lead = if others.len() == 1 {
+                "tunnel leads to valve"
+            } else {
+                "tunnels lead to valves"
+            };
+            let _ = writeln!(
+                input,
+                "Valve {} has flow rate={}; {} {}",
+                names[valve],
+                flow_rates[valve],
+                lead,
+                others.join(", ")
+            );
+        }
+        Ok(input)
+    }
+}
@@ -0,0 +1,195 @@
+pub mod hash {
+
+    use sha2::{Digest, Sha256};
+
+    // hex sha256 of the puzzle input, the same input always has the same
+    // hash whatever the files it was read from
+    pub fn input_hash(input: &str) -> String {
+        Sha256::digest(input.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+pub mod store {
+
+    use crate::check::answers::DayAnswers;
+    use crate::check::fixtures::Fixture;
+    use crate::ledger::hash::input_hash;
+    use common::Part;
+    use fs_err as fs;
+    use serde::{Deserialize, Serialize};
+    use std::error::Error;
+    use std::fmt;
+    use std::path::{Path, PathBuf};
+
+    const LEDGER_FILE: &str = "answer_ledger.toml";
+
+    /// Answer of one part of a day for one input
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+    #[serde(deny_unknown_fields)]
+    pub struct Entry {
+        pub day: u8,
+        pub part: u8,
+        // see `input_hash`
+        pub input: String,
+        pub answer: String,
+        // accepted as the right answer, later runs are checked against it
+        #[serde(default)]
+        pub verified: bool,
+        // seconds since the unix epoch of the run that gave the answer
+        pub timestamp: u64,
+    }
+
+    /// How a new answer compares with the one already recorded for the input
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum Verdict {
+        // first answer for the input
+        New,
+        Same { verified: bool },
+        Differs { recorded: String, verified: bool },
+    }
+
+    impl Verdict {
+        // a run disagreeing with a verified answer is an error
+        pub fn is_mismatch(&self) -> bool {
+            matches!(self, Verdict::Differs { verified: true, .. })
+        }
+    }
+
+    impl fmt::Display for Verdict {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Verdict::New => write!(f, "new answer"),
+                Verdict::Same { verified: true } => write!(f, "matches the verified answer"),
+                Verdict::Same { verified: false } => write!(f, "matches the recorded answer"),
+                Verdict::Differs {
+                    recorded,
+                    verified: true,
+                } => write!(f, "differs from the verified answer {}", recorded),
+                Verdict::Differs {
+                    recorded,
+                    verified: false,
+                } => write!(f, "differs from the recorded answer {}", recorded),
+            }
+        }
+    }
+
+    // the answers of the actual input of every day in its answers.toml
+    fn golden(root: &Path) -> Result<Vec<Entry>, Box<dyn Error>> {
+        let mut golden: Vec<Entry> = Vec::new();
+        for day in crate::DAYS {
+            let answers = DayAnswers::load(root, day)?;
+            let expected = answers.get(Fixture::Input);
+            let parts = [Part::One, Part::Two].map(|part| (part, expected.get(part)));
+            if parts.iter().all(|(_, answer)| answer.is_none()) {
+                continue;
+            }
+            let input = crate::read_input(&Fixture::Input.files(root, day))?;
+            let hash = input_hash(&input);
+            for (part, answer) in parts {
+                if let Some(answer) = answer {
+                    golden.push(Entry {
+                        day,
+                        part: part.into(),
+                        input: hash.clone(),
+                        answer: answer.to_string(),
+                        verified: true,
+                        timestamp: 0,
+                    });
+                }
+            }
+        }
+        Ok(golden)
+    }
+
+    /// Answers given so far, one entry per day, part and input
+    #[derive(Serialize, Deserialize, Default, Debug)]
+    #[serde(deny_unknown_fields)]
+    pub struct Ledger {
+        #[serde(default)]
+        pub answer: Vec<Entry>,
+        // verified answers of the actual inputs, taken from the answers.toml
+        // of each day rather than kept in the ledger
+        #[serde(skip)]
+        pub golden: Vec<Entry>,
+    }
+
+    impl Ledger {
+        pub fn filename(root: &Path) -> PathBuf {
+            root.join(LEDGER_FILE)
+        }
+
+        // there is no ledger before the first answer
+        pub fn load(root: &Path) -> Result<Ledger, Box<dyn Error>> {
+            let filename = Ledger::filename(root);
+            let mut ledger: Ledger = if filename.exists() {
+                let contents = fs::read_to_string(&filename)?;
+                toml::from_str(&contents)
+                    .map_err(|e| format!("invalid {}: {}", filename.display(), e))?
+            } else {
+                Ledger::default()
+            };
+            ledger.golden = golden(root)?;
+            Ok(ledger)
+        }
+
+        pub fn save(&self, root: &Path) -> Result<(), Box<dyn Error>> {
+            fs::write(Ledger::filename(root), toml::to_string(self)?)?;
+            Ok(())
+        }
+
+        /// Whether the answer for the input is that of answers.toml, which
+        /// no run replaces
+        pub fn is_golden(&self, day: u8, part: u8, input: &str) -> bool {
+            self.golden
+                .iter()
+                .any(|entry| entry.day == day && entry.part == part && entry.input == input)
+        }
+
+        // the answer of answers.toml comes first for an actual input
+        pub fn find(&self, day: u8, part: u8, input: &str) -> Option<&Entry> {
+            self.golden
+                .iter()
+                .chain(&self.answer)
+                .find(|entry| entry.day == day && entry.part == part && entry.input == input)
+        }
+
+        pub fn verdict(&self, day: u8, part: u8, input: &str, answer: &str) -> Verdict {
+            match self.find(day, part, input) {
+                None => Verdict::New,
+                Some(entry) if entry.answer == answer => Verdict::Same {
+                    verified: entry.verified,
+                },
+                Some(entry) => Verdict::Differs {
+                    recorded: entry.answer.clone(),
+                    verified: entry.verified,
+                },
+            }
+        }
+
+        // keep the answer of a run. A verified answer is only replaced by
+        // another verified one, an unverified one by the latest run.
+        pub fn record(&mut self, run: Entry) -> Verdict {
+            let verdict = self.verdict(run.day, run.part, &run.input, &run.answer);
+            // answers.toml is changed by hand
+            if self.is_golden(run.day, run.part, &run.input) {
+                return verdict;
+            }
+            let position = self.answer.iter().position(|entry| {
+                entry.day == run.day && entry.part == run.part && entry.input == run.input
+            });
+            match position {
+                None => self.answer.push(run),
+                Some(idx) => {
+                    let entry = &mut self.answer[idx];
+                    if run.verified || !entry.verified {
+                        *entry = run;
+                    }
+                }
+            }
+            verdict
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use common::{Part, Solver, Timed};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+pub mod all;
+pub mod bench;
+pub mod check;
+pub mod detect;
+pub mod draw;
+pub mod generate;
+pub mod ledger;
+pub mod output;
+pub mod watch;
+
+pub const DAYS: std::ops::RangeInclusive<u8> = 1..=16;
+
+// solution of each day, configured with the parameters of the actual puzzle
+pub fn solver(day: u8) -> Option<Box<dyn Solver>> {
+    match day {
+        1 => Some(Box::new(day1::Day1::default())),
+        2 => Some(Box::new(day2::Day2::default())),
+        3 => Some(Box::new(day3::Day3)),
+        4 => Some(Box::new(day4::Day4)),
+        5 => Some(Box::new(day5::Day5)),
+        6 => Some(Box::new(day6::Day6::default())),
+        7 => Some(Box::new(day7::Day7)),
+        8 => Some(Box::new(day8::Day8)),
+        9 => Some(Box::new(day9::Day9::default())),
+        10 => Some(Box::new(day10::Day10::default())),
+        11 => Some(Box::new(day11::Day11::default())),
+        12 => Some(Box::new(day12::Day12)),
+        13 => Some(Box::new(day13::Day13)),
+        14 => Some(Box::new(day14::Day14::default())),
+        15 => Some(Box::new(day15::Day15::default())),
+        16 => Some(Box::new(day16::Day16::default())),
+        _ => None,
+    }
+}
+
+// the worked examples use smaller parameters than the actual puzzle
+pub fn example_solver(day: u8) -> Option<Box<dyn Solver>> {
+    match day {
+        15 => Some(Box::new(day15::Day15 {
+            yloc: 10,
+            ymin: 0,
+            ymax: 20,
+        })),
+        _ => solver(day),
+    }
+}
+
+// the dayN directories holding the inputs and answers
+pub fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("aoc crate is inside the workspace")
+        .to_path_buf()
+}
+
+// each input file is a section of the puzzle input, see
+// `common::input::read_sections`. `-` reads a section from stdin.
+pub fn read_input<P: AsRef<Path>>(inputs: &[P]) -> Result<String, Box<dyn Error>> {
+    common::input::read_sections(inputs)
+}
+
+pub fn run(day: u8, part: Part, inputs: &[String]) -> Result<Timed, Box<dyn Error>> {
+    solve(day, part, &read_input(inputs)?)
+}
+
+pub fn solve(day: u8, part: Part, input: &str) -> Result<Timed, Box<dyn Error>> {
+    let solver = solver(day).ok_or_else(|| format!("no solution for day {}!", day))?;
+    solver.timed(input, &[part])
+}
@@ -0,0 +1,722 @@
+use aoc::all::jobs::{jobs, Job, SLOW};
+use aoc::all::outcome::run_all;
+use aoc::all::table::render;
+use aoc::bench::history::{History, Run};
+use aoc::bench::report::Comparison;
+use aoc::bench::timing::bench;
+use aoc::check::fixtures::Fixture;
+use aoc::check::report::{check, Status};
+use aoc::detect::choice::DayChoice;
+use aoc::detect::signature::{candidates, detect};
+use aoc::draw::pictures::frames;
+use aoc::generate::inputs::{describe, generate};
+use aoc::generate::shape::Shape;
+use aoc::ledger::hash::input_hash;
+use aoc::ledger::store::{Entry, Ledger, Verdict};
+use aoc::output::format::Format;
+use aoc::output::record::{self, Record};
+use aoc::watch::diff::diff;
+use aoc::watch::files::{sources, Snapshot};
+use aoc::watch::runner::run_built;
+use clap::{ArgAction, Parser, Subcommand};
+use common::input::STDIN;
+use common::profile::{self, CountingAllocator};
+use common::verbosity::Verbosity;
+use common::Part;
+use log::{error, info, warn};
+use picture::{Encoding, Frames, Palette};
+use std::error::Error;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+// counts the allocations of each phase for --profile
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runner for all the advent of code 2022 solutions
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+
+    /// only report errors
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// report each step of a solution to stderr, twice to report every
+    /// intermediate state
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// run one part of a day's solution
+    Run {
+        /// day of the puzzle, or auto to tell it from the input
+        day: DayChoice,
+
+        /// part of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// input file(s), day5 takes the starting configuration and the
+        /// moves. `-` reads from stdin
+        #[arg(required = true)]
+        input: Vec<String>,
+
+        /// print just the answer, or a JSON record with the timings and
+        /// any secondary values
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// record the answer as the right one for this input in
+        /// answer_ledger.toml, later runs giving another answer fail
+        #[arg(long)]
+        verify: bool,
+
+        /// record the answer in answer_ledger.toml, without it the answer
+        /// is only compared with the ledger
+        #[arg(long)]
+        save: bool,
+
+        /// report the time and allocations of each phase of the solution
+        /// to stderr
+        #[arg(long)]
+        profile: bool,
+    },
+
+    /// run one part of a day's solution again whenever its input files or
+    /// the sources of the solution change, showing how the answer changed
+    Watch {
+        /// day of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=16))]
+        day: u8,
+
+        /// part of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// input file(s), day5 takes the starting configuration and the
+        /// moves
+        #[arg(required = true)]
+        input: Vec<String>,
+
+        /// milliseconds between looking for changes
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+
+    /// draw the state of day 8, 10, 12 or 14 as an image, or as numbered
+    /// frames to animate
+    Draw {
+        /// day of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=16))]
+        day: u8,
+
+        /// part of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+
+        /// input file(s), `-` reads from stdin
+        #[arg(required = true)]
+        input: Vec<String>,
+
+        /// ppm or svg
+        #[arg(long, default_value = "ppm")]
+        format: Encoding,
+
+        /// file the final state is written to, stdout if not given
+        #[arg(short, long, conflicts_with = "frames")]
+        output: Option<PathBuf>,
+
+        /// directory to write every step to, as frame-00001.ppm, ...
+        #[arg(long)]
+        frames: Option<PathBuf>,
+
+        /// only keep every n-th step in the frames
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u64).range(1..))]
+        every: u64,
+
+        /// pixels per cell
+        #[arg(long, default_value_t = 4, value_parser = clap::value_parser!(u64).range(1..=64))]
+        scale: u64,
+
+        /// colours of the symbols of the drawing, replacing the default
+        /// ones, e.g. `#=ff0000,.=000000`
+        #[arg(long)]
+        palette: Option<Palette>,
+    },
+
+    /// check the answers of each day against the recorded answers.toml
+    Check {
+        /// also check the actual puzzle inputs, not just the examples
+        #[arg(long)]
+        input: bool,
+
+        /// print a line per part, or a JSON record per part
+        #[arg(long, value_enum, default_value_t = Format::Text)]
+        format: Format,
+
+        /// days to check, all days if none are given
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=16))]
+        days: Vec<u8>,
+    },
+
+    /// tell which day an input is for
+    Detect {
+        /// input file(s), `-` reads from stdin
+        #[arg(required = true)]
+        input: Vec<String>,
+
+        /// list every day the input looks like, with the share of its lines
+        /// that look right
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// run every part of every day on its actual input side by side, and
+    /// compare the answers with the ledger
+    All {
+        /// parts to leave out, as 1.2
+        #[arg(long)]
+        skip: Vec<Job>,
+
+        /// also run the parts taking far longer than the others, day15's
+        /// part 2
+        #[arg(long)]
+        slow: bool,
+
+        /// number of parts run at the same time, one per core by default
+        #[arg(short, long)]
+        jobs: Option<usize>,
+
+        /// days to run, all days if none are given
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=16))]
+        days: Vec<u8>,
+    },
+
+    /// print a random but valid puzzle input, e.g. to see how a solution
+    /// scales. The same seed and sizes always give the same input.
+    Generate {
+        /// day of the puzzle
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=16))]
+        day: u8,
+
+        #[arg(short, long, default_value_t = 0)]
+        seed: u64,
+
+        /// number of elves, moves, monkeys, valves, ... depending on the
+        /// day, see --describe
+        #[arg(long)]
+        size: Option<usize>,
+
+        /// width of a map, or number of stacks for day5
+        #[arg(long)]
+        width: Option<usize>,
+
+        /// height of a map, or crates per stack for day5
+        #[arg(long)]
+        height: Option<usize>,
+
+        /// print what the sizes count for the day instead
+        #[arg(long)]
+        describe: bool,
+    },
+
+    /// time parsing and each part of every day on its actual input, and
+    /// compare with the previous runs kept in bench_history.toml
+    Bench {
+        /// number of times each day is run, the median time is reported
+        #[arg(short, long, default_value_t = 10)]
+        iterations: u32,
+
+        /// label stored with the run in the history, e.g. what changed
+        #[arg(short, long)]
+        label: Option<String>,
+
+        /// do not add this run to the history
+        #[arg(long)]
+        no_save: bool,
+
+        /// days to time, all days if none are given
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=16))]
+        days: Vec<u8>,
+    },
+}
+
+fn main() {
+    let args = Args::parse();
+    let verbosity = Verbosity::from_flags(args.quiet, args.verbose);
+    verbosity.init();
+
+    match args.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+            verify,
+            save,
+            profile: show_profile,
+        } => {
+            let part = Part::try_from(part).expect("part is validated by the parser");
+            // stdin may be anything, its answers are not worth keeping
+            if (save || verify) && input.iter().any(|filename| filename == STDIN) {
+                eprintln!("Application error: cannot record the answer of stdin!");
+                process::exit(1);
+            }
+            let input = match aoc::read_input(&input) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            };
+            let day = match choose_day(day, &input) {
+                Ok(day) => day,
+                Err(e) => {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            };
+            let (timed, phases) = profile::record(|| aoc::solve(day, part, &input));
+            if show_profile {
+                eprint!("{}", profile::report(&phases));
+            }
+            let timed = match timed {
+                Ok(timed) => timed,
+                Err(e) => {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            };
+            let answer = &timed.parts[0];
+            let printed = match format {
+                Format::Text => Ok(answer.value.clone()),
+                Format::Json => Record::new(day, timed.parse, answer).to_json(),
+            };
+            match printed {
+                Ok(printed) => println!("{}", printed),
+                Err(e) => {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            }
+
+            let entry = Entry {
+                day,
+                part: part.into(),
+                input: input_hash(&input),
+                answer: answer.value.clone(),
+                verified: verify,
+                timestamp: now(),
+            };
+            match compare_with_ledger(&aoc::workspace_root(), entry, save || verify) {
+                Ok((verdict, _)) if verdict.is_mismatch() && !verify => {
+                    error!("day {} part {}: answer {}!", day, part, verdict);
+                    process::exit(1);
+                }
+                // answers.toml is not overridden by a verified run
+                Ok((Verdict::Differs { recorded, .. }, true)) if verify => {
+                    error!(
+                        "day {} part {}: kept the answer {} of answers.toml, {} is not verified!",
+                        day, part, recorded, answer.value
+                    );
+                    process::exit(1);
+                }
+                Ok((_, true)) if verify => eprintln!(
+                    "day {} part {}: {} is already verified in answers.toml",
+                    day, part, answer.value
+                ),
+                Ok((verdict @ Verdict::Differs { .. }, _)) => {
+                    warn!("day {} part {}: answer {}", day, part, verdict);
+                    if verify {
+                        eprintln!("day {} part {}: verified {}", day, part, answer.value);
+                    }
+                }
+                Ok((verdict, _)) => {
+                    info!("day {} part {}: {}", day, part, verdict);
+                    if verify {
+                        eprintln!("day {} part {}: verified {}", day, part, answer.value);
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Watch {
+            day,
+            part,
+            input,
+            interval,
+        } => {
+            if input.iter().any(|filename| filename == "-") {
+                eprintln!("Application error: cannot watch stdin!");
+                process::exit(1);
+            }
+            let part = Part::try_from(part).expect("part is validated by the parser");
+            let root = aoc::workspace_root();
+            let mut watched: Vec<PathBuf> = input.iter().map(PathBuf::from).collect();
+            watched.extend(sources(&root, day));
+
+            let mut snapshot = Snapshot::take(&watched);
+            let mut previous: Option<String> = None;
+            loop {
+                match run_built(&root, day, part, &input, verbosity) {
+                    Ok(answer) => {
+                        match &previous {
+                            None => println!("day {} part {}: {}", day, part, answer),
+                            Some(previous) if *previous == answer => {
+                                println!("day {} part {}: unchanged", day, part)
+                            }
+                            Some(previous) => {
+                                println!("day {} part {}: changed", day, part);
+                                for line in diff(previous, &answer) {
+                                    println!("{}", line);
+                                }
+                            }
+                        }
+                        previous = Some(answer);
+                    }
+                    // keep the last answer to compare the next run with
+                    Err(e) => eprintln!("day {} part {}: {}", day, part, e),
+                }
+
+                let changed = loop {
+                    thread::sleep(Duration::from_millis(interval));
+                    let next = Snapshot::take(&watched);
+                    let changed = next.changed(&snapshot);
+                    snapshot = next;
+                    if !changed.is_empty() {
+                        break changed;
+                    }
+                };
+                for path in changed {
+                    println!("changed {}", path.display());
+                }
+            }
+        }
+        Command::Draw {
+            day,
+            part,
+            input,
+            format,
+            output,
+            frames: frames_dir,
+            every,
+            scale,
+            palette,
+        } => {
+            let part = Part::try_from(part).expect("part is validated by the parser");
+            let palette = palette.unwrap_or_default();
+            let pictures =
+                aoc::read_input(&input).and_then(|input| frames(day, part, &input, every as usize));
+            let pictures = match pictures {
+                Ok(pictures) => pictures,
+                Err(e) => {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            };
+            let written = match (frames_dir, pictures.last()) {
+                (Some(dir), _) => write_frames(&dir, &pictures, format, palette, scale as usize),
+                (None, Some(last)) => {
+                    let image = format.encode(last, &palette, scale as usize);
+                    match output {
+                        Some(filename) => fs_err::write(filename, image).map_err(|e| e.into()),
+                        None => std::io::stdout().write_all(&image).map_err(|e| e.into()),
+                    }
+                }
+                (None, None) => Err("nothing to draw!".into()),
+            };
+            if let Err(e) = written {
+                eprintln!("Application error: {}", e);
+                process::exit(1);
+            }
+        }
+        Command::Check {
+            input,
+            format,
+            days,
+        } => {
+            let days = all_if_empty(days);
+            let fixtures: &[Fixture] = if input {
+                &[Fixture::Example, Fixture::Input]
+            } else {
+                &[Fixture::Example]
+            };
+
+            let root = aoc::workspace_root();
+            let mut failures: usize = 0;
+            for day in days {
+                for fixture in fixtures {
+                    match check(&root, day, *fixture) {
+                        Ok(outcomes) => {
+                            for outcome in outcomes {
+                                if outcome.status() == Status::Fail {
+                                    failures += 1;
+                                }
+                                match format {
+                                    Format::Text => println!("{}", outcome),
+                                    Format::Json => {
+                                        match record::from_outcome(&outcome).to_json() {
+                                            Ok(json) => println!("{}", json),
+                                            Err(e) => {
+                                                eprintln!("Application error: {}", e);
+                                                process::exit(1);
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            failures += 1;
+                            // keep stdout to the records in json
+                            match format {
+                                Format::Text => println!("day {:>2} {} error: {}", day, fixture, e),
+                                Format::Json => {
+                                    eprintln!("day {:>2} {} error: {}", day, fixture, e)
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            if failures > 0 {
+                eprintln!("{} answer(s) do not match!", failures);
+                process::exit(1);
+            }
+        }
+        Command::Detect { input, all } => {
+            let input = match aoc::read_input(&input) {
+                Ok(input) => input,
+                Err(e) => {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            };
+            let candidates = candidates(&input);
+            if candidates.is_empty() {
+                eprintln!("Application error: cannot tell which day the input is for!");
+                process::exit(1);
+            }
+            for candidate in candidates
+                .iter()
+                .take(if all { candidates.len() } else { 1 })
+            {
+                match all {
+                    true => println!("{} ({:.0}%)", candidate.day, candidate.score * 100.0),
+                    false => println!("{}", candidate.day),
+                }
+            }
+        }
+        Command::All {
+            mut skip,
+            slow,
+            jobs: threads,
+            days,
+        } => {
+            if !slow {
+                skip.extend(SLOW);
+            }
+            let root = aoc::workspace_root();
+            let ledger = match Ledger::load(&root) {
+                Ok(ledger) => ledger,
+                Err(e) => {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            };
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads.unwrap_or(0))
+                .build();
+            let pool = match pool {
+                Ok(pool) => pool,
+                Err(e) => {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            };
+
+            let start = Instant::now();
+            let jobs = jobs(&all_if_empty(days), &skip);
+            let outcomes = pool.install(|| run_all(&root, &jobs, &ledger));
+            print!("{}", render(&outcomes));
+
+            let failures = outcomes
+                .iter()
+                .filter(|outcome| outcome.status.is_failure())
+                .count();
+            eprintln!(
+                "{} part(s) in {:.1?}, {} failed",
+                outcomes.len(),
+                start.elapsed(),
+                failures
+            );
+            if failures > 0 {
+                process::exit(1);
+            }
+        }
+        Command::Generate {
+            day,
+            seed,
+            size,
+            width,
+            height,
+            describe: only_describe,
+        } => {
+            if only_describe {
+                println!("{}", describe(day).unwrap_or("no generator"));
+                return;
+            }
+            let shape = Shape {
+                size,
+                width,
+                height,
+            };
+            match generate(day, shape, seed) {
+                Ok(input) => print!("{}", input),
+                Err(e) => {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            }
+        }
+        Command::Bench {
+            iterations,
+            label,
+            no_save,
+            days,
+        } => {
+            if cfg!(debug_assertions) {
+                eprintln!("warning: timing a debug build, use --release!");
+            }
+
+            let root = aoc::workspace_root();
+            let mut history = match History::load(&root) {
+                Ok(history) => history,
+                Err(e) => {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            };
+
+            let mut result = Vec::new();
+            let mut failures: usize = 0;
+            for day in all_if_empty(days) {
+                match bench(&root, day, iterations) {
+                    Ok(entries) => {
+                        for entry in entries {
+                            println!("{}", Comparison::new(&entry, &history));
+                            result.push(entry);
+                        }
+                    }
+                    Err(e) => {
+                        failures += 1;
+                        println!("day {:>2} error: {}", day, e);
+                    }
+                }
+            }
+
+            if !no_save {
+                history.run.push(Run {
+                    timestamp: now(),
+                    label,
+                    iterations,
+                    result,
+                });
+                if let Err(e) = history.save(&root) {
+                    eprintln!("Application error: {}", e);
+                    process::exit(1);
+                }
+            }
+
+            if failures > 0 {
+                eprintln!("{} day(s) could not be timed!", failures);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+// the day given, or the one the input looks like for auto. A day the
+// input does not look like is run anyway, with a warning.
+fn choose_day(choice: DayChoice, input: &str) -> Result<u8, Box<dyn Error>> {
+    match choice {
+        DayChoice::Auto => {
+            let day = detect(input)?;
+            info!("the input looks like day {}", day);
+            Ok(day)
+        }
+        DayChoice::Day(day) => {
+            let candidates = candidates(input);
+            match candidates.first() {
+                Some(best) if candidates.iter().all(|x| x.day != day) => {
+                    warn!("the input looks like day {}, not day {}", best.day, day)
+                }
+                _ => {}
+            }
+            Ok(day)
+        }
+    }
+}
+
+fn write_frames(
+    dir: &Path,
+    pictures: &[grid::Grid<char>],
+    encoding: Encoding,
+    palette: Palette,
+    scale: usize,
+) -> Result<(), Box<dyn Error>> {
+    let mut frames = Frames::new(dir, encoding, palette, scale)?;
+    for picture in pictures {
+        frames.push(picture)?;
+    }
+    info!("wrote {} frames to {}", frames.count(), dir.display());
+    Ok(())
+}
+
+// seconds since the unix epoch
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|x| x.as_secs())
+        .unwrap_or(0)
+}
+
+// how the answer of a run compares with answer_ledger.toml, keeping the
+// answer there if asked to
+// the verdict on the answer, and whether it was compared with answers.toml,
+// in which case it is never recorded
+fn compare_with_ledger(
+    root: &Path,
+    entry: Entry,
+    save: bool,
+) -> Result<(Verdict, bool), Box<dyn Error>> {
+    let mut ledger = Ledger::load(root)?;
+    let golden = ledger.is_golden(entry.day, entry.part, &entry.input);
+    if !save || golden {
+        let verdict = ledger.verdict(entry.day, entry.part, &entry.input, &entry.answer);
+        return Ok((verdict, golden));
+    }
+    let verdict = ledger.record(entry);
+    ledger.save(root)?;
+    Ok((verdict, golden))
+}
+
+fn all_if_empty(days: Vec<u8>) -> Vec<u8> {
+    if days.is_empty() {
+        aoc::DAYS.collect()
+    } else {
+        days
+    }
+}
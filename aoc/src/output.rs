@@ -0,0 +1,34 @@
+// shared with the binaries of each day, so that both print the same records
+pub use common::output::format;
+
+pub mod record {
+
+    use crate::check::report::{Outcome, Status};
+    pub use common::output::record::Record;
+
+    /// Record of a part checked by `aoc check`, with its status
+    pub fn from_outcome(outcome: &Outcome) -> Record {
+        let mut record = match &outcome.answer {
+            Some(Ok(answer)) => Record::new(outcome.day, outcome.parse, answer),
+            _ => Record {
+                day: outcome.day,
+                part: outcome.part.into(),
+                ..Default::default()
+            },
+        };
+        if let Some(Err(e)) = &outcome.answer {
+            record.error = Some(e.clone());
+        }
+        record.fixture = Some(outcome.fixture.to_string());
+        record.status = Some(
+            match outcome.status() {
+                Status::Pass => "pass",
+                Status::Fail => "fail",
+                Status::Unchecked => "unchecked",
+            }
+            .to_string(),
+        );
+        record.expected = outcome.expected.clone();
+        record
+    }
+}
@@ -0,0 +1,150 @@
+pub mod files {
+
+    use fs_err as fs;
+    use std::collections::BTreeMap;
+    use std::path::{Path, PathBuf};
+    use std::time::SystemTime;
+
+    // crates every day is built with, besides its own
+    const SHARED: [&str; 4] = ["aoc", "common", "geometry", "grid"];
+
+    /// Modification time and length of each watched file, a missing file
+    /// has neither
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Snapshot {
+        files: BTreeMap<PathBuf, Option<(SystemTime, u64)>>,
+    }
+
+    impl Snapshot {
+        // directories are watched with everything under them
+        pub fn take(paths: &[PathBuf]) -> Snapshot {
+            let mut snapshot = Snapshot::default();
+            for path in paths {
+                snapshot.add(path);
+            }
+            snapshot
+        }
+
+        fn add(&mut self, path: &Path) {
+            if path.is_dir() {
+                if let Ok(entries) = fs::read_dir(path) {
+                    for entry in entries.flatten() {
+                        self.add(&entry.path());
+                    }
+                }
+                return;
+            }
+            let stamp = fs::metadata(path)
+                .ok()
+                .and_then(|meta| Some((meta.modified().ok()?, meta.len())));
+            self.files.insert(path.to_path_buf(), stamp);
+        }
+
+        // files added, removed or modified since the earlier snapshot
+        pub fn changed(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+            let mut changed: Vec<PathBuf> = self
+                .files
+                .iter()
+                .filter(|(path, stamp)| earlier.files.get(*path) != Some(stamp))
+                .map(|(path, _)| path.clone())
+                .collect();
+            changed.extend(
+                earlier
+                    .files
+                    .keys()
+                    .filter(|path| !self.files.contains_key(*path))
+                    .cloned(),
+            );
+            changed
+        }
+    }
+
+    // the manifest and sources of the day and of the crates it is built with
+    pub fn sources(root: &Path, day: u8) -> Vec<PathBuf> {
+        let day = format!("day{}", day);
+        std::iter::once(day.as_str())
+            .chain(SHARED)
+            .flat_map(|krate| {
+                [
+                    root.join(krate).join("Cargo.toml"),
+                    root.join(krate).join("src"),
+                ]
+            })
+            .collect()
+    }
+}
+
+pub mod diff {
+
+    // the answer line by line, `-` marking lines of the previous answer and
+    // `+` lines of the new one. Multi-line answers like day10's CRT keep the
+    // unchanged lines for context.
+    pub fn diff(previous: &str, current: &str) -> Vec<String> {
+        let old: Vec<&str> = previous.lines().collect();
+        let new: Vec<&str> = current.lines().collect();
+        let mut lines = Vec::new();
+        for idx in 0..old.len().max(new.len()) {
+            match (old.get(idx), new.get(idx)) {
+                (Some(old), Some(new)) if old == new => lines.push(format!("  {}", new)),
+                (old, new) => {
+                    if let Some(old) = old {
+                        lines.push(format!("- {}", old));
+                    }
+                    if let Some(new) = new {
+                        lines.push(format!("+ {}", new));
+                    }
+                }
+            }
+        }
+        lines
+    }
+}
+
+pub mod runner {
+
+    use common::verbosity::Verbosity;
+    use common::Part;
+    use std::env;
+    use std::error::Error;
+    use std::path::Path;
+    use std::process::{Command, Stdio};
+
+    // run the part in a fresh build of the runner, so that changes to the
+    // sources are picked up. Compiler errors and diagnostics go to stderr.
+    pub fn run_built(
+        root: &Path,
+        day: u8,
+        part: Part,
+        inputs: &[String],
+        verbosity: Verbosity,
+    ) -> Result<String, Box<dyn Error>> {
+        // cargo sets CARGO for the programs it runs
+        let mut cargo = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+        cargo.arg("run").arg("--quiet");
+        // same profile as this build
+        if !cfg!(debug_assertions) {
+            cargo.arg("--release");
+        }
+        cargo
+            .arg("--manifest-path")
+            .arg(root.join("Cargo.toml"))
+            .args(["-p", "aoc", "--", "run"])
+            .arg(day.to_string())
+            .arg(u8::from(part).to_string())
+            .args(inputs);
+        match verbosity {
+            Verbosity::Quiet => cargo.arg("-q"),
+            Verbosity::Normal => &mut cargo,
+            Verbosity::Verbose => cargo.arg("-v"),
+            Verbosity::Trace => cargo.arg("-vv"),
+        };
+
+        let output = cargo.stderr(Stdio::inherit()).output()?;
+        // an answer differing from the verified one still fails the run,
+        // but is worth showing
+        if !output.status.success() && output.stdout.is_empty() {
+            return Err("build or run failed!".into());
+        }
+        Ok(String::from_utf8(output.stdout)?.trim_end().to_string())
+    }
+}
@@ -0,0 +1,90 @@
+use aoc::all::jobs::{jobs, Job, SLOW};
+use aoc::all::outcome::{run_all, Status};
+use aoc::all::table::render;
+use aoc::check::fixtures::Fixture;
+use aoc::ledger::hash::input_hash;
+use aoc::ledger::store::{Entry, Ledger};
+use common::Part;
+
+fn job(day: u8, part: Part) -> Job {
+    Job { day, part }
+}
+
+#[test]
+fn parts_are_written_day_dot_part() {
+    assert_eq!("15.2".parse::<Job>(), Ok(job(15, Part::Two)));
+    assert_eq!(job(1, Part::One).to_string(), "1.1");
+    assert!("15".parse::<Job>().is_err());
+    assert!("15.3".parse::<Job>().is_err());
+    assert!("0.1".parse::<Job>().is_err());
+
+    let all = jobs(&[1, 15, 16], &[job(15, Part::Two), job(16, Part::Two)]);
+    assert_eq!(
+        all,
+        [
+            job(1, Part::One),
+            job(1, Part::Two),
+            job(15, Part::One),
+            job(16, Part::One)
+        ]
+    );
+    // left out by default
+    assert_eq!(jobs(&[15], &SLOW), [job(15, Part::One)]);
+}
+
+#[test]
+fn answers_are_compared_with_the_ledger() {
+    let root = aoc::workspace_root();
+    let input = aoc::read_input(&Fixture::Input.files(&root, 1)).unwrap();
+    let entry = |part: u8, answer: &str, verified: bool| Entry {
+        day: 1,
+        part,
+        input: input_hash(&input),
+        answer: answer.to_string(),
+        verified,
+        timestamp: 0,
+    };
+
+    let parts = [job(1, Part::One), job(1, Part::Two), job(10, Part::Two)];
+    let outcomes = run_all(&root, &parts, &Ledger::default());
+    assert_eq!(outcomes.iter().map(|x| x.job).collect::<Vec<_>>(), parts);
+    assert!(outcomes.iter().all(|x| x.status == Status::New));
+
+    let ledger = Ledger {
+        answer: vec![entry(1, "66719", true), entry(2, "1", true)],
+        ..Ledger::default()
+    };
+    let outcomes = run_all(&root, &parts[..2], &ledger);
+    assert_eq!(outcomes[0].status, Status::Verified);
+    assert_eq!(outcomes[1].status, Status::Mismatch);
+    assert!(outcomes[1].status.is_failure());
+
+    let ledger = Ledger {
+        answer: vec![entry(1, "66719", false), entry(2, "1", false)],
+        ..Ledger::default()
+    };
+    let outcomes = run_all(&root, &parts[..2], &ledger);
+    assert_eq!(outcomes[0].status, Status::Recorded);
+    assert_eq!(outcomes[1].status, Status::Changed);
+    assert!(!outcomes[1].status.is_failure());
+
+    // the inputs are missing from an empty directory
+    let outcomes = run_all(&std::env::temp_dir(), &parts[..1], &ledger);
+    assert_eq!(outcomes[0].status, Status::Error);
+    assert!(outcomes[0].answer.is_err());
+}
+
+#[test]
+fn table_has_a_row_per_line_of_answer() {
+    let root = aoc::workspace_root();
+    let parts = [job(6, Part::One), job(10, Part::Two)];
+    let table = render(&run_all(&root, &parts, &Ledger::default()));
+    let lines: Vec<&str> = table.lines().collect();
+    // header, rule, day6 and the six rows of the screen
+    assert_eq!(lines.len(), 2 + 1 + 6);
+    assert!(lines[0].starts_with("day  part  answer"));
+    assert!(lines[2].starts_with("  6     1  1647"));
+    assert!(lines[2].ends_with("new"));
+    assert!(lines[3].starts_with(" 10     2  ####.###"));
+    assert!(lines[4].starts_with("           #....#..#"));
+}
@@ -0,0 +1,72 @@
+use aoc::check::fixtures::Fixture;
+use aoc::detect::choice::DayChoice;
+use aoc::detect::signature::{candidates, detect};
+use aoc::generate::inputs::generate;
+use aoc::generate::shape::Shape;
+
+#[test]
+fn examples_and_inputs_are_told_apart() {
+    let root = aoc::workspace_root();
+    for day in aoc::DAYS {
+        for fixture in [Fixture::Example, Fixture::Input] {
+            let input = aoc::read_input(&fixture.files(&root, day)).unwrap();
+            assert_eq!(detect(&input).unwrap(), day, "day {} {}", day, fixture);
+        }
+    }
+}
+
+#[test]
+fn generated_inputs_are_told_apart() {
+    for day in aoc::DAYS {
+        for seed in 0..5 {
+            let input = generate(day, Shape::default(), seed).unwrap();
+            assert_eq!(detect(&input).unwrap(), day, "day {} seed {}", day, seed);
+        }
+    }
+}
+
+#[test]
+fn a_few_odd_lines_are_left_to_the_parser() {
+    let mut lines: Vec<String> = (0..20).map(|x| format!("addx {}", x - 10)).collect();
+    lines[3] = "adx 4".to_string();
+    assert_eq!(detect(&lines.join("\n")).unwrap(), 10);
+    lines[4] = "nop".to_string();
+    lines[5] = "nop".to_string();
+    assert!(detect(&lines.join("\n")).is_err());
+
+    assert!(detect("").is_err());
+    assert!(detect("\n\n").is_err());
+    assert!(detect("# advent-of-code-2022\n").is_err());
+}
+
+#[test]
+fn likelier_days_come_first() {
+    // a map of tree heights is also a list of numbers
+    let days: Vec<u8> = candidates("30373\n25512\n65332\n")
+        .iter()
+        .map(|x| x.day)
+        .collect();
+    assert_eq!(days, [8, 1]);
+    // groups of numbers are not a map
+    let days: Vec<u8> = candidates("1000\n2000\n\n3000\n")
+        .iter()
+        .map(|x| x.day)
+        .collect();
+    assert_eq!(days, [1]);
+    // moves without the starting stacks are still day5's
+    assert_eq!(
+        detect("move 1 from 2 to 1\nmove 3 from 1 to 3\n").unwrap(),
+        5
+    );
+    // windows line endings
+    assert_eq!(detect("A Y\r\nB X\r\nC Z\r\n").unwrap(), 2);
+}
+
+#[test]
+fn day_is_a_number_or_auto() {
+    assert_eq!("auto".parse::<DayChoice>(), Ok(DayChoice::Auto));
+    assert_eq!("16".parse::<DayChoice>(), Ok(DayChoice::Day(16)));
+    assert!("17".parse::<DayChoice>().is_err());
+    assert!("0".parse::<DayChoice>().is_err());
+    assert!("Auto".parse::<DayChoice>().is_err());
+}
@@ -0,0 +1,55 @@
+use aoc::check::fixtures::Fixture;
+use aoc::draw::pictures::{frames, DAYS};
+use common::Part;
+
+fn example(day: u8) -> String {
+    aoc::read_input(&Fixture::Example.files(&aoc::workspace_root(), day)).unwrap()
+}
+
+fn answer(day: u8, part: Part) -> String {
+    aoc::example_solver(day)
+        .unwrap()
+        .solve(&example(day), part)
+        .unwrap()
+}
+
+fn count(picture: &grid::Grid<char>, symbol: char) -> usize {
+    picture.iter().filter(|(_, cell)| **cell == symbol).count()
+}
+
+#[test]
+fn frames_of_a_day_have_the_same_size() {
+    for day in DAYS {
+        for part in [Part::One, Part::Two] {
+            let pictures = frames(day, part, &example(day), 1).unwrap();
+            let (rows, cols) = (pictures[0].rows(), pictures[0].cols());
+            assert!(pictures
+                .iter()
+                .all(|picture| (picture.rows(), picture.cols()) == (rows, cols)));
+        }
+    }
+    assert!(frames(9, Part::One, &example(9), 1).is_err());
+}
+
+#[test]
+fn last_frame_is_the_answer() {
+    let screen = frames(10, Part::Two, &example(10), 1).unwrap();
+    assert_eq!(screen.len(), 240);
+    assert_eq!(screen.last().unwrap().render(|x| *x), answer(10, Part::Two));
+
+    let visible = frames(8, Part::One, &example(8), 1).unwrap();
+    assert_eq!(count(&visible[0], '#').to_string(), answer(8, Part::One));
+
+    let route = frames(12, Part::One, &example(12), 1).unwrap();
+    // the source and the destination are not marked
+    let steps = count(route.last().unwrap(), '*') + 1;
+    assert_eq!(steps.to_string(), answer(12, Part::One));
+
+    let cave = frames(14, Part::Two, &example(14), 10).unwrap();
+    assert_eq!(cave.len(), 1 + 93 / 10 + 1);
+    assert_eq!(
+        count(cave.last().unwrap(), 'o').to_string(),
+        answer(14, Part::Two)
+    );
+    assert_eq!(count(&cave[0], '+'), 1);
+}
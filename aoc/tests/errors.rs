@@ -0,0 +1,142 @@
+use common::Solution;
+use std::error::Error;
+
+// line, column and offending text of a typed parse error
+fn location<K: 'static + std::fmt::Debug + std::fmt::Display>(
+    result: Result<impl Sized, Box<dyn Error>>,
+) -> (usize, usize, String) {
+    let e = match result {
+        Ok(_) => panic!("invalid input was accepted"),
+        Err(e) => e,
+    };
+    let e = e
+        .downcast_ref::<common::ParseError<K>>()
+        .unwrap_or_else(|| panic!("not a parse error: {}", e));
+    (e.line, e.column, e.text.clone())
+}
+
+#[test]
+fn day1_invalid_calories() {
+    let input = "1000\n2000\n\n3x00\n";
+    let (line, column, text) = location::<day1::ErrorKind>(day1::Day1::default().parse(input));
+    assert_eq!((line, column, text.as_str()), (4, 1, "3x00"));
+}
+
+#[test]
+fn day1_negative_and_overflowing_calories() {
+    let input = "1000\n\n2000\n-300\n";
+    let (line, _, text) = location::<day1::ErrorKind>(day1::Day1::default().parse(input));
+    assert_eq!((line, text.as_str()), (4, "-300"));
+
+    let input = "1000\n\n9223372036854775807\n1\n";
+    let result = day1::Day1::default().parse(input);
+    let (line, _, text) = location::<day1::ErrorKind>(result);
+    assert_eq!((line, text.as_str()), (4, "1"));
+}
+
+#[test]
+fn day1_top_three_overflowing() {
+    let day1 = day1::Day1::default();
+    let totals = vec![i64::MAX, 1, i64::MAX - 1, 2];
+    assert_eq!(day1.part1(&totals).unwrap(), i64::MAX);
+    assert!(day1.part2(&totals).is_err());
+    assert_eq!(day1.part2(&vec![5, 1, 9, 7]).unwrap(), 21);
+}
+
+#[test]
+fn day1_lenient_skips_bad_lines() {
+    let day1 = day1::Day1 {
+        mode: day1::calories::tally::Mode::Lenient,
+    };
+    // the last elf is kept without a blank line after it
+    let input = "1000\nlots\n-5\n\n2000\r\n3000";
+    assert_eq!(day1.parse(input).unwrap(), vec![1000, 5000]);
+    // an elf of bad lines only is dropped
+    assert_eq!(day1.parse("x\n\n7\n").unwrap(), vec![7]);
+    assert_eq!(day1.parse("7\n\nx\ny").unwrap(), vec![7]);
+    // an overflow cannot be skipped
+    assert!(day1.parse("9223372036854775807\n1\n").is_err());
+}
+
+#[test]
+fn day2_invalid_choice() {
+    let input = "A Y\nB Q\n";
+    let (line, column, text) = location::<day2::ErrorKind>(day2::Day2::default().parse(input));
+    assert_eq!((line, column, text.as_str()), (2, 3, "Q"));
+}
+
+// a letter fitting the other part only is reported by the part solved
+#[test]
+fn day2_second_column_of_the_other_part() {
+    let day2 = day2::Day2 {
+        rules: day2::rules::engine::Rules::rpsls(),
+    };
+    // V is spock, a move but no outcome
+    let rounds = day2.parse("A Y\nB  V\n").unwrap();
+    assert!(day2.part1(&rounds).is_ok());
+    let (line, column, text) = location::<day2::ErrorKind>(day2.part2(&rounds));
+    assert_eq!((line, column, text.as_str()), (2, 4, "V"));
+    let error = day2.part2(&rounds).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("line 2, column 4: not an outcome"));
+}
+
+#[test]
+fn day4_invalid_assignment() {
+    let input = "2-4,6-8\n2-3,4_5\n";
+    let (line, column, _) = location::<day4::ErrorKind>(day4::Day4.parse(input));
+    assert_eq!((line, column), (2, 6));
+}
+
+#[test]
+fn day5_invalid_move() {
+    let input = "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\nmove x from 1 to 2\n";
+    let (line, column, text) = location::<day5::ErrorKind>(day5::Day5.parse(input));
+    assert_eq!((line, column, text.as_str()), (6, 6, "x"));
+}
+
+#[test]
+fn day10_invalid_instruction() {
+    let input = "noop\naddx 3\nsubx 5\n";
+    let day10 = day10::Day10::default();
+    let (line, column, text) = location::<day10::ErrorKind>(day10.parse(input));
+    assert_eq!((line, column, text.as_str()), (3, 1, "subx"));
+}
+
+#[test]
+fn day11_invalid_operation() {
+    let input = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old / 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+";
+    let day11 = day11::Day11::default();
+    let (line, column, text) = location::<day11::ErrorKind>(day11.parse(input));
+    assert_eq!((line, column, text.as_str()), (3, 24, "/"));
+}
+
+#[test]
+fn day13_unclosed_list() {
+    let input = "[1,[2,3]]\n[[1],4\n";
+    let (line, column, _) = location::<day13::ErrorKind>(day13::Day13.parse(input));
+    assert_eq!((line, column), (2, 1));
+}
+
+#[test]
+fn day14_diagonal_path() {
+    let input = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,5\n";
+    let day14 = day14::Day14::default();
+    let (line, column, text) = location::<day14::ErrorKind>(day14.parse(input));
+    assert_eq!((line, column, text.as_str()), (2, 9, " 502,5"));
+}
+
+#[test]
+fn day16_missing_start_valve() {
+    let input = "Valve BB has flow rate=13; tunnels lead to valves CC\n\
+                 Valve CC has flow rate=2; tunnel leads to valve BB\n";
+    let (line, _, _) = location::<day16::ErrorKind>(day16::Day16::default().parse(input));
+    assert_eq!(line, 3);
+}
@@ -0,0 +1,39 @@
+use common::Solution;
+
+fn dir_sizes(output: &str) -> Vec<(String, usize)> {
+    let filesystem = day7::Day7.parse(output).unwrap();
+    let mut sizes: Vec<_> = filesystem.dir_sizes.into_iter().collect();
+    sizes.sort();
+    sizes
+}
+
+fn sizes(expected: &[(&str, usize)]) -> Vec<(String, usize)> {
+    expected
+        .iter()
+        .map(|&(dir, size)| (dir.to_string(), size))
+        .collect()
+}
+
+// `cd /` goes back to the root from any depth, the files listed after it
+// are not counted in the directory left
+#[test]
+fn cd_root_leaves_nested_directories() {
+    let output = "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\ndir b\n10 f\n$ cd b\n$ ls\n5 h\n\
+                  $ cd /\n$ ls\n20 g\n";
+    assert_eq!(
+        dir_sizes(output),
+        sizes(&[("/", 35), ("a", 15), ("a/b", 5)])
+    );
+}
+
+// a directory holds the directories under its path, not every directory
+// whose path contains its name
+#[test]
+fn subdirectories_are_matched_by_path() {
+    let output = "$ cd /\n$ ls\ndir a\ndir ba\n$ cd a\n$ ls\ndir b\n1 f\n$ cd b\n$ ls\n2 g\n\
+                  $ cd ..\n$ cd ..\n$ cd ba\n$ ls\n100 h\n";
+    assert_eq!(
+        dir_sizes(output),
+        sizes(&[("/", 103), ("a", 3), ("a/b", 2), ("ba", 100)])
+    );
+}
@@ -0,0 +1,50 @@
+use aoc::generate::inputs::generate;
+use aoc::generate::shape::Shape;
+use common::Part;
+
+mod shapes;
+use shapes::small;
+
+#[test]
+fn generated_inputs_are_solved() {
+    for day in aoc::DAYS {
+        let solver = aoc::example_solver(day).unwrap();
+        for seed in 0..3 {
+            let input = generate(day, small(day), seed).unwrap();
+            let parts: &[Part] = match day {
+                // there may not be a single place left for the distress beacon
+                15 => &[Part::One],
+                _ => &[Part::One, Part::Two],
+            };
+            if let Err(e) = solver.timed(&input, parts) {
+                panic!("day {} seed {}: {}\n{}", day, seed, e, input);
+            }
+        }
+    }
+}
+
+#[test]
+fn seed_gives_the_same_input() {
+    for day in aoc::DAYS {
+        let first = generate(day, small(day), 42).unwrap();
+        assert_eq!(first, generate(day, small(day), 42).unwrap());
+        assert_ne!(first, generate(day, small(day), 43).unwrap(), "day {}", day);
+    }
+}
+
+#[test]
+fn shapes_out_of_range_are_refused() {
+    let monkeys = |size| Shape {
+        size: Some(size),
+        ..Default::default()
+    };
+    assert!(generate(11, monkeys(3), 0).is_err());
+    assert!(generate(11, monkeys(16), 0).is_err());
+    let map = Shape {
+        width: Some(10),
+        height: Some(10),
+        ..Default::default()
+    };
+    assert!(generate(12, map, 0).is_err());
+    assert!(generate(17, Shape::default(), 0).is_err());
+}
@@ -0,0 +1,59 @@
+use aoc::check::fixtures::Fixture;
+use aoc::check::report::{check, Status};
+
+fn assert_answers(day: u8, fixture: Fixture) {
+    let outcomes = check(&aoc::workspace_root(), day, fixture).expect("could not check day");
+
+    let mut passed: usize = 0;
+    for outcome in outcomes {
+        match outcome.status() {
+            Status::Pass => passed += 1,
+            Status::Fail => panic!("{}", outcome),
+            Status::Unchecked => {}
+        }
+    }
+    assert!(
+        passed > 0,
+        "no answers recorded for day {} {}",
+        day,
+        fixture
+    );
+}
+
+// the examples are quick enough to always run, the actual inputs are
+// checked with `cargo test -- --ignored`
+macro_rules! golden {
+    ($($example:ident, $input:ident: $day:expr;)*) => {
+        $(
+            #[test]
+            fn $example() {
+                assert_answers($day, Fixture::Example);
+            }
+
+            #[test]
+            #[ignore]
+            fn $input() {
+                assert_answers($day, Fixture::Input);
+            }
+        )*
+    };
+}
+
+golden! {
+    day1_example, day1_input: 1;
+    day2_example, day2_input: 2;
+    day3_example, day3_input: 3;
+    day4_example, day4_input: 4;
+    day5_example, day5_input: 5;
+    day6_example, day6_input: 6;
+    day7_example, day7_input: 7;
+    day8_example, day8_input: 8;
+    day9_example, day9_input: 9;
+    day10_example, day10_input: 10;
+    day11_example, day11_input: 11;
+    day12_example, day12_input: 12;
+    day13_example, day13_input: 13;
+    day14_example, day14_input: 14;
+    day15_example, day15_input: 15;
+    day16_example, day16_input: 16;
+}
@@ -0,0 +1,118 @@
+use common::Solution;
+use day1::calories::tally::Mode;
+use std::io::Cursor;
+
+// in-memory input parses the same as the example file
+#[test]
+fn parse_reader_matches_parse() {
+    let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n";
+    let from_reader = day1::Day1::default()
+        .parse_reader(Cursor::new(input))
+        .unwrap();
+    assert_eq!(from_reader, day1::Day1::default().parse(input).unwrap());
+    assert_eq!(from_reader, vec![6000, 4000, 11000]);
+}
+
+#[test]
+fn constructors_read_from_any_reader() {
+    let root = aoc::workspace_root();
+    let example = std::fs::read_to_string(root.join("day8").join("test.txt")).unwrap();
+    let tree_matrix = day8::read_tree_matrix(example.as_bytes()).unwrap();
+    assert_eq!(day8::visible_trees(&tree_matrix).unwrap(), 21);
+
+    let example = std::fs::read(root.join("day12").join("test.txt")).unwrap();
+    let graph = day12::read_graph(&example[..]).unwrap();
+    assert_eq!(day12::shortest_path(&graph).unwrap(), 31);
+}
+
+// streaming keeps the same elves as sorting every total, ties included
+#[test]
+fn streamed_top_elves_match_stats() {
+    let root = aoc::workspace_root();
+    let input = std::fs::read_to_string(root.join("day1").join("input.txt")).unwrap();
+    let stats = day1::calories::stats::Stats::new(&day1::Day1::default().parse(&input).unwrap());
+    for n in [0, 1, 3, 10, 10_000] {
+        let top = day1::calories::stream::top_elves(input.as_bytes(), n, Mode::Strict).unwrap();
+        assert_eq!(top, stats.top(n), "top {}", n);
+    }
+
+    let ties = "5\n\n7\n\n5\n\n7\n\n6\n";
+    let stats = day1::calories::stats::Stats::new(&day1::Day1::default().parse(ties).unwrap());
+    let top = day1::calories::stream::top_elves(Cursor::new(ties), 3, Mode::Strict).unwrap();
+    assert_eq!(top, stats.top(3));
+}
+
+// a bad line is reported where the parser of the whole input reports it
+#[test]
+fn streamed_errors_match_parse() {
+    let input = "1000\r\n2000\r\n\r\n\r\n40x0\r\n";
+    let streamed =
+        day1::calories::stream::top_elves(Cursor::new(input), 3, Mode::Strict).unwrap_err();
+    let parsed = day1::Day1::default().parse(input).unwrap_err();
+    assert_eq!(streamed.to_string(), parsed.to_string());
+}
+
+// the exported items add up to the totals the puzzle is solved with
+#[test]
+fn exported_inventories_match_parse() {
+    let root = aoc::workspace_root();
+    let input = std::fs::read_to_string(root.join("day1").join("input.txt")).unwrap();
+    let elves = day1::calories::export::inventories(&input, Mode::Strict).unwrap();
+    let totals: Vec<i64> = elves.iter().map(|elf| elf.total).collect();
+    assert_eq!(totals, day1::Day1::default().parse(&input).unwrap());
+    for elf in &elves {
+        assert_eq!(elf.items, elf.calories.len());
+        assert_eq!(elf.total, elf.calories.iter().sum::<i64>());
+    }
+
+    let csv = day1::calories::export::to_csv(&elves);
+    let mut totals = vec![0; elves.len()];
+    for row in csv.lines().skip(1) {
+        let row: Vec<i64> = row.split(',').map(|x| x.parse().unwrap()).collect();
+        totals[row[0] as usize - 1] += row[2];
+        let elf = &elves[row[0] as usize - 1];
+        assert_eq!((row[3], row[4]), (elf.items as i64, elf.total));
+    }
+    assert_eq!(
+        csv.lines().count(),
+        elves.iter().map(|elf| elf.items).sum::<usize>() + 1
+    );
+    assert!(elves
+        .iter()
+        .zip(&totals)
+        .all(|(elf, &total)| elf.total == total));
+    let json = day1::calories::export::to_json(&elves).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(parsed.as_array().unwrap().len(), elves.len());
+    assert_eq!(parsed[0]["elf"], 1);
+    assert_eq!(parsed[0]["total"], elves[0].total);
+}
+
+// leniently, an elf of bad lines only is dropped by the parser, the stream
+// and the export alike
+#[test]
+fn lenient_readers_drop_the_same_elves() {
+    let input = "1000\n\nlots\n-5\n\n3000\nx\n\n2000\n";
+    let day1 = day1::Day1 {
+        mode: Mode::Lenient,
+    };
+    let totals = day1.parse(input).unwrap();
+    assert_eq!(totals, vec![1000, 3000, 2000]);
+
+    let top = day1::calories::stream::top_elves(Cursor::new(input), 3, Mode::Lenient).unwrap();
+    let numbers: Vec<usize> = top.iter().map(|elf| elf.number).collect();
+    assert_eq!(top, day1::calories::stats::Stats::new(&totals).top(3));
+    assert_eq!(numbers, [2, 3, 1]);
+
+    let elves = day1::calories::export::inventories(input, Mode::Lenient).unwrap();
+    let exported: Vec<i64> = elves.iter().map(|elf| elf.total).collect();
+    assert_eq!(exported, totals);
+    assert_eq!(elves[1].calories, [3000]);
+
+    // and report the same bad lines
+    for input in ["1\n\n2\nx\n", "1\n\n9223372036854775807\n1\n", "1\n\n\n-3"] {
+        let exported = day1::calories::export::inventories(input, Mode::Strict).unwrap_err();
+        let parsed = day1::Day1::default().parse(input).unwrap_err();
+        assert_eq!(exported.to_string(), parsed.to_string());
+    }
+}
@@ -0,0 +1,115 @@
+use aoc::check::answers::DayAnswers;
+use aoc::check::fixtures::Fixture;
+use aoc::ledger::hash::input_hash;
+use aoc::ledger::store::{Entry, Ledger, Verdict};
+use common::Part;
+
+fn entry(answer: &str, verified: bool) -> Entry {
+    Entry {
+        day: 7,
+        part: 1,
+        input: input_hash("$ cd /\n$ ls\n14848514 b.txt\n"),
+        answer: answer.to_string(),
+        verified,
+        timestamp: 0,
+    }
+}
+
+#[test]
+fn hash_is_sha256() {
+    assert_eq!(
+        input_hash("abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+}
+
+#[test]
+fn later_runs_are_compared_with_the_recorded_answer() {
+    let mut ledger = Ledger::default();
+    assert_eq!(ledger.record(entry("95437", false)), Verdict::New);
+    assert_eq!(
+        ledger.record(entry("95437", false)),
+        Verdict::Same { verified: false }
+    );
+    // an unverified answer follows the latest run
+    let verdict = ledger.record(entry("95438", false));
+    assert!(!verdict.is_mismatch());
+    assert_eq!(ledger.answer.len(), 1);
+    assert_eq!(ledger.answer[0].answer, "95438");
+
+    ledger.record(entry("95437", true));
+    let verdict = ledger.record(entry("95438", false));
+    assert!(verdict.is_mismatch());
+    assert_eq!(
+        verdict.to_string(),
+        "differs from the verified answer 95437"
+    );
+    // a verified answer stays until another one is verified
+    assert_eq!(ledger.answer[0].answer, "95437");
+    assert!(ledger.answer[0].verified);
+}
+
+#[test]
+fn ledger_is_saved_and_loaded() {
+    let dir = std::env::temp_dir().join(format!("aoc-ledger-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    assert!(Ledger::load(&dir).unwrap().answer.is_empty());
+
+    let mut ledger = Ledger::default();
+    ledger.record(entry("95437", true));
+    ledger.record(Entry {
+        day: 10,
+        part: 2,
+        answer: "##..\n#..#".to_string(),
+        ..entry("", false)
+    });
+    ledger.save(&dir).unwrap();
+    assert_eq!(Ledger::load(&dir).unwrap().answer, ledger.answer);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// the answers.toml of each day holds the verified answers of the actual
+// inputs, and a run cannot change them
+#[test]
+fn ledger_is_seeded_from_the_answers() {
+    let root = aoc::workspace_root();
+    let ledger = Ledger::load(&root).unwrap();
+    for day in aoc::DAYS {
+        let files = Fixture::Input.files(&root, day);
+        let hash = input_hash(&aoc::read_input(&files).unwrap());
+        let answers = DayAnswers::load(&root, day).unwrap();
+        for part in [Part::One, Part::Two] {
+            let Some(expected) = answers.get(Fixture::Input).get(part) else {
+                continue;
+            };
+            let entry = ledger.find(day, part.into(), &hash);
+            let entry = entry.unwrap_or_else(|| panic!("day {} part {} not in ledger", day, part));
+            assert!(entry.verified);
+            assert_eq!(entry.answer, expected, "day {} part {}", day, part);
+        }
+    }
+
+    // the committed ledger keeps only the answers of other inputs
+    for entry in &ledger.answer {
+        assert!(!ledger.golden.iter().any(|golden| {
+            (golden.day, golden.part, &golden.input) == (entry.day, entry.part, &entry.input)
+        }));
+    }
+
+    let mut ledger = Ledger::load(&root).unwrap();
+    let hash = input_hash(&aoc::read_input(&Fixture::Input.files(&root, 1)).unwrap());
+    let run = Entry {
+        day: 1,
+        part: 1,
+        input: hash.clone(),
+        answer: "1".to_string(),
+        verified: true,
+        timestamp: 0,
+    };
+    assert!(ledger.is_golden(1, 1, &hash));
+    assert!(!ledger.is_golden(1, 1, &input_hash("1000\n")));
+    assert!(ledger.record(run).is_mismatch());
+    assert_eq!(ledger.find(1, 1, &hash).unwrap().answer, "66719");
+    assert!(ledger.answer.iter().all(|entry| entry.input != hash));
+}
@@ -0,0 +1,33 @@
+use aoc::check::fixtures::Fixture;
+use aoc::check::report::check;
+use aoc::output::record::{self, Record};
+use common::Part;
+
+#[test]
+fn record_holds_secondary_values() {
+    let input = aoc::read_input(&Fixture::Example.files(&aoc::workspace_root(), 15)).unwrap();
+    let solver = aoc::example_solver(15).unwrap();
+    let timed = solver.timed(&input, &[Part::Two]).unwrap();
+    let record = Record::new(15, timed.parse, &timed.parts[0]);
+
+    assert_eq!((record.day, record.part), (15, 2));
+    assert_eq!(record.answer.as_deref(), Some("56000011"));
+    assert_eq!(record.details["x"], "14");
+    assert_eq!(record.details["y"], "11");
+
+    let json: serde_json::Value = serde_json::from_str(&record.to_json().unwrap()).unwrap();
+    assert_eq!(json["details"]["y"], "11");
+    assert!(json.get("fixture").is_none());
+}
+
+#[test]
+fn check_records_carry_the_status() {
+    let outcomes = check(&aoc::workspace_root(), 13, Fixture::Example).unwrap();
+    let record = record::from_outcome(&outcomes[1]);
+
+    assert_eq!(record.status.as_deref(), Some("pass"));
+    assert_eq!(record.fixture.as_deref(), Some("example"));
+    assert_eq!(record.expected, record.answer);
+    assert_eq!(record.details["marker1"], "10");
+    assert_eq!(record.details["marker2"], "14");
+}
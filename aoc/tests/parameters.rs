@@ -0,0 +1,37 @@
+use aoc::check::fixtures::Fixture;
+use common::Solution;
+
+fn example(day: u8) -> String {
+    aoc::read_input(&Fixture::Example.files(&aoc::workspace_root(), day)).unwrap()
+}
+
+// the distress beacon is searched for within any bounds, not only from 0
+#[test]
+fn day15_search_area_with_a_lower_bound() {
+    let day15 = |ymin, ymax| day15::Day15 {
+        yloc: 10,
+        ymin,
+        ymax,
+    };
+    let sensors = day15(0, 20).parse(&example(15)).unwrap();
+    for (ymin, ymax) in [(0, 20), (5, 20), (11, 14)] {
+        let beacon = day15(ymin, ymax).part2(&sensors).unwrap();
+        assert_eq!((beacon.x, beacon.y), (14, 11), "{}..={}", ymin, ymax);
+    }
+    assert!(day15(15, 20).part2(&sensors).is_err());
+    assert!(day15(20, 5).part2(&sensors).is_err());
+}
+
+// the queried cycles are summed whatever their order, repeats once
+#[test]
+fn day10_query_cycles_in_any_order() {
+    let day10 = |query_cycles: &[u32]| day10::Day10 {
+        query_cycles: query_cycles.to_vec(),
+    };
+    let instructions = day10(&[]).parse(&example(10)).unwrap();
+    let sum = |query_cycles: &[u32]| day10(query_cycles).part1(&instructions).unwrap();
+    assert_eq!(sum(&[20, 60]), 1560);
+    assert_eq!(sum(&[60, 20]), 1560);
+    assert_eq!(sum(&[60, 20, 60]), 1560);
+    assert_eq!(sum(&[220, 20, 180, 60, 140, 100]), 13140);
+}
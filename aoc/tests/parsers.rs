@@ -0,0 +1,459 @@
+use aoc::generate::inputs::generate;
+use common::Solution;
+use geometry::Point;
+use id_tree::{NodeId, Tree};
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::select;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::error::Error;
+
+mod shapes;
+use shapes::small;
+
+// parse with the parser of the day, keeping only whether it succeeded
+fn parse(day: u8, input: &str) -> Result<(), Box<dyn Error>> {
+    match day {
+        1 => day1::Day1::default().parse(input).map(drop),
+        2 => day2::Day2::default().parse(input).map(drop),
+        3 => day3::Day3.parse(input).map(drop),
+        4 => day4::Day4.parse(input).map(drop),
+        5 => day5::Day5.parse(input).map(drop),
+        6 => day6::Day6::default().parse(input).map(drop),
+        7 => day7::Day7.parse(input).map(drop),
+        8 => day8::Day8.parse(input).map(drop),
+        9 => day9::Day9::default().parse(input).map(drop),
+        10 => day10::Day10::default().parse(input).map(drop),
+        11 => day11::Day11::default().parse(input).map(drop),
+        12 => day12::Day12.parse(input).map(drop),
+        13 => day13::Day13.parse(input).map(drop),
+        14 => day14::Day14::default().parse(input).map(drop),
+        15 => day15::Day15::default().parse(input).map(drop),
+        16 => day16::Day16::default().parse(input).map(drop),
+        _ => Err("no such day!".into()),
+    }
+}
+
+// a day and one of its generated inputs
+fn generated() -> impl Strategy<Value = (u8, String)> {
+    (1..=16u8, any::<u64>()).prop_map(|(day, seed)| (day, generate(day, small(day), seed).unwrap()))
+}
+
+#[derive(Clone, Debug)]
+enum Edit {
+    Delete(usize),
+    Insert(usize, char),
+    Replace(usize, char),
+}
+
+// characters the inputs are made of, and now and then any other
+fn noise() -> impl Strategy<Value = char> {
+    prop_oneof![
+        4 => select("0123456789 \n,:;=-[]$/.ABCDEXYZabcdexyz".chars().collect::<Vec<_>>()),
+        1 => any::<char>(),
+    ]
+}
+
+fn edit() -> impl Strategy<Value = Edit> {
+    prop_oneof![
+        any::<usize>().prop_map(Edit::Delete),
+        (any::<usize>(), noise()).prop_map(|(at, c)| Edit::Insert(at, c)),
+        (any::<usize>(), noise()).prop_map(|(at, c)| Edit::Replace(at, c)),
+    ]
+}
+
+fn apply(input: &str, edits: &[Edit]) -> String {
+    let mut chars: Vec<char> = input.chars().collect();
+    for edit in edits {
+        match *edit {
+            Edit::Insert(at, c) => chars.insert(at % (chars.len() + 1), c),
+            Edit::Delete(at) if !chars.is_empty() => {
+                chars.remove(at % chars.len());
+            }
+            Edit::Replace(at, c) if !chars.is_empty() => {
+                let at = at % chars.len();
+                chars[at] = c;
+            }
+            _ => {}
+        }
+    }
+    chars.into_iter().collect()
+}
+
+proptest! {
+    #[test]
+    fn generated_inputs_parse((day, input) in generated()) {
+        if let Err(e) = parse(day, &input) {
+            return Err(TestCaseError::fail(format!("day {}: {}\n{}", day, e, input)));
+        }
+    }
+
+    #[test]
+    fn edited_inputs_never_panic((day, input) in generated(), edits in vec(edit(), 1..6)) {
+        let _ = parse(day, &apply(&input, &edits));
+    }
+
+    #[test]
+    fn arbitrary_text_never_panics(day in 1..=16u8, input in vec(noise(), 0..200)) {
+        let _ = parse(day, &input.into_iter().collect::<String>());
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Packet {
+    Value(u8),
+    List(Vec<Packet>),
+}
+
+impl Packet {
+    fn render(&self) -> String {
+        match self {
+            Packet::Value(value) => value.to_string(),
+            Packet::List(items) => {
+                let items: Vec<String> = items.iter().map(Packet::render).collect();
+                format!("[{}]", items.join(","))
+            }
+        }
+    }
+}
+
+fn packet() -> impl Strategy<Value = Packet> {
+    let leaf = any::<u8>().prop_map(Packet::Value);
+    let nested = leaf.prop_recursive(4, 32, 5, |inner| vec(inner, 0..5).prop_map(Packet::List));
+    vec(nested, 0..5).prop_map(Packet::List)
+}
+
+// the packet read back from the tree, where -1 stands for a list
+fn render_tree(tree: &Tree<i32>, node: &NodeId) -> String {
+    match *tree.get(node).unwrap().data() {
+        -1 => {
+            let items: Vec<String> = tree
+                .children_ids(node)
+                .unwrap()
+                .map(|child| render_tree(tree, child))
+                .collect();
+            format!("[{}]", items.join(","))
+        }
+        value => value.to_string(),
+    }
+}
+
+fn valve_name() -> impl Strategy<Value = String> {
+    ("[A-Z]", "[A-Z]").prop_map(|(a, b)| a + &b)
+}
+
+fn direction() -> impl Strategy<Value = (geometry::Direction, &'static str)> {
+    use geometry::Direction;
+    select(vec![
+        (Direction::Right, "R"),
+        (Direction::Left, "L"),
+        (Direction::Up, "U"),
+        (Direction::Down, "D"),
+    ])
+}
+
+proptest! {
+    #[test]
+    fn day1_reads_each_elf(elves in vec(vec(0..100_000i64, 1..6), 0..10)) {
+        let input: Vec<String> = elves
+            .iter()
+            .map(|items| items.iter().map(i64::to_string).collect::<Vec<_>>().join("\n"))
+            .collect();
+        let model = day1::Day1::default().parse(&input.join("\n\n")).unwrap();
+        let totals: Vec<i64> = elves.iter().map(|items| items.iter().sum()).collect();
+        prop_assert_eq!(model, totals);
+    }
+
+    #[test]
+    fn day2_reads_each_round(
+        rounds in vec((select(vec!['A', 'B', 'C']), select(vec!['X', 'Y', 'Z'])), 0..20),
+    ) {
+        let input: String = rounds.iter().map(|(a, b)| format!("{} {}\n", a, b)).collect();
+        let model = day2::Day2::default().parse(&input).unwrap();
+        let read: Vec<(char, char)> = model.iter().map(|round| (round.opponent, round.second)).collect();
+        prop_assert_eq!(read, rounds);
+        let lines: Vec<usize> = model.iter().map(|round| round.line).collect();
+        prop_assert_eq!(lines, (1..=model.len()).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn day3_reads_each_rucksack(rucksacks in vec("([a-zA-Z]{2}){1,20}", 0..10)) {
+        let input: String = rucksacks.iter().map(|rucksack| format!("{}\n", rucksack)).collect();
+        prop_assert_eq!(day3::Day3.parse(&input).unwrap(), rucksacks);
+    }
+
+    #[test]
+    fn day4_reads_each_pair(pairs in vec(((0..100u32, 0..20u32), (0..100u32, 0..20u32)), 0..20)) {
+        let pairs: Vec<_> = pairs
+            .into_iter()
+            .map(|((a, len_a), (b, len_b))| ((a, a + len_a), (b, b + len_b)))
+            .collect();
+        let input: String = pairs
+            .iter()
+            .map(|((a1, a2), (b1, b2))| format!("{}-{},{}-{}\n", a1, a2, b1, b2))
+            .collect();
+        prop_assert_eq!(day4::Day4.parse(&input).unwrap(), pairs);
+    }
+
+    #[test]
+    fn day5_reads_stacks_and_moves(
+        stacks in vec(vec(select(('A'..='Z').collect::<Vec<_>>()), 0..6), 1..10),
+        moves in vec((1..10u32, 1..10u32, 1..10u32), 0..10),
+    ) {
+        let height = stacks.iter().map(Vec::len).max().unwrap();
+        let mut input = String::new();
+        for level in (0..height).rev() {
+            let row: Vec<String> = stacks
+                .iter()
+                .map(|stack| match stack.get(level) {
+                    Some(c) => format!("[{}]", c),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            input += &(row.join(" ") + "\n");
+        }
+        let numbers: Vec<String> = (1..=stacks.len()).map(|n| format!(" {} ", n)).collect();
+        input += &(numbers.join(" ") + "\n\n");
+        for (quantity, src, dst) in &moves {
+            input += &format!("move {} from {} to {}\n", quantity, src, dst);
+        }
+
+        let ship = day5::Day5.parse(&input).unwrap();
+        let expected: HashMap<u32, Vec<char>> = (1..)
+            .zip(stacks.iter().cloned())
+            .collect();
+        prop_assert_eq!(ship.stacks, expected);
+        let read: Vec<_> = ship.moves.iter().map(|m| (m.quantity, m.src, m.dst)).collect();
+        prop_assert_eq!(read, moves);
+    }
+
+    #[test]
+    fn day6_reads_the_datastream(datastream in "[a-z]{1,200}") {
+        let model = day6::Day6::default().parse(&format!("{}\n", datastream)).unwrap();
+        prop_assert_eq!(model, datastream);
+    }
+
+    #[test]
+    fn day7_sums_each_directory(
+        files in vec(
+            (
+                vec(select(vec!["a", "b", "ab", "ba"]), 0..4),
+                "[a-z]{1,8}(\\.[a-z]{3})?",
+                1..1_000_000usize,
+            ),
+            1..20,
+        ),
+    ) {
+        // a file name may be listed twice, the last size is kept
+        let mut sizes: BTreeMap<(Vec<&str>, String), usize> = BTreeMap::new();
+        let mut input = String::new();
+        for (dirs, name, size) in &files {
+            input += "$ cd /\n";
+            for dir in dirs {
+                input += &format!("$ ls\ndir {}\n$ cd {}\n", dir, dir);
+            }
+            input += &format!("$ ls\n{} {}\n", size, name);
+            sizes.insert((dirs.clone(), name.clone()), *size);
+        }
+
+        let filesystem = day7::Day7.parse(&input).unwrap();
+        let mut expected: HashMap<String, usize> = HashMap::new();
+        for ((dirs, _), size) in &sizes {
+            *expected.entry("/".to_string()).or_default() += size;
+            for depth in 1..=dirs.len() {
+                *expected.entry(dirs[..depth].join("/")).or_default() += size;
+            }
+        }
+        prop_assert_eq!(filesystem.total_filesize, sizes.values().sum::<usize>());
+        prop_assert_eq!(filesystem.dir_sizes, expected);
+    }
+
+    #[test]
+    fn day8_reads_each_tree(rows in 1..10usize, cols in 1..10usize, seed in vec(0..10u32, 100)) {
+        let heights: Vec<u32> = seed[..rows * cols].to_vec();
+        let input: String = heights
+            .chunks(cols)
+            .map(|row| row.iter().map(u32::to_string).collect::<String>() + "\n")
+            .collect();
+        let expected = grid::Grid::from_vec(rows, cols, heights).unwrap();
+        prop_assert_eq!(day8::Day8.parse(&input).unwrap(), expected);
+    }
+
+    #[test]
+    fn day9_reads_each_move(moves in vec((direction(), 0..100u32), 0..20)) {
+        let input: String = moves
+            .iter()
+            .map(|((_, name), dist)| format!("{} {}\n", name, dist))
+            .collect();
+        let expected: Vec<_> = moves.iter().map(|((dir, _), dist)| (*dir, *dist)).collect();
+        prop_assert_eq!(day9::Day9::default().parse(&input).unwrap(), expected);
+    }
+
+    #[test]
+    fn day10_reads_each_instruction(values in vec(prop::option::of(-50..50i32), 0..30)) {
+        use day10::Instruction;
+        let input: String = values
+            .iter()
+            .map(|value| match value {
+                Some(v) => format!("addx {}\n", v),
+                None => "noop\n".to_string(),
+            })
+            .collect();
+        let expected: Vec<Instruction> = values
+            .iter()
+            .map(|value| match value {
+                Some(v) => Instruction::Addx { v: *v },
+                None => Instruction::Noop,
+            })
+            .collect();
+        prop_assert_eq!(day10::Day10::default().parse(&input).unwrap(), expected);
+    }
+
+    #[test]
+    fn day11_reads_each_monkey(
+        monkeys in vec(
+            (
+                vec(0..100u128, 0..5),
+                select(vec!['+', '*']),
+                prop::option::of(1..20u128),
+                1..30u128,
+                0..8u32,
+                0..8u32,
+            ),
+            1..8,
+        ),
+    ) {
+        let mut input = String::new();
+        let mut expected = Vec::new();
+        for (id, (items, op, rhs, divisor, if_true, if_false)) in monkeys.iter().enumerate() {
+            let items: Vec<String> = items.iter().map(u128::to_string).collect();
+            let rhs_text = rhs.map_or("old".to_string(), |rhs| rhs.to_string());
+            input += &format!("Monkey {}:\n", id);
+            input += &format!("  Starting items: {}\n", items.join(", "));
+            input += &format!("  Operation: new = old {} {}\n", op, rhs_text);
+            input += &format!("  Test: divisible by {}\n", divisor);
+            input += &format!("    If true: throw to monkey {}\n", if_true);
+            input += &format!("    If false: throw to monkey {}\n\n", if_false);
+            expected.push(format!(
+                "id : {}, rhs: {}, divisor: {}, monkeys to transfer to : {},{}, items: {}",
+                id,
+                rhs.map_or("None".to_string(), |rhs| rhs.to_string()),
+                divisor,
+                if_true,
+                if_false,
+                items.iter().map(|item| item.to_string() + ",").collect::<String>(),
+            ));
+        }
+        let model = day11::Day11::default().parse(&input).unwrap();
+        let read: Vec<String> = model.iter().map(|monkey| monkey.to_string()).collect();
+        prop_assert_eq!(read, expected);
+    }
+
+    #[test]
+    fn day12_reads_the_heightmap(
+        rows in 1..8usize,
+        cols in 2..8usize,
+        cells in vec(select(('a'..='z').collect::<Vec<_>>()), 64),
+        start in any::<prop::sample::Index>(),
+        end in any::<prop::sample::Index>(),
+    ) {
+        let mut cells: Vec<char> = cells[..rows * cols].to_vec();
+        let start = start.index(cells.len());
+        // anywhere but on the start
+        let end = (start + 1 + end.index(cells.len() - 1)) % cells.len();
+        cells[start] = 'S';
+        cells[end] = 'E';
+        let input: String = cells.chunks(cols).map(|row| row.iter().collect::<String>() + "\n").collect();
+
+        let model = day12::Day12.parse(&input).unwrap();
+        let expected = grid::Grid::from_vec(rows, cols, cells).unwrap();
+        prop_assert_eq!(model.heightmap(), &expected);
+        prop_assert_eq!(model.source(), (start / cols, start % cols));
+        prop_assert_eq!(model.dest(), (end / cols, end % cols));
+    }
+
+    #[test]
+    fn day13_reads_each_packet(packets in vec(packet(), 0..6)) {
+        let lines: Vec<String> = packets.iter().map(Packet::render).collect();
+        let model = day13::Day13.parse(&lines.join("\n")).unwrap();
+        let read: Vec<String> = model
+            .iter()
+            .map(|tree| render_tree(tree, tree.root_node_id().unwrap()))
+            .collect();
+        prop_assert_eq!(read, lines);
+    }
+
+    #[test]
+    fn day14_reads_each_path(
+        paths in vec(
+            ((400..600i64, 0..100i64), vec((any::<bool>(), -20..20i64), 1..5)),
+            0..6,
+        ),
+    ) {
+        // each path a start and turns along either x or y
+        let mut rocks: HashSet<Point> = HashSet::new();
+        let mut input = String::new();
+        for ((x, y), turns) in &paths {
+            let mut at = Point::new(*x, *y);
+            let mut corners = vec![at];
+            rocks.insert(at);
+            for &(along_x, length) in turns {
+                let step = if along_x { Point::new(length.signum(), 0) } else { Point::new(0, length.signum()) };
+                for _ in 0..length.abs() {
+                    at = Point::new(at.x + step.x, (at.y + step.y).max(0));
+                    rocks.insert(at);
+                }
+                corners.push(at);
+            }
+            let corners: Vec<String> = corners.iter().map(|at| format!("{},{}", at.x, at.y)).collect();
+            input += &(corners.join(" -> ") + "\n");
+        }
+
+        let canvas = day14::Day14::default().parse(&input).unwrap();
+        let top_left = canvas.picture_bounds().top_left();
+        let read: HashSet<Point> = canvas
+            .picture()
+            .iter()
+            .filter(|(_, cell)| **cell == '#')
+            .map(|((row, col), _)| Point::new(top_left.x + col as i64, top_left.y + row as i64))
+            .collect();
+        prop_assert_eq!(read, rocks);
+    }
+
+    #[test]
+    fn day15_reads_each_report(
+        sensor in (-10_000_000..10_000_000i64, -10_000_000..10_000_000i64),
+        beacon in (-10_000_000..10_000_000i64, -10_000_000..10_000_000i64),
+    ) {
+        let line = format!(
+            "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            sensor.0, sensor.1, beacon.0, beacon.1
+        );
+        let read = day15::sensors::parse::parser(&line);
+        prop_assert_eq!(read, Ok((Point::from(sensor), Point::from(beacon))));
+    }
+
+    #[test]
+    fn day16_reads_each_valve(
+        name in valve_name(),
+        flow_rate in 0..100u32,
+        tunnels in vec(valve_name(), 1..5),
+    ) {
+        use day16::valves::valve::{convert_name, convert_num};
+        let line = match tunnels.as_slice() {
+            [tunnel] => format!(
+                "Valve {} has flow rate={}; tunnel leads to valve {}",
+                name, flow_rate, tunnel
+            ),
+            _ => format!(
+                "Valve {} has flow rate={}; tunnels lead to valves {}",
+                name, flow_rate, tunnels.join(", ")
+            ),
+        };
+        let (valve, read) = day16::valves::parse::parse_input(&line).unwrap();
+        prop_assert_eq!(convert_num(valve.name), name);
+        prop_assert_eq!(valve.name, convert_name(convert_num(valve.name)));
+        prop_assert_eq!(valve.flow_rate, flow_rate);
+        prop_assert_eq!(read, tunnels);
+    }
+}
@@ -0,0 +1,60 @@
+use aoc::check::fixtures::Fixture;
+use common::profile::record;
+use common::Part;
+
+fn phases(day: u8, part: Part) -> Vec<(usize, String)> {
+    let root = aoc::workspace_root();
+    let input = aoc::read_input(&Fixture::Example.files(&root, day)).unwrap();
+    let (timed, phases) = record(|| aoc::solve(day, part, &input));
+    timed.unwrap();
+    phases
+        .into_iter()
+        .map(|phase| (phase.depth, phase.name))
+        .collect()
+}
+
+fn expected(names: &[(usize, &str)]) -> Vec<(usize, String)> {
+    names
+        .iter()
+        .map(|(depth, name)| (*depth, name.to_string()))
+        .collect()
+}
+
+#[test]
+fn every_day_parses_then_solves() {
+    for day in aoc::DAYS {
+        // day16's part 2 is not solved yet
+        let part = if day == 16 { Part::One } else { Part::Two };
+        let names = phases(day, part);
+        assert_eq!(names[0], (0, "parse".to_string()), "day {}", day);
+        assert!(
+            names.contains(&(0, format!("part {}", part))),
+            "day {}",
+            day
+        );
+    }
+}
+
+#[test]
+fn model_construction_is_a_phase_of_its_own() {
+    assert_eq!(
+        phases(12, Part::Two),
+        expected(&[
+            (0, "parse"),
+            (1, "heightmap"),
+            (1, "graph"),
+            (0, "part 2"),
+            (1, "astar")
+        ])
+    );
+    assert_eq!(
+        phases(16, Part::One),
+        expected(&[
+            (0, "parse"),
+            (1, "scan"),
+            (1, "distances"),
+            (0, "part 1"),
+            (1, "orders")
+        ])
+    );
+}
@@ -0,0 +1,123 @@
+use common::Solution;
+use day2::rules::engine::{Outcome, Rules};
+
+const RPS: &str = include_str!("../../day2/rules/rps.toml");
+
+// the scores of the puzzle for every round, by the letters of the guide
+#[test]
+fn rps_scores_as_the_puzzle() {
+    let rules = Rules::rps();
+    let played = [
+        ("A X", 4, 3),
+        ("A Y", 8, 4),
+        ("A Z", 3, 8),
+        ("B X", 1, 1),
+        ("B Y", 5, 5),
+        ("B Z", 9, 9),
+        ("C X", 7, 2),
+        ("C Y", 2, 6),
+        ("C Z", 6, 7),
+    ];
+    let day2 = day2::Day2::default();
+    for (round, part1, part2) in played {
+        let model = day2.parse(round).unwrap();
+        assert_eq!(day2.part1(&model).unwrap(), part1, "{}", round);
+        assert_eq!(day2.part2(&model).unwrap(), part2, "{}", round);
+    }
+    assert_eq!(rules.outcome_for('Z'), Some(Outcome::Win));
+    assert_eq!(rules.outcome_for('A'), None);
+}
+
+#[test]
+fn rpsls_moves_beat_two_others() {
+    let rules = Rules::rpsls();
+    let count = rules.moves().len();
+    assert_eq!(count, 5);
+    for player in 0..count {
+        let wins = (0..count)
+            .filter(|&opponent| rules.outcome(player, opponent) == Outcome::Win)
+            .count();
+        assert_eq!(wins, 2, "{}", rules.moves()[player].name);
+    }
+
+    // the letters of rpsls are checked when parsing the guide
+    let day2 = day2::Day2 { rules };
+    let round = day2.parse("E V\n").unwrap()[0];
+    assert_eq!((round.opponent, round.second), ('E', 'V'));
+    assert!(day2::Day2::default().parse("E V\n").is_err());
+}
+
+#[test]
+fn invalid_definitions_are_reported() {
+    let invalid = [
+        (
+            RPS.replace("player = \"Y\"", "player = \"X\""),
+            "player letter X is used twice!",
+        ),
+        (
+            RPS.replace("beats = [\"rock\"]", "beats = [\"rok\"]"),
+            "paper beats unknown move rok!",
+        ),
+        (
+            RPS.replace("beats = [\"rock\"]", "beats = [\"paper\"]"),
+            "paper cannot beat itself!",
+        ),
+        (
+            RPS.replace("beats = [\"rock\"]", "beats = [\"rock\", \"scissors\"]"),
+            "paper and scissors beat each other!",
+        ),
+        (
+            RPS.replace("name = \"paper\"", "name = \"rock\""),
+            "move rock is defined twice!",
+        ),
+        (
+            RPS.replace("letter = \"Y\"", "letter = \" \""),
+            "outcome letter cannot be blank!",
+        ),
+    ];
+    for (definition, message) in invalid {
+        let e = Rules::parse(&definition).unwrap_err();
+        assert_eq!(e.to_string(), message);
+    }
+    assert!(Rules::parse(&RPS.replace("score = 6", "score = \"six\"")).is_err());
+    assert!(Rules::load("rps").is_ok());
+}
+
+// scores that do not fit in an i32 are errors, not wrapping or panicking
+#[test]
+fn overflowing_scores_are_reported() {
+    let huge = RPS.replace("score = 6", &format!("score = {}", i32::MAX - 2));
+    let day2 = day2::Day2 {
+        rules: Rules::parse(&huge).unwrap(),
+    };
+    // winning with scissors, worth 3, overflows alone
+    let model = day2.parse("B Z").unwrap();
+    assert!(day2.part1(&model).is_err());
+    // winning with paper just fits, twice does not
+    let model = day2.parse("A Y").unwrap();
+    assert_eq!(day2.part1(&model).unwrap(), i32::MAX);
+    let model = day2.parse("A Y\nA Y").unwrap();
+    assert!(day2.part1(&model).is_err());
+    assert!(day2.part2(&day2.parse("A Z\nA Z").unwrap()).is_err());
+}
+
+// a file named as the built-in rules is read in their place
+#[test]
+fn rule_files_are_not_shadowed_by_the_builtins() {
+    let dir = std::env::temp_dir().join(format!("day2-rules-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let scissors = RPS.replace("score = 3\n", "score = 30\n");
+    std::fs::write(dir.join("rps"), &scissors).unwrap();
+
+    let cwd = std::env::current_dir().unwrap();
+    std::env::set_current_dir(&dir).unwrap();
+    let loaded = Rules::load("rps");
+    let builtin = Rules::load("rpsls");
+    std::env::set_current_dir(cwd).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let loaded = loaded.unwrap();
+    let scissors = loaded.player_move('Z').unwrap();
+    assert_eq!(loaded.moves()[scissors].score, 30);
+    assert_eq!(builtin.unwrap().moves().len(), 5);
+}
@@ -0,0 +1,41 @@
+use aoc::generate::shape::Shape;
+
+// small inputs, so that every day is quick to solve
+pub fn small(day: u8) -> Shape {
+    match day {
+        8 => Shape {
+            width: Some(12),
+            height: Some(9),
+            ..Default::default()
+        },
+        12 => Shape {
+            width: Some(30),
+            height: Some(8),
+            ..Default::default()
+        },
+        11 => Shape {
+            size: Some(5),
+            ..Default::default()
+        },
+        14 => Shape {
+            size: Some(20),
+            width: Some(20),
+            height: Some(15),
+        },
+        // checked with the parameters of the example
+        15 => Shape {
+            size: Some(10),
+            width: Some(20),
+            height: None,
+        },
+        16 => Shape {
+            size: Some(6),
+            ..Default::default()
+        },
+        _ => Shape {
+            size: Some(20),
+            width: None,
+            height: None,
+        },
+    }
+}
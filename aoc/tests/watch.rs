@@ -0,0 +1,50 @@
+use aoc::watch::diff::diff;
+use aoc::watch::files::{sources, Snapshot};
+use std::fs;
+
+#[test]
+fn diff_marks_changed_lines() {
+    assert_eq!(diff("13", "15"), vec!["- 13", "+ 15"]);
+    assert_eq!(
+        diff("##..\n#..#", "##..\n#.##\n...."),
+        vec!["  ##..", "- #..#", "+ #.##", "+ ...."]
+    );
+    assert_eq!(diff("CMZ\nMCD", "CMZ"), vec!["  CMZ", "- MCD"]);
+}
+
+#[test]
+fn snapshot_sees_modified_and_removed_files() {
+    let dir = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+    fs::create_dir_all(dir.join("src")).unwrap();
+    let start = dir.join("start.txt");
+    let moves = dir.join("src").join("moves.txt");
+    fs::write(&start, "[Z]\n 1 \n").unwrap();
+    fs::write(&moves, "move 1 from 1 to 1\n").unwrap();
+
+    let watched = vec![start.clone(), dir.join("src")];
+    let before = Snapshot::take(&watched);
+    assert!(Snapshot::take(&watched).changed(&before).is_empty());
+
+    fs::write(&moves, "move 1 from 1 to 2\nmove 1 from 2 to 1\n").unwrap();
+    assert_eq!(
+        Snapshot::take(&watched).changed(&before),
+        vec![moves.clone()]
+    );
+
+    fs::remove_file(&start).unwrap();
+    let after = Snapshot::take(&watched);
+    assert!(after.changed(&before).contains(&start));
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn sources_cover_the_day_and_shared_crates() {
+    let root = aoc::workspace_root();
+    let watched = sources(&root, 5);
+    assert!(watched.contains(&root.join("day5").join("src")));
+    assert!(watched.contains(&root.join("common").join("src")));
+    assert!(Snapshot::take(&watched)
+        .changed(&Snapshot::default())
+        .contains(&root.join("day5").join("src").join("lib.rs")));
+}
@@ -0,0 +1,203 @@
+use crate::output::format::Format;
+use crate::output::record::Record;
+use crate::verbosity::Verbosity;
+use crate::{input, Part, Solution, Solver};
+use clap::{ArgAction, Args};
+use std::error::Error;
+
+/// Command line arguments shared by the binaries of every day, flattened
+/// into the arguments of each day next to its puzzle parameters.
+#[derive(Args, Debug, Clone, PartialEq, Eq)]
+pub struct DayArgs {
+    /// part of the puzzle
+    // optional only to the days relaxing it, as day1 does for `--export`
+    #[arg(short, long, required = true, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub part: Option<u8>,
+
+    /// input file(s), day5 takes the starting configuration and the moves.
+    /// `-` reads from stdin
+    #[arg(short, long, required = true, num_args = 1..)]
+    pub input: Vec<String>,
+
+    /// only report errors
+    #[arg(short, long)]
+    pub quiet: bool,
+
+    /// report each step to stderr, twice to report every intermediate state
+    #[arg(short, long, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// print the bare answer, or a JSON record as `aoc run` does
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+}
+
+impl DayArgs {
+    /// The part chosen by `--part`
+    ///
+    /// ```
+    /// use common::cli::DayArgs;
+    /// use common::output::format::Format;
+    ///
+    /// let args = DayArgs {
+    ///     part: Some(2),
+    ///     input: vec!["-".to_string()],
+    ///     quiet: false,
+    ///     verbose: 1,
+    ///     format: Format::Text,
+    /// };
+    /// assert_eq!(args.part(), common::Part::Two);
+    /// ```
+    pub fn part(&self) -> Part {
+        // the parser only accepts 1 and 2
+        let part = self.part.expect("part is given");
+        Part::try_from(part).expect("part is 1 or 2")
+    }
+
+    /// Verbosity chosen by `--quiet` and `--verbose`
+    ///
+    /// ```
+    /// use common::cli::DayArgs;
+    /// use common::output::format::Format;
+    /// use common::verbosity::Verbosity;
+    ///
+    /// let args = DayArgs {
+    ///     part: Some(2),
+    ///     input: vec!["-".to_string()],
+    ///     quiet: false,
+    ///     verbose: 1,
+    ///     format: Format::Text,
+    /// };
+    /// assert_eq!(args.verbosity(), Verbosity::Verbose);
+    /// ```
+    pub fn verbosity(&self) -> Verbosity {
+        Verbosity::from_flags(self.quiet, self.verbose)
+    }
+
+    /// Contents of the input files, sections separated by a blank line
+    ///
+    /// ```
+    /// use common::cli::DayArgs;
+    /// use common::output::format::Format;
+    ///
+    /// let filename = std::env::temp_dir().join("common-cli-read.txt");
+    /// std::fs::write(&filename, "1\n2").unwrap();
+    /// let args = DayArgs {
+    ///     part: Some(2),
+    ///     input: vec![filename.to_string_lossy().to_string()],
+    ///     quiet: false,
+    ///     verbose: 1,
+    ///     format: Format::Text,
+    /// };
+    /// assert_eq!(args.read().unwrap(), "1\n2\n");
+    /// ```
+    pub fn read(&self) -> Result<String, Box<dyn Error>> {
+        input::read_sections(&self.input)
+    }
+
+    /// The answer to the chosen part of the input in the chosen format
+    ///
+    /// ```
+    /// use common::cli::DayArgs;
+    /// use common::output::format::Format;
+    /// use common::Solution;
+    /// use std::error::Error;
+    ///
+    /// // sum of the numbers of the input, and of their squares
+    /// struct Sum;
+    ///
+    /// impl Solution for Sum {
+    ///     type Model = Vec<u32>;
+    ///     type Answer1 = u32;
+    ///     type Answer2 = u32;
+    ///
+    ///     fn parse(&self, input: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+    ///         Ok(input.lines().map(|x| x.parse()).collect::<Result<_, _>>()?)
+    ///     }
+    ///
+    ///     fn part1(&self, model: &Vec<u32>) -> Result<u32, Box<dyn Error>> {
+    ///         Ok(model.iter().sum())
+    ///     }
+    ///
+    ///     fn part2(&self, model: &Vec<u32>) -> Result<u32, Box<dyn Error>> {
+    ///         Ok(model.iter().map(|x| x * x).sum())
+    ///     }
+    /// }
+    ///
+    /// let mut args = DayArgs {
+    ///     part: Some(2),
+    ///     input: vec!["-".to_string()],
+    ///     quiet: false,
+    ///     verbose: 1,
+    ///     format: Format::Text,
+    /// };
+    /// assert_eq!(args.answer(1, &Sum, "1\n2\n").unwrap(), "5");
+    /// args.format = Format::Json;
+    /// let record = args.answer(1, &Sum, "1\n2\n").unwrap();
+    /// assert!(record.starts_with(r#"{"day":1,"part":2,"answer":"5","#));
+    /// ```
+    pub fn answer<S: Solution>(
+        &self,
+        day: u8,
+        solution: &S,
+        contents: &str,
+    ) -> Result<String, Box<dyn Error>> {
+        match self.format {
+            Format::Text => solution.solve(contents, self.part()),
+            Format::Json => {
+                let timed = solution.timed(contents, &[self.part()])?;
+                Record::new(day, timed.parse, &timed.parts[0]).to_json()
+            }
+        }
+    }
+
+    /// Solve the chosen part of the input and print the answer.
+    ///
+    /// ```
+    /// use common::cli::DayArgs;
+    /// use common::output::format::Format;
+    /// use common::Solution;
+    /// use std::error::Error;
+    ///
+    /// // sum of the numbers of the input, and of their squares
+    /// struct Sum;
+    ///
+    /// impl Solution for Sum {
+    ///     type Model = Vec<u32>;
+    ///     type Answer1 = u32;
+    ///     type Answer2 = u32;
+    ///
+    ///     fn parse(&self, input: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+    ///         Ok(input.lines().map(|x| x.parse()).collect::<Result<_, _>>()?)
+    ///     }
+    ///
+    ///     fn part1(&self, model: &Vec<u32>) -> Result<u32, Box<dyn Error>> {
+    ///         Ok(model.iter().sum())
+    ///     }
+    ///
+    ///     fn part2(&self, model: &Vec<u32>) -> Result<u32, Box<dyn Error>> {
+    ///         Ok(model.iter().map(|x| x * x).sum())
+    ///     }
+    /// }
+    ///
+    /// let filename = std::env::temp_dir().join("common-cli-run.txt");
+    /// std::fs::write(&filename, "1\n2\n").unwrap();
+    /// let args = DayArgs {
+    ///     part: Some(2),
+    ///     input: vec![filename.to_string_lossy().to_string()],
+    ///     quiet: false,
+    ///     verbose: 1,
+    ///     format: Format::Text,
+    /// };
+    /// // prints 5
+    /// args.run(1, &Sum).unwrap();
+    /// std::fs::write(&filename, "x\n").unwrap();
+    /// assert!(args.run(1, &Sum).is_err());
+    /// ```
+    pub fn run<S: Solution>(&self, day: u8, solution: &S) -> Result<(), Box<dyn Error>> {
+        self.verbosity().init();
+        let contents = self.read()?;
+        println!("{}", self.answer(day, solution, &contents)?);
+        Ok(())
+    }
+}
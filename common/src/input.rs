@@ -0,0 +1,72 @@
+use fs_err as fs;
+use std::error::Error;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+// filename standing for the standard input
+pub const STDIN: &str = "-";
+
+/// Reader over a puzzle input file, or over stdin for `-`.
+///
+/// ```
+/// use std::io::BufRead;
+///
+/// let filename = std::env::temp_dir().join("common-input-open.txt");
+/// std::fs::write(&filename, "1\n2\n").unwrap();
+/// let lines: Vec<String> = common::input::open(&filename).unwrap().lines().map(Result::unwrap).collect();
+/// assert_eq!(lines, ["1", "2"]);
+/// assert!(common::input::open("missing.txt").is_err());
+/// ```
+pub fn open<P: AsRef<Path>>(filename: P) -> Result<Box<dyn BufRead>, Box<dyn Error>> {
+    let filename = filename.as_ref();
+    if filename == Path::new(STDIN) {
+        Ok(Box::new(BufReader::new(io::stdin())))
+    } else {
+        Ok(Box::new(BufReader::new(fs::File::open(filename)?)))
+    }
+}
+
+/// All of the input given by `reader`. Every day's input is small enough
+/// to be held in memory, so the solutions parse a `&str`.
+///
+/// ```
+/// assert_eq!(common::input::read("1\n2\n".as_bytes()).unwrap(), "1\n2\n");
+/// assert!(common::input::read([0xff, 0xfe].as_slice()).is_err());
+/// ```
+pub fn read<R: BufRead>(mut reader: R) -> Result<String, Box<dyn Error>> {
+    let mut contents = String::new();
+    reader.read_to_string(&mut contents)?;
+    Ok(contents)
+}
+
+/// Contents of a puzzle input file, or of stdin for `-`.
+///
+/// ```
+/// let filename = std::env::temp_dir().join("common-input-read-to-string.txt");
+/// std::fs::write(&filename, "abc\n").unwrap();
+/// assert_eq!(common::input::read_to_string(&filename).unwrap(), "abc\n");
+/// ```
+pub fn read_to_string<P: AsRef<Path>>(filename: P) -> Result<String, Box<dyn Error>> {
+    read(open(filename)?)
+}
+
+/// Puzzle input made of several files, each a section of the input.
+/// Sections are separated by an empty line, so day5 can keep the starting
+/// stack configuration and the moves in separate files.
+///
+/// ```
+/// let dir = std::env::temp_dir();
+/// let (start, moves) = (dir.join("common-input-start.txt"), dir.join("common-input-moves.txt"));
+/// std::fs::write(&start, "[A]\n 1\n\n").unwrap();
+/// std::fs::write(&moves, "move 1 from 1 to 1").unwrap();
+/// let input = common::input::read_sections(&[start, moves]).unwrap();
+/// assert_eq!(input, "[A]\n 1\n\nmove 1 from 1 to 1\n");
+/// ```
+pub fn read_sections<P: AsRef<Path>>(filenames: &[P]) -> Result<String, Box<dyn Error>> {
+    let mut sections: Vec<String> = Vec::with_capacity(filenames.len());
+    for filename in filenames {
+        let contents = read_to_string(filename)?;
+        sections.push(contents.trim_end_matches('\n').to_string());
+    }
+    Ok(sections.join("\n\n") + "\n")
+}
@@ -0,0 +1,328 @@
+use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+pub mod cli;
+pub mod input;
+pub mod output;
+pub mod profile;
+pub mod verbosity;
+
+/// One of the two parts of a day's puzzle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl TryFrom<u8> for Part {
+    type Error = Box<dyn Error>;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            _ => Err(format!("there is no part {} of a puzzle!", value).into()),
+        }
+    }
+}
+
+impl From<Part> for u8 {
+    fn from(part: Part) -> u8 {
+        match part {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+/// A day's solution: the input is parsed once into a typed model and both
+/// parts compute their answer from that model.
+///
+/// Puzzle parameters (marker sizes, rope lengths, ...) live on the
+/// implementing type, so a configured value can be used instead of the
+/// `Default` one.
+pub trait Solution {
+    type Model;
+    type Answer1: fmt::Display;
+    type Answer2: fmt::Display;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>>;
+
+    /// Parse the whole of `reader`, e.g. stdin or an in-memory buffer.
+    ///
+    /// ```
+    /// use common::Solution;
+    /// use std::error::Error;
+    ///
+    /// // sum of the numbers of the input, and of their squares
+    /// struct Sum;
+    ///
+    /// impl Solution for Sum {
+    ///     type Model = Vec<u32>;
+    ///     type Answer1 = u32;
+    ///     type Answer2 = u32;
+    ///
+    ///     fn parse(&self, input: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+    ///         Ok(input.lines().map(|x| x.parse()).collect::<Result<_, _>>()?)
+    ///     }
+    ///
+    ///     fn part1(&self, model: &Vec<u32>) -> Result<u32, Box<dyn Error>> {
+    ///         Ok(model.iter().sum())
+    ///     }
+    ///
+    ///     fn part2(&self, model: &Vec<u32>) -> Result<u32, Box<dyn Error>> {
+    ///         Ok(model.iter().map(|x| x * x).sum())
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(Sum.parse_reader("1\n2\n".as_bytes()).unwrap(), [1, 2]);
+    /// ```
+    fn parse_reader<R: BufRead>(&self, reader: R) -> Result<Self::Model, Box<dyn Error>>
+    where
+        Self: Sized,
+    {
+        self.parse(&input::read(reader)?)
+    }
+
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>>;
+
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>>;
+
+    /// Values worked out along the way to the part 1 answer, e.g. the
+    /// location behind a tuning frequency, as (name, value) pairs.
+    fn details1(&self, _answer: &Self::Answer1) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+
+    fn details2(&self, _answer: &Self::Answer2) -> Vec<(&'static str, String)> {
+        Vec::new()
+    }
+}
+
+/// An error in the puzzle input, pointing at the offending text. The kind
+/// of error is specific to each day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError<K> {
+    pub kind: K,
+    // line and column are counted from 1
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+impl<K> ParseError<K> {
+    /// Error for `text` at `line` and `column`, both counted from 1
+    ///
+    /// ```
+    /// let error = common::ParseError::new("invalid number", 3, 7, "x");
+    /// assert_eq!(error.to_string(), "line 3, column 7: invalid number `x`");
+    /// ```
+    pub fn new(kind: K, line: usize, column: usize, text: &str) -> ParseError<K> {
+        ParseError {
+            kind,
+            line,
+            column,
+            text: text.to_string(),
+        }
+    }
+
+    /// Error for `text` at line index `lineidx` (counted from 0, as given by
+    /// `lines().enumerate()`). `text` should be a slice of `line`, the
+    /// column falls back to the start of the line otherwise.
+    ///
+    /// ```
+    /// let line = "move 1 from x to 1";
+    /// let error = common::ParseError::at("invalid stack", 4, line, &line[12..13]);
+    /// assert_eq!(error.to_string(), "line 5, column 13: invalid stack `x`");
+    /// // text not taken from the line points at its start
+    /// assert_eq!(common::ParseError::at("invalid stack", 4, line, "x").column, 1);
+    /// ```
+    pub fn at(kind: K, lineidx: usize, line: &str, text: &str) -> ParseError<K> {
+        let start = line.as_ptr() as usize;
+        let offset = (text.as_ptr() as usize).wrapping_sub(start);
+        let column = if offset <= line.len() {
+            line[..offset].chars().count() + 1
+        } else {
+            1
+        };
+        ParseError::new(kind, lineidx + 1, column, text)
+    }
+
+    /// Error covering the whole of the line at index `lineidx`
+    ///
+    /// ```
+    /// let error = common::ParseError::line("invalid move", 0, "move 1");
+    /// assert_eq!(error.to_string(), "line 1, column 1: invalid move `move 1`");
+    /// ```
+    pub fn line(kind: K, lineidx: usize, line: &str) -> ParseError<K> {
+        ParseError::new(kind, lineidx + 1, 1, line)
+    }
+
+    /// The same error with a kind of another crate, e.g. to report an error
+    /// of a shared parser as one of the day using it.
+    ///
+    /// ```
+    /// let error = common::ParseError::new("invalid cell", 2, 3, "x");
+    /// let error = error.map_kind(|kind| format!("{} height", kind));
+    /// assert_eq!(error.to_string(), "line 2, column 3: invalid cell height `x`");
+    /// ```
+    pub fn map_kind<L, F: FnOnce(K) -> L>(self, f: F) -> ParseError<L> {
+        ParseError {
+            kind: f(self.kind),
+            line: self.line,
+            column: self.column,
+            text: self.text,
+        }
+    }
+}
+
+impl<K: fmt::Display> fmt::Display for ParseError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {} `{}`",
+            self.line, self.column, self.kind, self.text
+        )
+    }
+}
+
+impl<K: fmt::Display + fmt::Debug> Error for ParseError<K> {}
+
+/// Answer to one part of a puzzle and the time it took.
+#[derive(Clone, Debug)]
+pub struct Answer {
+    pub part: Part,
+    pub value: String,
+    // see `Solution::details1`
+    pub details: Vec<(&'static str, String)>,
+    pub elapsed: Duration,
+}
+
+/// Answers of a solution together with the time each phase took.
+#[derive(Clone, Debug)]
+pub struct Timed {
+    pub parse: Duration,
+    pub parts: Vec<Answer>,
+}
+
+/// Object safe view of a `Solution`, so that the solutions of different
+/// days can be stored and run side by side.
+pub trait Solver {
+    /// Parse the input and solve one part of it
+    ///
+    /// ```
+    /// use common::{Part, Solver};
+    ///
+    /// let solvers: Vec<(&str, Box<dyn Solver>)> = vec![("day6", Box::new(Marker))];
+    /// assert_eq!(solvers[0].1.solve("abcd", Part::One).unwrap(), "3");
+    /// assert!(solvers[0].1.solve("", Part::Two).is_err());
+    ///
+    /// // position of the first `d`, with part 2 failing
+    /// struct Marker;
+    ///
+    /// impl common::Solution for Marker {
+    ///     type Model = String;
+    ///     type Answer1 = usize;
+    ///     type Answer2 = usize;
+    ///
+    ///     fn parse(&self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
+    ///         Ok(input.to_string())
+    ///     }
+    ///
+    ///     fn part1(&self, model: &String) -> Result<usize, Box<dyn std::error::Error>> {
+    ///         Ok(model.find('d').ok_or("no d!")?)
+    ///     }
+    ///
+    ///     fn part2(&self, _: &String) -> Result<usize, Box<dyn std::error::Error>> {
+    ///         Err("no part 2!".into())
+    ///     }
+    /// }
+    /// ```
+    fn solve(&self, input: &str, part: Part) -> Result<String, Box<dyn Error>>;
+
+    /// Parse the input once and solve the given parts, timing each phase.
+    ///
+    /// ```
+    /// use common::{Part, Solution, Solver};
+    /// use std::error::Error;
+    ///
+    /// // sum of the numbers of the input, and of their squares
+    /// struct Sum;
+    ///
+    /// impl Solution for Sum {
+    ///     type Model = Vec<u32>;
+    ///     type Answer1 = u32;
+    ///     type Answer2 = u32;
+    ///
+    ///     fn parse(&self, input: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+    ///         Ok(input.lines().map(|x| x.parse()).collect::<Result<_, _>>()?)
+    ///     }
+    ///
+    ///     fn part1(&self, model: &Vec<u32>) -> Result<u32, Box<dyn Error>> {
+    ///         Ok(model.iter().sum())
+    ///     }
+    ///
+    ///     fn part2(&self, model: &Vec<u32>) -> Result<u32, Box<dyn Error>> {
+    ///         Ok(model.iter().map(|x| x * x).sum())
+    ///     }
+    /// }
+    ///
+    /// let timed = Sum.timed("1\n2\n", &[Part::Two, Part::One]).unwrap();
+    /// let answers: Vec<_> = timed.parts.iter().map(|x| (x.part, x.value.as_str())).collect();
+    /// assert_eq!(answers, [(Part::Two, "5"), (Part::One, "3")]);
+    /// assert!(Sum.timed("x\n", &[Part::One]).is_err());
+    /// ```
+    fn timed(&self, input: &str, parts: &[Part]) -> Result<Timed, Box<dyn Error>>;
+}
+
+impl<S: Solution> Solver for S {
+    fn solve(&self, input: &str, part: Part) -> Result<String, Box<dyn Error>> {
+        let model = self.parse(input)?;
+        match part {
+            Part::One => Ok(self.part1(&model)?.to_string()),
+            Part::Two => Ok(self.part2(&model)?.to_string()),
+        }
+    }
+
+    fn timed(&self, input: &str, parts: &[Part]) -> Result<Timed, Box<dyn Error>> {
+        let start = Instant::now();
+        let model = profile::phase("parse", || self.parse(input))?;
+        let parse = start.elapsed();
+
+        let mut timed = Timed {
+            parse,
+            parts: Vec::with_capacity(parts.len()),
+        };
+        for part in parts {
+            let start = Instant::now();
+            let (value, details) = match part {
+                Part::One => {
+                    let answer = profile::phase("part 1", || self.part1(&model))?;
+                    (answer.to_string(), self.details1(&answer))
+                }
+                Part::Two => {
+                    let answer = profile::phase("part 2", || self.part2(&model))?;
+                    (answer.to_string(), self.details2(&answer))
+                }
+            };
+            timed.parts.push(Answer {
+                part: *part,
+                value,
+                details,
+                elapsed: start.elapsed(),
+            });
+        }
+        Ok(timed)
+    }
+}
@@ -0,0 +1,102 @@
+pub mod format {
+
+    use clap::ValueEnum;
+
+    /// How answers are printed
+    #[derive(ValueEnum, Copy, Clone, Debug, Default, PartialEq, Eq)]
+    pub enum Format {
+        // just the answer, or a line per part for `aoc check`
+        #[default]
+        Text,
+        // a JSON record per line, see `Record`
+        Json,
+    }
+}
+
+pub mod record {
+
+    use crate::Answer;
+    use serde::Serialize;
+    use std::collections::BTreeMap;
+    use std::error::Error;
+    use std::time::Duration;
+
+    /// One part of a day as printed by `--format json`
+    #[derive(Serialize, Clone, Debug, Default, PartialEq, Eq)]
+    pub struct Record {
+        pub day: u8,
+        pub part: u8,
+        pub answer: Option<String>,
+        pub parse_nanos: u64,
+        pub solve_nanos: u64,
+        // values worked out along the way, e.g. the location of day15's
+        // distress beacon
+        pub details: BTreeMap<String, String>,
+        // only set by `aoc check`
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub fixture: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub status: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub expected: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub error: Option<String>,
+    }
+
+    impl Record {
+        /// Record of the answer to one part of a day, parsed in `parse`
+        ///
+        /// ```
+        /// use common::output::record::Record;
+        /// use common::{Answer, Part};
+        /// use std::time::Duration;
+        ///
+        /// let answer = Answer {
+        ///     part: Part::Two,
+        ///     value: "56000011".to_string(),
+        ///     details: vec![("x", "14".to_string())],
+        ///     elapsed: Duration::from_nanos(200),
+        /// };
+        /// let record = Record::new(15, Duration::from_nanos(100), &answer);
+        /// assert_eq!((record.day, record.part, record.parse_nanos, record.solve_nanos), (15, 2, 100, 200));
+        /// assert_eq!(record.details["x"], "14");
+        /// assert_eq!(record.status, None);
+        /// ```
+        pub fn new(day: u8, parse: Duration, answer: &Answer) -> Record {
+            Record {
+                day,
+                part: answer.part.into(),
+                answer: Some(answer.value.clone()),
+                parse_nanos: parse.as_nanos() as u64,
+                solve_nanos: answer.elapsed.as_nanos() as u64,
+                details: answer
+                    .details
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.clone()))
+                    .collect(),
+                ..Default::default()
+            }
+        }
+
+        /// The record as a single line of JSON, so that a stream of records
+        /// is JSON lines
+        ///
+        /// ```
+        /// use common::output::record::Record;
+        ///
+        /// let record = Record {
+        ///     day: 6,
+        ///     part: 1,
+        ///     answer: Some("7".to_string()),
+        ///     ..Default::default()
+        /// };
+        /// assert_eq!(
+        ///     record.to_json().unwrap(),
+        ///     r#"{"day":6,"part":1,"answer":"7","parse_nanos":0,"solve_nanos":0,"details":{}}"#
+        /// );
+        /// ```
+        pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+            Ok(serde_json::to_string(self)?)
+        }
+    }
+}
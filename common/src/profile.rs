@@ -0,0 +1,221 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED: AtomicU64 = AtomicU64::new(0);
+
+/// System allocator counting the allocations of every thread. A binary
+/// reporting allocations per phase installs it with `#[global_allocator]`.
+pub struct CountingAllocator;
+
+fn count(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    ALLOCATED.fetch_add(size as u64, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        count(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    // a grown vector or string counts as a new allocation
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        count(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+/// Allocations so far and the bytes they asked for, both stay at 0
+/// without the counting allocator.
+///
+/// ```
+/// // no counting allocator installed in this test
+/// let _numbers: Vec<u64> = (0..100).collect();
+/// assert_eq!(common::profile::allocations(), (0, 0));
+/// ```
+pub fn allocations() -> (u64, u64) {
+    (
+        ALLOCATIONS.load(Ordering::Relaxed),
+        ALLOCATED.load(Ordering::Relaxed),
+    )
+}
+
+/// Time and allocations of one phase of a solution, summed over the calls
+/// of the phase within the same enclosing phase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Phase {
+    pub name: String,
+    // number of enclosing phases
+    pub depth: usize,
+    pub calls: u64,
+    pub elapsed: Duration,
+    pub allocations: u64,
+    pub bytes: u64,
+}
+
+#[derive(Default)]
+struct Recording {
+    phases: Vec<Phase>,
+    // indices of the phases running now, outermost first
+    open: Vec<usize>,
+}
+
+impl Recording {
+    // the phase of that name in the innermost running phase, added if it
+    // is the first call
+    fn enter(&mut self, name: &str) -> usize {
+        let parent = self.open.last().copied();
+        let depth = self.open.len();
+        let siblings = parent.map_or(0, |idx| idx + 1);
+        let existing = self.phases[siblings..]
+            .iter()
+            .position(|phase| phase.depth == depth && phase.name == name)
+            .map(|idx| idx + siblings)
+            // phases of another parent come after a shallower one
+            .filter(|&idx| {
+                self.phases[siblings..idx]
+                    .iter()
+                    .all(|phase| phase.depth >= depth)
+            });
+        let idx = existing.unwrap_or_else(|| {
+            self.phases.push(Phase {
+                name: name.to_string(),
+                depth,
+                calls: 0,
+                elapsed: Duration::ZERO,
+                allocations: 0,
+                bytes: 0,
+            });
+            self.phases.len() - 1
+        });
+        self.open.push(idx);
+        idx
+    }
+}
+
+thread_local! {
+    // phases of the `record` running on this thread
+    static RECORDING: RefCell<Option<Recording>> = const { RefCell::new(None) };
+}
+
+/// Run `f` as the phase `name` of the enclosing `record`, or just run it
+/// when nothing is recorded.
+///
+/// ```
+/// use common::profile::{phase, record};
+///
+/// assert_eq!(phase("parse", || 3), 3);
+/// let (sum, phases) = record(|| phase("parse", || phase("line", || 1) + phase("line", || 2)));
+/// assert_eq!(sum, 3);
+/// let phases: Vec<_> = phases.iter().map(|x| (x.name.as_str(), x.depth, x.calls)).collect();
+/// assert_eq!(phases, [("parse", 0, 1), ("line", 1, 2)]);
+/// ```
+pub fn phase<T, F: FnOnce() -> T>(name: &str, f: F) -> T {
+    let idx = RECORDING.with(|recording| {
+        recording
+            .borrow_mut()
+            .as_mut()
+            .map(|recording| recording.enter(name))
+    });
+    let Some(idx) = idx else {
+        return f();
+    };
+
+    let (allocations_before, bytes_before) = allocations();
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+    let (allocations_after, bytes_after) = allocations();
+
+    RECORDING.with(|recording| {
+        if let Some(recording) = recording.borrow_mut().as_mut() {
+            recording.open.pop();
+            let phase = &mut recording.phases[idx];
+            phase.calls += 1;
+            phase.elapsed += elapsed;
+            phase.allocations += allocations_after - allocations_before;
+            phase.bytes += bytes_after - bytes_before;
+        }
+    });
+    result
+}
+
+/// Run `f` and return the phases it went through on this thread, in the
+/// order they started. Allocations are counted on every thread.
+///
+/// ```
+/// use common::profile::{phase, record};
+///
+/// let ((), phases) = record(|| {
+///     phase("part 1", || ());
+///     phase("part 2", || ());
+/// });
+/// let names: Vec<_> = phases.iter().map(|x| x.name.as_str()).collect();
+/// assert_eq!(names, ["part 1", "part 2"]);
+/// // nothing recorded outside of it
+/// assert!(record(|| ()).1.is_empty());
+/// ```
+pub fn record<T, F: FnOnce() -> T>(f: F) -> (T, Vec<Phase>) {
+    let previous = RECORDING.with(|recording| recording.replace(Some(Recording::default())));
+    let result = f();
+    let recording = RECORDING.with(|recording| recording.replace(previous));
+    (result, recording.map(|x| x.phases).unwrap_or_default())
+}
+
+/// The phases as a table, nested phases indented under their parent
+///
+/// ```
+/// use common::profile::{phase, record, report};
+///
+/// let (_, phases) = record(|| phase("parse", || phase("line", || 1) + phase("line", || 2)));
+/// let report = report(&phases);
+/// let lines: Vec<&str> = report.lines().collect();
+/// assert!(lines[0].starts_with("phase "));
+/// assert!(lines[1].starts_with("parse "));
+/// assert!(lines[2].starts_with("  line x2 "));
+/// ```
+pub fn report(phases: &[Phase]) -> String {
+    let names: Vec<String> = phases
+        .iter()
+        .map(|phase| match phase.calls {
+            1 => format!("{}{}", "  ".repeat(phase.depth), phase.name),
+            calls => format!("{}{} x{}", "  ".repeat(phase.depth), phase.name, calls),
+        })
+        .collect();
+    let width = names
+        .iter()
+        .map(|x| x.len())
+        .fold("phase".len(), usize::max);
+
+    let mut report = format!(
+        "{:<width$}  {:>10}  {:>11}  {:>13}\n",
+        "phase",
+        "time",
+        "allocations",
+        "bytes",
+        width = width
+    );
+    for (name, phase) in names.iter().zip(phases) {
+        report += &format!(
+            "{:<width$}  {:>10}  {:>11}  {:>13}\n",
+            name,
+            format!("{:.1?}", phase.elapsed),
+            phase.allocations,
+            phase.bytes,
+            width = width
+        );
+    }
+    report
+}
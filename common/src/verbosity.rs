@@ -0,0 +1,94 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// How much a solution reports while it runs. Answers are printed to
+/// stdout whatever the verbosity, diagnostics are logged to stderr.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    // errors only
+    Quiet,
+    // and warnings about odd inputs
+    #[default]
+    Normal,
+    // and a summary of each step, e.g. each pair of packets
+    Verbose,
+    // and every intermediate state, e.g. each knot after each move
+    Trace,
+}
+
+impl Verbosity {
+    /// Verbosity given by `-q` and the number of `-v` flags
+    ///
+    /// ```
+    /// use common::verbosity::Verbosity;
+    ///
+    /// assert_eq!(Verbosity::from_flags(false, 0), Verbosity::Normal);
+    /// assert_eq!(Verbosity::from_flags(false, 3), Verbosity::Trace);
+    /// // quiet wins over verbose
+    /// assert_eq!(Verbosity::from_flags(true, 1), Verbosity::Quiet);
+    /// ```
+    pub fn from_flags(quiet: bool, verbose: u8) -> Verbosity {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::Trace,
+        }
+    }
+
+    /// Most detailed level of the messages logged
+    ///
+    /// ```
+    /// use common::verbosity::Verbosity;
+    /// use log::LevelFilter;
+    ///
+    /// assert_eq!(Verbosity::Normal.level_filter(), LevelFilter::Warn);
+    /// assert_eq!(Verbosity::Verbose.level_filter(), LevelFilter::Debug);
+    /// ```
+    pub fn level_filter(&self) -> LevelFilter {
+        match self {
+            Verbosity::Quiet => LevelFilter::Error,
+            Verbosity::Normal => LevelFilter::Warn,
+            Verbosity::Verbose => LevelFilter::Debug,
+            Verbosity::Trace => LevelFilter::Trace,
+        }
+    }
+
+    /// Log to stderr from now on, a second call only changes the level.
+    ///
+    /// ```
+    /// use common::verbosity::Verbosity;
+    ///
+    /// Verbosity::Quiet.init();
+    /// assert!(!log::log_enabled!(log::Level::Warn));
+    /// Verbosity::Trace.init();
+    /// assert!(log::log_enabled!(log::Level::Trace));
+    /// ```
+    pub fn init(self) {
+        // fails if the logger is already set, which is fine
+        let _ = log::set_logger(&STDERR);
+        log::set_max_level(self.level_filter());
+    }
+}
+
+struct StderrLogger;
+
+static STDERR: StderrLogger = StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        match record.level() {
+            Level::Error => eprintln!("error: {}", record.args()),
+            Level::Warn => eprintln!("warning: {}", record.args()),
+            _ => eprintln!("{}", record.args()),
+        }
+    }
+
+    fn flush(&self) {}
+}
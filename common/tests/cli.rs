@@ -0,0 +1,143 @@
+use clap::Parser;
+use common::cli::DayArgs;
+use common::verbosity::Verbosity;
+use common::Part;
+
+#[derive(Parser, Debug)]
+struct Args {
+    #[command(flatten)]
+    day: DayArgs,
+
+    #[arg(long)]
+    rope_length: Option<usize>,
+}
+
+fn parse(args: &[&str]) -> Result<Args, clap::Error> {
+    Args::try_parse_from(["day9"].iter().chain(args))
+}
+
+#[test]
+fn day_arguments_sit_next_to_the_puzzle_parameters() {
+    let args = parse(&[
+        "--part",
+        "2",
+        "--input",
+        "day9/test.txt",
+        "--rope-length",
+        "3",
+    ])
+    .unwrap();
+    assert_eq!(args.day.part(), Part::Two);
+    assert_eq!(args.day.input, ["day9/test.txt"]);
+    assert_eq!(args.day.verbosity(), Verbosity::Normal);
+    assert_eq!(args.rope_length, Some(3));
+
+    let args = parse(&["-p", "1", "-i", "day5/start.txt", "day5/input.txt", "-vv"]).unwrap();
+    assert_eq!(args.day.part(), Part::One);
+    assert_eq!(args.day.input.len(), 2);
+    assert_eq!(args.day.verbosity(), Verbosity::Trace);
+    assert_eq!(args.rope_length, None);
+}
+
+#[test]
+fn missing_or_invalid_arguments_are_usage_errors() {
+    use clap::error::ErrorKind;
+    let kind = |args: &[&str]| parse(args).unwrap_err().kind();
+    assert_eq!(kind(&["--input", "-"]), ErrorKind::MissingRequiredArgument);
+    assert_eq!(kind(&["--part", "1"]), ErrorKind::MissingRequiredArgument);
+    assert_eq!(
+        kind(&["--part", "3", "--input", "-"]),
+        ErrorKind::ValueValidation
+    );
+    assert_eq!(
+        kind(&["-p", "1", "-i", "-", "--rope-length", "x"]),
+        ErrorKind::ValueValidation
+    );
+    assert_eq!(
+        kind(&["-p", "1", "-i", "-", "--marker-size", "4"]),
+        ErrorKind::UnknownArgument
+    );
+}
+
+#[test]
+fn sections_are_separated_by_an_empty_line() {
+    let dir = std::env::temp_dir().join(format!("common-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (start, moves) = (dir.join("start.txt"), dir.join("moves.txt"));
+    std::fs::write(&start, "    [D]\n[N] [C]\n 1   2\n\n").unwrap();
+    std::fs::write(&moves, "move 1 from 2 to 1\n").unwrap();
+    assert_eq!(
+        common::input::read_sections(&[start, moves]).unwrap(),
+        "    [D]\n[N] [C]\n 1   2\n\nmove 1 from 2 to 1\n"
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+// counts the lines of the input, and their characters in part 2
+struct Lines;
+
+impl common::Solution for Lines {
+    type Model = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<usize>, Box<dyn std::error::Error>> {
+        Ok(input.lines().map(str::len).collect())
+    }
+
+    fn part1(&self, model: &Vec<usize>) -> Result<usize, Box<dyn std::error::Error>> {
+        Ok(model.len())
+    }
+
+    fn part2(&self, model: &Vec<usize>) -> Result<usize, Box<dyn std::error::Error>> {
+        Ok(model.iter().sum())
+    }
+}
+
+#[test]
+fn answers_are_printed_bare_or_as_json_records() {
+    let args = parse(&["-p", "2", "-i", "-"]).unwrap();
+    assert_eq!(args.day.answer(9, &Lines, "ab\ncde\n").unwrap(), "5");
+
+    let args = parse(&["-p", "2", "-i", "-", "--format", "json"]).unwrap();
+    let printed = args.day.answer(9, &Lines, "ab\ncde\n").unwrap();
+    assert!(!printed.contains('\n'));
+    let json: serde_json::Value = serde_json::from_str(&printed).unwrap();
+    assert_eq!(
+        (json["day"].as_u64(), json["part"].as_u64()),
+        (Some(9), Some(2))
+    );
+    assert_eq!(json["answer"], "5");
+    assert!(json["solve_nanos"].is_u64());
+}
+
+// as day1 does, needing no part to export the elves
+#[test]
+fn days_can_make_the_part_optional() {
+    use clap::{CommandFactory, FromArgMatches};
+
+    #[derive(Parser, Debug)]
+    struct Export {
+        #[command(flatten)]
+        day: DayArgs,
+
+        #[arg(long)]
+        export: bool,
+    }
+
+    let parse = |args: &[&str]| {
+        Export::command()
+            .mut_arg("part", |arg| {
+                arg.required(false).required_unless_present("export")
+            })
+            .try_get_matches_from(["day1"].iter().chain(args))
+            .and_then(|matches| Export::from_arg_matches(&matches))
+    };
+    let args = parse(&["-i", "-", "--export"]).unwrap();
+    assert_eq!(args.day.part, None);
+    assert!(parse(&["-i", "-"]).is_err());
+    assert_eq!(
+        parse(&["-i", "-", "-p", "2"]).unwrap().day.part(),
+        Part::Two
+    );
+}
@@ -0,0 +1,70 @@
+use common::profile::{allocations, phase, record, report, CountingAllocator};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn names(phases: &[common::profile::Phase]) -> Vec<(usize, &str, u64)> {
+    phases
+        .iter()
+        .map(|phase| (phase.depth, phase.name.as_str(), phase.calls))
+        .collect()
+}
+
+#[test]
+fn phases_are_only_recorded_within_record() {
+    assert_eq!(phase("parse", || 1 + 1), 2);
+    let (sum, phases) = record(|| 1 + 1);
+    assert_eq!(sum, 2);
+    assert!(phases.is_empty());
+}
+
+#[test]
+fn calls_of_a_phase_are_summed_under_their_parent() {
+    let (_, phases) = record(|| {
+        phase("parse", || phase("scan", || ()));
+        phase("part 1", || {
+            for _ in 0..3 {
+                phase("search", || ());
+            }
+            phase("release", || ());
+        });
+        phase("part 2", || phase("search", || ()));
+    });
+    assert_eq!(
+        names(&phases),
+        [
+            (0, "parse", 1),
+            (1, "scan", 1),
+            (0, "part 1", 1),
+            (1, "search", 3),
+            (1, "release", 1),
+            (0, "part 2", 1),
+            (1, "search", 1),
+        ]
+    );
+    let parent = phases[2].elapsed;
+    assert!(phases[3].elapsed + phases[4].elapsed <= parent);
+}
+
+#[test]
+fn allocations_are_counted_per_phase() {
+    let before = allocations();
+    let (vectors, phases) = record(|| {
+        phase("allocate", || {
+            (0..10)
+                .map(|x| Vec::<u64>::with_capacity(x + 1))
+                .collect::<Vec<_>>()
+        })
+    });
+    assert_eq!(vectors.len(), 10);
+    assert!(allocations().0 >= before.0 + 11);
+    // other tests may allocate on their own threads at the same time
+    assert!(phases[0].allocations >= 11);
+    assert!(phases[0].bytes >= 8 * 55);
+
+    let table = report(&phases);
+    let lines: Vec<&str> = table.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("phase"));
+    assert!(lines[1].starts_with("allocate"));
+}
@@ -0,0 +1,11 @@
+use common::verbosity::Verbosity;
+
+#[test]
+fn flags_select_a_level() {
+    assert_eq!(Verbosity::from_flags(false, 0), Verbosity::Normal);
+    assert_eq!(Verbosity::from_flags(false, 1), Verbosity::Verbose);
+    assert_eq!(Verbosity::from_flags(false, 3), Verbosity::Trace);
+    assert_eq!(Verbosity::from_flags(true, 2), Verbosity::Quiet);
+    assert!(Verbosity::Quiet.level_filter() < Verbosity::Normal.level_filter());
+    assert!(Verbosity::Verbose.level_filter() < Verbosity::Trace.level_filter());
+}
@@ -0,0 +1,553 @@
+pub mod stats {
+    use std::error::Error;
+
+    /// An elf and the calories it carries, elves are numbered from 1 in
+    /// the order of the inventory
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct Elf {
+        pub number: usize,
+        pub calories: i64,
+    }
+
+    /// Elves whose totals fall within `low..=high`
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub struct Bin {
+        pub low: i64,
+        pub high: i64,
+        pub count: usize,
+    }
+
+    /// Calories carried by each elf of an inventory
+    ///
+    /// ```
+    /// use day1::calories::stats::{Elf, Stats};
+    ///
+    /// let stats = Stats::new(&[6000, 4000, 11000, 24000, 10000]);
+    /// assert_eq!(stats.len(), 5);
+    /// assert_eq!(stats.most(), Some(Elf { number: 4, calories: 24000 }));
+    /// assert_eq!(stats.elves()[0], Elf { number: 1, calories: 6000 });
+    /// ```
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct Stats {
+        // in the order of the inventory
+        elves: Vec<Elf>,
+        // indices of the elves, the most calories first
+        ranked: Vec<usize>,
+    }
+
+    impl Stats {
+        pub fn new(totals: &[i64]) -> Stats {
+            let elves: Vec<Elf> = totals
+                .iter()
+                .enumerate()
+                .map(|(idx, &calories)| Elf {
+                    number: idx + 1,
+                    calories,
+                })
+                .collect();
+            let mut ranked: Vec<usize> = (0..elves.len()).collect();
+            // a stable sort keeps elves carrying as much in inventory order
+            ranked.sort_by_key(|&idx| std::cmp::Reverse(elves[idx].calories));
+            Stats { elves, ranked }
+        }
+
+        pub fn len(&self) -> usize {
+            self.elves.len()
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.elves.is_empty()
+        }
+
+        pub fn elves(&self) -> &[Elf] {
+            &self.elves
+        }
+
+        /// The elf carrying the most calories, the first of them on a tie
+        pub fn most(&self) -> Option<Elf> {
+            self.top(1).first().copied()
+        }
+
+        /// The `n` elves carrying the most calories, the most first. Fewer
+        /// are returned if there are not that many elves.
+        ///
+        /// ```
+        /// use day1::calories::stats::Stats;
+        ///
+        /// let stats = Stats::new(&[6000, 4000, 11000, 24000, 10000]);
+        /// let numbers: Vec<usize> = stats.top(3).iter().map(|elf| elf.number).collect();
+        /// assert_eq!(numbers, [4, 3, 5]);
+        /// assert_eq!(stats.top(10).len(), 5);
+        /// ```
+        pub fn top(&self, n: usize) -> Vec<Elf> {
+            self.ranked
+                .iter()
+                .take(n)
+                .map(|&idx| self.elves[idx])
+                .collect()
+        }
+
+        /// The `n` elves carrying the least calories, the least first
+        ///
+        /// ```
+        /// use day1::calories::stats::Stats;
+        ///
+        /// let stats = Stats::new(&[6000, 4000, 11000, 24000, 10000]);
+        /// let calories: Vec<i64> = stats.bottom(2).iter().map(|elf| elf.calories).collect();
+        /// assert_eq!(calories, [4000, 6000]);
+        /// ```
+        pub fn bottom(&self, n: usize) -> Vec<Elf> {
+            self.ranked
+                .iter()
+                .rev()
+                .take(n)
+                .map(|&idx| self.elves[idx])
+                .collect()
+        }
+
+        /// Calories carried by all the elves together, in i128 as the
+        /// totals of a few elves may already overflow an i64
+        ///
+        /// ```
+        /// use day1::calories::stats::Stats;
+        ///
+        /// let stats = Stats::new(&[i64::MAX, i64::MAX]);
+        /// assert_eq!(stats.total(), 2 * i64::MAX as i128);
+        /// assert_eq!(stats.mean(), Some(i64::MAX as f64));
+        /// ```
+        pub fn total(&self) -> i128 {
+            self.elves.iter().map(|elf| elf.calories as i128).sum()
+        }
+
+        pub fn mean(&self) -> Option<f64> {
+            match self.len() {
+                0 => None,
+                len => Some(self.total() as f64 / len as f64),
+            }
+        }
+
+        /// Middle total, the mean of the two middle ones for an even number
+        /// of elves
+        ///
+        /// ```
+        /// use day1::calories::stats::Stats;
+        ///
+        /// assert_eq!(Stats::new(&[6000, 4000, 11000]).median(), Some(6000.0));
+        /// assert_eq!(Stats::new(&[6000, 4000, 11000, 24000]).median(), Some(8500.0));
+        /// assert_eq!(Stats::new(&[]).median(), None);
+        /// ```
+        pub fn median(&self) -> Option<f64> {
+            let len = self.len();
+            if len == 0 {
+                return None;
+            }
+            let at = |rank: usize| self.elves[self.ranked[rank]].calories as f64;
+            if len % 2 == 1 {
+                Some(at(len / 2))
+            } else {
+                Some((at(len / 2 - 1) + at(len / 2)) / 2.0)
+            }
+        }
+
+        /// Smallest total that at least `percent` percent of the elves carry
+        /// no more than, by the nearest rank
+        ///
+        /// ```
+        /// use day1::calories::stats::Stats;
+        ///
+        /// let stats = Stats::new(&[6000, 4000, 11000, 24000, 10000]);
+        /// assert_eq!(stats.percentile(50.0).unwrap(), 10000);
+        /// assert_eq!(stats.percentile(90.0).unwrap(), 24000);
+        /// assert_eq!(stats.percentile(0.0).unwrap(), 4000);
+        /// assert!(stats.percentile(101.0).is_err());
+        /// assert!(Stats::new(&[]).percentile(50.0).is_err());
+        /// ```
+        pub fn percentile(&self, percent: f64) -> Result<i64, Box<dyn Error>> {
+            if !(0.0..=100.0).contains(&percent) {
+                return Err(format!("invalid percentile {}, expected 0 to 100!", percent).into());
+            }
+            if self.is_empty() {
+                return Err("no elves in inventory!".into());
+            }
+            let len = self.len();
+            let rank = ((percent / 100.0 * len as f64).ceil() as usize).clamp(1, len);
+            // ranked holds the most first
+            Ok(self.elves[self.ranked[len - rank]].calories)
+        }
+
+        /// Number of elves within `bins` ranges of equal width, from the
+        /// least to the most carried
+        ///
+        /// ```
+        /// use day1::calories::stats::{Bin, Stats};
+        ///
+        /// let stats = Stats::new(&[6000, 4000, 11000, 24000, 10000]);
+        /// assert_eq!(
+        ///     stats.histogram(2),
+        ///     [
+        ///         Bin { low: 4000, high: 14000, count: 4 },
+        ///         Bin { low: 14001, high: 24000, count: 1 },
+        ///     ]
+        /// );
+        /// assert!(Stats::new(&[]).histogram(2).is_empty());
+        ///
+        /// let stats = Stats::new(&[i64::MIN, 0, i64::MAX]);
+        /// let counts: Vec<usize> = stats.histogram(3).iter().map(|bin| bin.count).collect();
+        /// assert_eq!(counts, [1, 1, 1]);
+        /// assert_eq!(stats.histogram(3)[2].high, i64::MAX);
+        /// ```
+        pub fn histogram(&self, bins: usize) -> Vec<Bin> {
+            let (Some(least), Some(most)) = (self.bottom(1).pop(), self.most()) else {
+                return Vec::new();
+            };
+            if bins == 0 {
+                return Vec::new();
+            }
+            // in i128, as the range between the bounds may not fit an i64
+            let (low, high) = (least.calories as i128, most.calories as i128);
+            // round up so that the bins cover the most carried
+            let width = ((high - low + 1) as u128).div_ceil(bins as u128).max(1) as i128;
+            let mut histogram: Vec<Bin> = (0..bins as i128)
+                .map(|idx| (low + idx * width, (low + (idx + 1) * width - 1).min(high)))
+                .take_while(|&(bin_low, _)| bin_low <= high)
+                .map(|(bin_low, bin_high)| Bin {
+                    // within the bounds, which are i64
+                    low: bin_low as i64,
+                    high: bin_high as i64,
+                    count: 0,
+                })
+                .collect();
+            for elf in &self.elves {
+                histogram[((elf.calories as i128 - low) / width) as usize].count += 1;
+            }
+            histogram
+        }
+    }
+}
+
+pub mod tally {
+    use crate::{ErrorKind, ParseError};
+    use log::warn;
+
+    /// What to do with a line that is not a calorie count
+    #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+    pub enum Mode {
+        // report it as an error
+        #[default]
+        Strict,
+        // skip it with a warning, the elf keeps the rest of its lines and
+        // an elf left without any is dropped
+        Lenient,
+    }
+
+    /// Calories of the item on the line at index `lineidx`, none for a line
+    /// skipped in lenient mode
+    ///
+    /// ```
+    /// use day1::calories::tally::{calories, Mode};
+    ///
+    /// assert_eq!(calories("1000", 0, Mode::Strict).unwrap(), Some(1000));
+    /// assert_eq!(calories("-5", 0, Mode::Lenient).unwrap(), None);
+    /// let error = calories("lots", 3, Mode::Strict).unwrap_err();
+    /// assert_eq!(error.to_string(), "line 4, column 1: invalid calorie count `lots`");
+    /// ```
+    pub fn calories(item: &str, lineidx: usize, mode: Mode) -> Result<Option<i64>, ParseError> {
+        let error = match item.parse::<i64>() {
+            Ok(calories) if calories >= 0 => return Ok(Some(calories)),
+            Ok(_) => ErrorKind::NegativeCalories,
+            Err(_) => ErrorKind::InvalidCalories,
+        };
+        let error = ParseError::line(error, lineidx, item);
+        match mode {
+            Mode::Strict => Err(error),
+            Mode::Lenient => {
+                warn!("skipping {}", error);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Warn of an elf dropped in lenient mode, `line` being its last
+    pub fn dropped(line: usize) {
+        warn!(
+            "dropping the elf ending on line {}, none of its lines were calories",
+            line
+        );
+    }
+
+    /// Sums the calories of each elf, fed one line of the inventory at a
+    /// time, so that the whole input and a stream of it are read the same
+    ///
+    /// ```
+    /// use day1::calories::tally::{Mode, Tally};
+    ///
+    /// let mut tally = Tally::new(Mode::Strict);
+    /// assert_eq!(tally.line("1000").unwrap(), None);
+    /// assert_eq!(tally.line("2000").unwrap(), None);
+    /// assert_eq!(tally.line("").unwrap(), Some(3000));
+    /// assert_eq!(tally.line("").unwrap(), None);
+    /// assert!(tally.line("-4000").is_err());
+    /// // the last elf needs no blank line after it
+    /// assert_eq!(tally.line("5000").unwrap(), None);
+    /// assert_eq!(tally.finish(), Some(5000));
+    ///
+    /// let mut tally = Tally::new(Mode::Lenient);
+    /// assert_eq!(tally.line("1000").unwrap(), None);
+    /// assert_eq!(tally.line("lots").unwrap(), None);
+    /// assert_eq!(tally.line("").unwrap(), Some(1000));
+    /// // an elf of skipped lines only is no elf
+    /// assert_eq!(tally.line("lots").unwrap(), None);
+    /// assert_eq!(tally.finish(), None);
+    /// ```
+    #[derive(Clone, Debug)]
+    pub struct Tally {
+        mode: Mode,
+        // index of the next line
+        lineidx: usize,
+        // calories of the elf being read, none between elves
+        total: Option<i64>,
+        // last line skipped since the last elf, from 1
+        skipped: Option<usize>,
+    }
+
+    impl Tally {
+        pub fn new(mode: Mode) -> Tally {
+            Tally {
+                mode,
+                lineidx: 0,
+                total: None,
+                skipped: None,
+            }
+        }
+
+        /// Read the next line, giving the calories of the elf it ends
+        pub fn line(&mut self, line: &str) -> Result<Option<i64>, ParseError> {
+            // a line read with its end from a stream
+            let item = line.trim_end_matches('\n').trim_end_matches('\r');
+            if item.is_empty() {
+                self.lineidx += 1;
+                return Ok(self.end());
+            }
+            let Some(calories) = calories(item, self.lineidx, self.mode)? else {
+                self.lineidx += 1;
+                self.skipped = Some(self.lineidx);
+                return Ok(None);
+            };
+            let total = self
+                .total
+                .unwrap_or(0)
+                .checked_add(calories)
+                .ok_or_else(|| ParseError::line(ErrorKind::TooManyCalories, self.lineidx, item))?;
+            self.total = Some(total);
+            self.lineidx += 1;
+            Ok(None)
+        }
+
+        /// Calories of the last elf, if no blank line followed it
+        pub fn finish(&mut self) -> Option<i64> {
+            self.end()
+        }
+
+        // calories of the elf ending here, none if all its lines were skipped
+        fn end(&mut self) -> Option<i64> {
+            if let (Some(line), None) = (self.skipped.take(), self.total) {
+                dropped(line);
+            }
+            self.total.take()
+        }
+    }
+}
+
+pub mod stream {
+    use crate::calories::stats::Elf;
+    use crate::calories::tally::{Mode, Tally};
+    use std::cmp::{Ordering, Reverse};
+    use std::collections::BinaryHeap;
+    use std::error::Error;
+    use std::io::BufRead;
+
+    // an elf ordered by its calories, the first in the inventory being the
+    // greater on a tie so that later ones do not push it out
+    #[derive(PartialEq, Eq)]
+    struct Ranked(Elf);
+
+    impl Ord for Ranked {
+        fn cmp(&self, other: &Self) -> Ordering {
+            self.0
+                .calories
+                .cmp(&other.0.calories)
+                .then(other.0.number.cmp(&self.0.number))
+        }
+    }
+
+    impl PartialOrd for Ranked {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    /// The `n` elves carrying the most calories, the most first, read one
+    /// line at a time so that only those elves are held in memory
+    ///
+    /// ```
+    /// use day1::calories::stream::top_elves;
+    /// use day1::calories::tally::Mode;
+    ///
+    /// let input = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000\n";
+    /// let top = top_elves(input.as_bytes(), 2, Mode::Strict).unwrap();
+    /// let numbers: Vec<usize> = top.iter().map(|elf| elf.number).collect();
+    /// assert_eq!(numbers, [4, 3]);
+    /// assert!(top_elves("1000\nlots\n".as_bytes(), 2, Mode::Strict).is_err());
+    /// ```
+    pub fn top_elves<R: BufRead>(
+        mut reader: R,
+        n: usize,
+        mode: Mode,
+    ) -> Result<Vec<Elf>, Box<dyn Error>> {
+        // the least of the top elves on top of the heap
+        let mut heap: BinaryHeap<Reverse<Ranked>> = BinaryHeap::with_capacity(n + 1);
+        let mut keep = |elf: Elf| {
+            heap.push(Reverse(Ranked(elf)));
+            if heap.len() > n {
+                heap.pop();
+            }
+        };
+
+        let mut tally = Tally::new(mode);
+        let mut line = String::new();
+        let mut number: usize = 0;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+            if let Some(calories) = tally.line(&line)? {
+                number += 1;
+                keep(Elf { number, calories });
+            }
+        }
+        if let Some(calories) = tally.finish() {
+            keep(Elf {
+                number: number + 1,
+                calories,
+            });
+        }
+
+        Ok(heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(Ranked(elf))| elf)
+            .collect())
+    }
+}
+
+pub mod export {
+    use crate::calories::tally::{calories, dropped, Mode};
+    use crate::{ErrorKind, ParseError};
+    use clap::ValueEnum;
+    use serde::Serialize;
+    use std::error::Error;
+
+    /// How the inventories are exported
+    #[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Format {
+        // a row per item: the elf carrying it, its number, its calories and
+        // the number of items and total of the elf
+        Csv,
+        // an array of `Inventory` objects
+        Json,
+    }
+
+    /// The items an elf carries, elves are numbered from 1 in the order of
+    /// the inventory
+    #[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+    pub struct Inventory {
+        pub elf: usize,
+        pub items: usize,
+        pub total: i64,
+        pub calories: Vec<i64>,
+    }
+
+    /// The inventory of each elf, bad lines being errors or skipped as
+    /// for solving the puzzle
+    ///
+    /// ```
+    /// use day1::calories::export::inventories;
+    /// use day1::calories::tally::Mode;
+    ///
+    /// let elves = inventories("1000\n2000\n\n4000\n", Mode::Strict).unwrap();
+    /// assert_eq!((elves[0].elf, elves[0].items, elves[0].total), (1, 2, 3000));
+    /// assert_eq!(elves[1].calories, [4000]);
+    /// assert!(inventories("1000\n-2000\n", Mode::Strict).is_err());
+    /// ```
+    pub fn inventories(input: &str, mode: Mode) -> Result<Vec<Inventory>, Box<dyn Error>> {
+        let component_lines = input.lines().enumerate().collect::<Vec<_>>();
+        let mut inventories: Vec<Inventory> = Vec::new();
+        for group in component_lines.split(|(_, x)| x.is_empty()) {
+            let mut total: i64 = 0;
+            let mut items: Vec<i64> = Vec::new();
+            for &(lineidx, line) in group {
+                let Some(item) = calories(line, lineidx, mode)? else {
+                    continue;
+                };
+                total = total
+                    .checked_add(item)
+                    .ok_or_else(|| ParseError::line(ErrorKind::TooManyCalories, lineidx, line))?;
+                items.push(item);
+            }
+            match group.last() {
+                // a blank line too many
+                None => continue,
+                Some(&(lineidx, _)) if items.is_empty() => dropped(lineidx + 1),
+                Some(_) => inventories.push(Inventory {
+                    elf: inventories.len() + 1,
+                    items: items.len(),
+                    total,
+                    calories: items,
+                }),
+            }
+        }
+        Ok(inventories)
+    }
+
+    /// A row per item, repeating the number of items and the total of the
+    /// elf carrying it
+    ///
+    /// ```
+    /// use day1::calories::export::{inventories, to_csv};
+    /// use day1::calories::tally::Mode;
+    ///
+    /// let elves = inventories("1000\n2000\n\n4000\n", Mode::Strict).unwrap();
+    /// assert_eq!(
+    ///     to_csv(&elves),
+    ///     "elf,item,calories,items,total\n1,1,1000,2,3000\n1,2,2000,2,3000\n2,1,4000,1,4000\n"
+    /// );
+    /// ```
+    pub fn to_csv(inventories: &[Inventory]) -> String {
+        let mut csv = String::from("elf,item,calories,items,total\n");
+        for inventory in inventories {
+            for (idx, calories) in inventory.calories.iter().enumerate() {
+                csv += &format!(
+                    "{},{},{},{},{}\n",
+                    inventory.elf,
+                    idx + 1,
+                    calories,
+                    inventory.items,
+                    inventory.total
+                );
+            }
+        }
+        csv
+    }
+
+    pub fn to_json(inventories: &[Inventory]) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(inventories)? + "\n")
+    }
+
+    pub fn export(inventories: &[Inventory], format: Format) -> Result<String, Box<dyn Error>> {
+        match format {
+            Format::Csv => Ok(to_csv(inventories)),
+            Format::Json => to_json(inventories),
+        }
+    }
+}
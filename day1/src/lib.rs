@@ -1,85 +1,72 @@
-use fs_err as fs;
+use common::Solution;
 use std::error::Error;
-
-pub fn run_iter(filename: String) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
-    let component_groups = component_lines.split(|x| x.is_empty()).collect::<Vec<_>>();
-    let mut calories: Vec<i64> = component_groups
-        .into_iter()
-        .map(|x| {
-            x.into_iter()
-                .map(|y| y.parse::<i64>().unwrap())
-                .sum::<i64>()
-        })
-        .collect();
-
-    // sort the calories vector
-    calories.sort();
-
-    println!(
-        "elf with most calories has {} calories",
-        calories[calories.len() - 1]
-    );
-
-    println!(
-        "elf with second most calories has {} calories",
-        calories[calories.len() - 2]
-    );
-
-    println!(
-        "elf with third most calories has {} calories",
-        calories[calories.len() - 3]
-    );
-
-    println!(
-        "three elves with most calories have {} calories combined",
-        calories[calories.len() - 1] + calories[calories.len() - 2] + calories[calories.len() - 3]
-    );
-
-    Ok(())
+use std::fmt;
+pub mod calories;
+use crate::calories::tally::{Mode, Tally};
+
+/// What is wrong with a line of the calorie inventory
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    InvalidCalories,
+    NegativeCalories,
+    // the calories of an elf do not fit in an i64
+    TooManyCalories,
 }
 
-#[allow(dead_code)]
-pub fn run(filename: String) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines();
-
-    let mut counts: Vec<i64> = Vec::new();
-    let mut calories: i64 = 0;
-
-    for item in component_lines {
-        if let Ok(calorie) = item.parse::<i64>() {
-            calories = calories + calorie;
-        }
-        if item.is_empty() {
-            counts.push(calories);
-            calories = 0;
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidCalories => write!(f, "invalid calorie count"),
+            ErrorKind::NegativeCalories => write!(f, "negative calorie count"),
+            ErrorKind::TooManyCalories => write!(f, "too many calories for one elf at"),
         }
     }
+}
 
-    // sort the counts vector
-    counts.sort();
-
-    println!(
-        "elf with most calories has {} calories",
-        counts[counts.len() - 1]
-    );
+pub type ParseError = common::ParseError<ErrorKind>;
 
-    println!(
-        "elf with second most calories has {} calories",
-        counts[counts.len() - 2]
-    );
+#[derive(Default)]
+pub struct Day1 {
+    // whether lines that are not calorie counts are errors or skipped
+    pub mode: Mode,
+}
 
-    println!(
-        "elf with third most calories has {} calories",
-        counts[counts.len() - 3]
-    );
+impl Solution for Day1 {
+    // calories carried by each elf, in input order
+    type Model = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        let mut tally = Tally::new(self.mode);
+        let mut calories: Vec<i64> = Vec::new();
+        for line in input.lines() {
+            if let Some(total) = tally.line(line)? {
+                calories.push(total);
+            }
+        }
+        calories.extend(tally.finish());
+        Ok(calories)
+    }
 
-    println!(
-        "three elves with most calories have {} calories combined",
-        counts[counts.len() - 1] + counts[counts.len() - 2] + counts[counts.len() - 3]
-    );
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        model
+            .iter()
+            .copied()
+            .max()
+            .ok_or_else(|| "no elves in inventory!".into())
+    }
 
-    Ok(())
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        if model.len() < 3 {
+            return Err("need at least three elves in inventory!".into());
+        }
+        // the three carrying the most first, in no particular order
+        let mut totals = model.clone();
+        totals.select_nth_unstable_by(2, |a, b| b.cmp(a));
+        totals[..3]
+            .iter()
+            .try_fold(0i64, |sum, &calories| sum.checked_add(calories))
+            .ok_or_else(|| "calories of the top three elves do not fit in an i64!".into())
+    }
 }
@@ -1,16 +1,205 @@
-use std::env;
+use clap::{CommandFactory, FromArgMatches};
+use common::cli::DayArgs;
+use common::{input, Part, Solution};
+use day1::calories;
+use day1::calories::export::{inventories, Format};
+use day1::calories::stats::{Elf, Stats};
+use day1::calories::stream::top_elves;
+use day1::calories::tally::Mode;
+use day1::Day1;
+use std::error::Error;
+use std::path::PathBuf;
 use std::process;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+/// Day 1: Calorie Counting
+#[derive(clap::Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    day: DayArgs,
+
+    /// also list the elves carrying the most calories
+    #[arg(long, value_name = "N")]
+    top: Option<usize>,
+
+    /// also list the elves carrying the least calories
+    #[arg(long, value_name = "N")]
+    bottom: Option<usize>,
+
+    /// also report the calories carried by elves at these percentiles, as 50,90
+    #[arg(long, value_delimiter = ',')]
+    percentile: Vec<f64>,
+
+    /// also report the median and mean calories
+    #[arg(long)]
+    summary: bool,
 
-    if args.len() != 2 {
-        eprintln!("Provide only one input, the filename of values!")
+    /// also report how many elves carry calories within this many ranges
+    #[arg(long, value_name = "BINS")]
+    histogram: Option<usize>,
+
+    /// read the input a line at a time, holding only the top elves in
+    /// memory, for inventories too large to be read whole
+    #[arg(
+        long,
+        conflicts_with_all = ["bottom", "percentile", "summary", "histogram", "format"]
+    )]
+    stream: bool,
+
+    /// skip lines that are not calorie counts with a warning instead of
+    /// failing on them
+    #[arg(long)]
+    lenient: bool,
+
+    /// write the items of each elf in place of the answer
+    #[arg(
+        long,
+        value_enum,
+        conflicts_with_all = [
+            "stream",
+            "top",
+            "bottom",
+            "percentile",
+            "summary",
+            "histogram",
+            "format"
+        ]
+    )]
+    export: Option<Format>,
+
+    /// file the export is written to, stdout if not given
+    #[arg(short, long, requires = "export")]
+    output: Option<PathBuf>,
+}
+
+impl Args {
+    fn day1(&self) -> Day1 {
+        Day1 {
+            mode: if self.lenient {
+                Mode::Lenient
+            } else {
+                Mode::Strict
+            },
+        }
     }
 
-    let filename_in: String = args[1].clone();
+    fn has_queries(&self) -> bool {
+        self.top.is_some()
+            || self.bottom.is_some()
+            || !self.percentile.is_empty()
+            || self.summary
+            || self.histogram.is_some()
+    }
+}
+
+fn print_elves(title: &str, elves: &[Elf]) {
+    println!("{}:", title);
+    for elf in elves {
+        println!("  elf {} carries {} calories", elf.number, elf.calories);
+    }
+}
+
+fn query(args: &Args, stats: &Stats) -> Result<(), Box<dyn Error>> {
+    if let Some(n) = args.top {
+        print_elves(&format!("top {}", n), &stats.top(n));
+    }
+    if let Some(n) = args.bottom {
+        print_elves(&format!("bottom {}", n), &stats.bottom(n));
+    }
+    for &percent in &args.percentile {
+        println!("percentile {}: {}", percent, stats.percentile(percent)?);
+    }
+    if args.summary {
+        let median = stats.median().ok_or("no elves in inventory!")?;
+        let mean = stats.mean().ok_or("no elves in inventory!")?;
+        println!("median: {}", median);
+        println!("mean: {:.1}", mean);
+    }
+    if let Some(bins) = args.histogram {
+        let histogram = stats.histogram(bins);
+        let width = histogram
+            .iter()
+            .map(|bin| format!("{}..={}", bin.low, bin.high).len())
+            .max()
+            .unwrap_or(0);
+        for bin in histogram {
+            let range = format!("{}..={}", bin.low, bin.high);
+            println!(
+                "{:>width$}  {:>4}  {}",
+                range,
+                bin.count,
+                "#".repeat(bin.count)
+            );
+        }
+    }
+    Ok(())
+}
+
+// the answer of the part and the top elves, from the top elves alone
+fn stream(args: &Args) -> Result<(), Box<dyn Error>> {
+    let day1 = args.day1();
+    args.day.verbosity().init();
+    let [filename] = args.day.input.as_slice() else {
+        return Err("streaming reads a single input file!".into());
+    };
+    // part 2 sums the three elves carrying the most
+    let top = top_elves(
+        input::open(filename)?,
+        args.top.unwrap_or(0).max(3),
+        day1.mode,
+    )?;
+    let totals: Vec<i64> = top.iter().map(|elf| elf.calories).collect();
+    match args.day.part() {
+        Part::One => println!("{}", day1.part1(&totals)?),
+        Part::Two => println!("{}", day1.part2(&totals)?),
+    }
+    if let Some(n) = args.top {
+        print_elves(&format!("top {}", n), &top[..n.min(top.len())]);
+    }
+    Ok(())
+}
+
+// the items of each elf, as csv or json
+fn export(args: &Args, format: Format) -> Result<(), Box<dyn Error>> {
+    args.day.verbosity().init();
+    let contents = args.day.read()?;
+    let exported = calories::export::export(&inventories(&contents, args.day1().mode)?, format)?;
+    match &args.output {
+        Some(filename) => fs_err::write(filename, exported)?,
+        None => print!("{}", exported),
+    }
+    Ok(())
+}
+
+// the answer of the part, followed by whatever was asked about the elves
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    if args.stream {
+        return stream(args);
+    }
+    if let Some(format) = args.export {
+        return export(args, format);
+    }
+    let day1 = args.day1();
+    if !args.has_queries() {
+        return args.day.run(1, &day1);
+    }
+    args.day.verbosity().init();
+    let contents = args.day.read()?;
+    println!("{}", args.day.answer(1, &day1, &contents)?);
+    let totals = day1.parse(&contents)?;
+    query(args, &Stats::new(&totals))
+}
+
+fn main() {
+    // the export holds every elf, whichever the part
+    let matches = Args::command()
+        .mut_arg("part", |arg| {
+            arg.required(false).required_unless_present("export")
+        })
+        .get_matches();
+    let args = Args::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
 
-    if let Err(e) = day1::run_iter(filename_in) {
+    if let Err(e) = run(&args) {
         eprintln!("Application error: {}", e);
         process::exit(1);
     }
@@ -1,5 +1,6 @@
 pub mod cpu {
 
+    use log::debug;
     use std::collections::VecDeque;
     use std::fmt;
 
@@ -9,6 +10,7 @@ pub mod cpu {
         pub register: i32,
     }
 
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
     pub enum Instruction {
         Noop,
         Addx { v: i32 },
@@ -25,6 +27,12 @@ pub mod cpu {
     }
 
     impl State {
+        /// State at cycle `c1` with `r1` in the register
+        ///
+        /// ```
+        /// let state = day10::State::new(20, 21);
+        /// assert_eq!((state.cycle, state.register), (20, 21));
+        /// ```
         pub fn new(c1: u32, r1: i32) -> State {
             State {
                 cycle: c1,
@@ -32,10 +40,21 @@ pub mod cpu {
             }
         }
 
+        /// Signal strength, cycle times register, when the cycle is the next
+        /// one queried, which is then taken off the queries
+        ///
+        /// ```
+        /// use std::collections::VecDeque;
+        ///
+        /// let mut queries = VecDeque::from([20, 60]);
+        /// assert_eq!(day10::State::new(19, 21).query(&mut queries), None);
+        /// assert_eq!(day10::State::new(20, 21).query(&mut queries), Some(420));
+        /// assert_eq!(queries, [60]);
+        /// ```
         pub fn query(&self, queries: &mut VecDeque<u32>) -> Option<i64> {
             if Some(self.cycle) == queries.front().copied() {
                 let signal_strength = (self.cycle as i32 * self.register) as i64;
-                println!(
+                debug!(
                     "Hit query at {}, signal_strength here is {}",
                     self.cycle, signal_strength
                 );
@@ -49,13 +68,16 @@ pub mod cpu {
 
 pub mod render {
     use crate::crt::cpu::State;
+    use grid::Grid;
     use std::fmt;
 
     const SCREENWIDTH: usize = 40;
+    const SCREENHEIGHT: usize = 6;
 
     pub struct Screen {
         state: [char; SCREENWIDTH],
         activepixel: usize,
+        rows: Vec<String>,
     }
 
     impl fmt::Display for Screen {
@@ -64,14 +86,69 @@ pub mod render {
         }
     }
 
+    impl Default for Screen {
+        fn default() -> Self {
+            Screen::new()
+        }
+    }
+
     impl Screen {
+        /// A dark screen, drawing from its top left pixel
+        ///
+        /// ```
+        /// let screen = day10::Screen::new();
+        /// assert!(screen.rows().is_empty());
+        /// assert!(screen.picture().iter().all(|(_, pixel)| *pixel == '.'));
+        /// ```
         pub fn new() -> Screen {
             Screen {
                 state: ['.'; SCREENWIDTH],
                 activepixel: 0,
+                rows: Vec::new(),
+            }
+        }
+
+        /// Rows that have been completely drawn so far
+        ///
+        /// ```
+        /// let mut screen = day10::Screen::new();
+        /// for cycle in 1..=40 {
+        ///     screen.render(&day10::State::new(cycle, 1));
+        /// }
+        /// assert_eq!(screen.rows(), [format!("###{}", ".".repeat(37))]);
+        /// ```
+        pub fn rows(&self) -> &[String] {
+            &self.rows
+        }
+
+        /// The whole screen as drawn so far, rows not reached yet are dark
+        ///
+        /// ```
+        /// let mut screen = day10::Screen::new();
+        /// screen.render(&day10::State::new(1, 0));
+        /// let picture = screen.picture();
+        /// assert_eq!((picture.rows(), picture.cols()), (6, 40));
+        /// assert_eq!((picture[(0, 0)], picture[(0, 1)], picture[(1, 0)]), ('#', '.', '.'));
+        /// ```
+        pub fn picture(&self) -> Grid<char> {
+            let mut cells: Vec<char> = self.rows.iter().flat_map(|row| row.chars()).collect();
+            if self.activepixel > 0 {
+                cells.extend(self.state);
             }
+            let rows = (cells.len() / SCREENWIDTH).max(SCREENHEIGHT);
+            cells.resize(rows * SCREENWIDTH, '.');
+            Grid::from_vec(rows, SCREENWIDTH, cells).expect("rows are all of the screen width")
         }
 
+        /// Draws the active pixel, lit when the sprite around the register
+        /// covers it, and moves on to the next one
+        ///
+        /// ```
+        /// let mut screen = day10::Screen::new();
+        /// screen.render(&day10::State::new(1, 5));
+        /// screen.render(&day10::State::new(2, 2));
+        /// assert_eq!((screen.picture()[(0, 0)], screen.picture()[(0, 1)]), ('.', '#'));
+        /// ```
         pub fn render(&mut self, cpu: &State) {
             let to_draw: i32 = (self.activepixel as i32)
                 .checked_sub(cpu.register)
@@ -82,8 +159,8 @@ pub mod render {
                 self.state[self.activepixel] = '#';
             }
 
-            if cpu.cycle % (SCREENWIDTH as u32) == 0 {
-                println!("{}", self);
+            if cpu.cycle.is_multiple_of(SCREENWIDTH as u32) {
+                self.rows.push(self.to_string());
                 self.state = ['.'; SCREENWIDTH];
             }
 
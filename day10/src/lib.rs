@@ -1,114 +1,203 @@
-use fs_err as fs;
+use common::Solution;
+use grid::Grid;
 use std::collections::VecDeque;
 use std::error::Error;
+use std::fmt;
 
 mod crt;
-use crate::crt::cpu::Instruction;
-use crate::crt::cpu::State;
-use crate::crt::render::Screen;
+pub use crate::crt::cpu::{Instruction, State};
+pub use crate::crt::render::Screen;
 
-pub fn draw(filename: String) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
+/// What is wrong with an instruction of the program
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // only addx and noop are known
+    InvalidInstruction,
+    MissingValue,
+    InvalidValue,
+}
 
-    let mut state = State::new(0, 1);
-    let mut screen = Screen::new();
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidInstruction => write!(f, "invalid instruction"),
+            ErrorKind::MissingValue => write!(f, "missing V value for addx"),
+            ErrorKind::InvalidValue => write!(f, "invalid V value for addx"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<ErrorKind>;
+
+pub struct Day10 {
+    pub query_cycles: Vec<u32>,
+}
+
+impl Default for Day10 {
+    fn default() -> Self {
+        Day10 {
+            query_cycles: vec![20, 60, 100, 140, 180, 220],
+        }
+    }
+}
+
+impl Solution for Day10 {
+    type Model = Vec<Instruction>;
+    type Answer1 = i64;
+    // the rows drawn on the CRT
+    type Answer2 = String;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        parse_instructions(input)
+    }
 
-    for line in component_lines {
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        // the cycles are queried in order as the cpu runs, each once
+        let mut query_cycles: Vec<u32> = self.query_cycles.clone();
+        query_cycles.sort_unstable();
+        query_cycles.dedup();
+        let query_cycles: VecDeque<u32> = query_cycles.into();
+        let (signal_strengths_sum, _) = signal_strengths(model, query_cycles);
+        Ok(signal_strengths_sum)
+    }
+
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        Ok(render(model).join("\n"))
+    }
+}
+
+/// One instruction per line, `noop` or `addx` and its value
+///
+/// ```
+/// use day10::Instruction;
+///
+/// let instructions = day10::parse_instructions("noop\naddx 3\naddx -5\n").unwrap();
+/// assert_eq!(instructions, [Instruction::Noop, Instruction::Addx { v: 3 }, Instruction::Addx { v: -5 }]);
+/// assert!(day10::parse_instructions("addx\n").is_err());
+/// assert!(day10::parse_instructions("addx x\n").is_err());
+/// assert!(day10::parse_instructions("jmp 3\n").is_err());
+/// ```
+pub fn parse_instructions(contents: &str) -> Result<Vec<Instruction>, Box<dyn Error>> {
+    let mut instructions: Vec<Instruction> = Vec::new();
+
+    for (lineidx, line) in contents.lines().enumerate() {
         if line.is_empty() {
             continue;
         } else {
             let input_vec: Vec<_> = line.split_whitespace().collect();
 
-            let instr: Instruction = match input_vec[0] {
-                "addx" => Some(Instruction::Addx {
-                    v: input_vec[1]
-                        .parse::<i32>()
-                        .expect("invalid V value for addx"),
-                }),
-                "noop" => Some(Instruction::Noop),
-                _ => None,
-            }
-            .expect("invalid instruction in input file");
-
-            match instr {
-                Instruction::Noop => {
-                    state.cycle += 1;
-                    screen.render(&state);
+            let instr: Instruction = match input_vec.first() {
+                Some(&"addx") => {
+                    let value = input_vec
+                        .get(1)
+                        .ok_or_else(|| ParseError::line(ErrorKind::MissingValue, lineidx, line))?;
+                    Instruction::Addx {
+                        v: value.parse::<i32>().map_err(|_| {
+                            ParseError::at(ErrorKind::InvalidValue, lineidx, line, value)
+                        })?,
+                    }
                 }
-                Instruction::Addx { v } => {
-                    state.cycle += 1;
-                    screen.render(&state);
-                    state.cycle += 1;
-                    screen.render(&state);
-                    state.register += v;
+                Some(&"noop") => Instruction::Noop,
+                Some(instr) => {
+                    return Err(
+                        ParseError::at(ErrorKind::InvalidInstruction, lineidx, line, instr).into(),
+                    )
                 }
-            }
+                None => {
+                    return Err(
+                        ParseError::line(ErrorKind::InvalidInstruction, lineidx, line).into(),
+                    )
+                }
+            };
+
+            instructions.push(instr);
         }
     }
-    Ok(())
+
+    Ok(instructions)
+}
+
+fn render(instructions: &[Instruction]) -> Vec<String> {
+    run_screen(instructions, |_| {}).rows().to_vec()
 }
 
-pub fn run(filename: String, mut query_cycles: VecDeque<u32>) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
+/// The screen after each cycle, the last one is the fully drawn screen
+///
+/// ```
+/// let instructions = day10::parse_instructions("noop\naddx 3\naddx -5\n").unwrap();
+/// let screens = day10::screens(&instructions);
+/// assert_eq!(screens.len(), 5);
+/// let last = screens.last().unwrap();
+/// assert_eq!((last.rows(), last.cols()), (6, 40));
+/// assert_eq!((0..5).map(|col| last[(0, col)]).collect::<String>(), "#####");
+/// ```
+pub fn screens(instructions: &[Instruction]) -> Vec<Grid<char>> {
+    let mut screens = Vec::new();
+    run_screen(instructions, |screen| screens.push(screen.picture()));
+    screens
+}
+
+fn run_screen<F: FnMut(&Screen)>(instructions: &[Instruction], mut after_cycle: F) -> Screen {
+    let mut state = State::new(0, 1);
+    let mut screen = Screen::new();
+
+    for instr in instructions {
+        match instr {
+            Instruction::Noop => {
+                state.cycle += 1;
+                screen.render(&state);
+                after_cycle(&screen);
+            }
+            Instruction::Addx { v } => {
+                state.cycle += 1;
+                screen.render(&state);
+                after_cycle(&screen);
+                state.cycle += 1;
+                screen.render(&state);
+                after_cycle(&screen);
+                state.register += v;
+            }
+        }
+    }
+
+    screen
+}
 
+// sum of the signal strengths at the queried cycles, along with the final
+// state of the cpu
+fn signal_strengths(instructions: &[Instruction], mut query_cycles: VecDeque<u32>) -> (i64, State) {
     let mut state = State::new(0, 1);
 
-    let signal_strengths_sum: i64 = component_lines
-        .into_iter()
-        .map(|line| {
-            if line.is_empty() {
-                return 0;
-            } else {
-                let input_vec: Vec<_> = line.split_whitespace().collect();
-
-                let instr: Instruction = match input_vec[0] {
-                    "addx" => Some(Instruction::Addx {
-                        v: input_vec[1]
-                            .parse::<i32>()
-                            .expect("invalid V value for addx"),
-                    }),
-                    "noop" => Some(Instruction::Noop),
-                    _ => None,
+    let signal_strengths_sum: i64 = instructions
+        .iter()
+        .map(|instr| {
+            match instr {
+                Instruction::Noop => {
+                    state.cycle += 1;
+                    if let Some(signal_strength) = state.query(&mut query_cycles) {
+                        return signal_strength;
+                    }
+                    //println!("state is {}, end of noop", state);
                 }
-                .expect("invalid instruction in input file");
-
-                match instr {
-                    Instruction::Noop => {
-                        state.cycle += 1;
-                        if let Some(signal_strength) = state.query(&mut query_cycles) {
-                            return signal_strength;
-                        }
-                        //println!("state is {}, end of noop", state);
+                Instruction::Addx { v } => {
+                    let mut signal_strength = 0;
+                    state.cycle += 1;
+                    //println!("state is {}, middle of addx", state);
+                    if let Some(signal_strength2) = state.query(&mut query_cycles) {
+                        signal_strength += signal_strength2;
                     }
-                    Instruction::Addx { v } => {
-                        let mut signal_strength = 0;
-                        state.cycle += 1;
-                        //println!("state is {}, middle of addx", state);
-                        if let Some(signal_strength2) = state.query(&mut query_cycles) {
-                            signal_strength += signal_strength2;
-                        }
-                        state.cycle += 1;
-                        //println!("state is {}, middle of addx", state);
-                        if let Some(signal_strength3) = state.query(&mut query_cycles) {
-                            signal_strength += signal_strength3;
-                        }
-                        state.register += v;
-                        return signal_strength;
+                    state.cycle += 1;
+                    //println!("state is {}, middle of addx", state);
+                    if let Some(signal_strength3) = state.query(&mut query_cycles) {
+                        signal_strength += signal_strength3;
                     }
+                    state.register += v;
+                    return signal_strength;
                 }
-                0
             }
+            0
         })
         .sum();
 
-    println!(
-        "sum of requested signal strengths is {}",
-        signal_strengths_sum
-    );
-
-    println!("final state is {}", state);
-
-    Ok(())
+    (signal_strengths_sum, state)
 }
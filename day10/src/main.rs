@@ -1,20 +1,28 @@
-#[allow(unused)]
-use std::collections::VecDeque;
-use std::env;
+use clap::Parser;
+use common::cli::DayArgs;
 use std::process;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+/// Day 10: Cathode-Ray Tube
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    day: DayArgs,
 
-    if args.len() != 2 {
-        eprintln!("Provide only one input, the filename move instructions for head!")
-    }
+    /// cycles whose signal strengths are summed for part 1, as 20,60,100, in
+    /// any order
+    #[arg(long, value_delimiter = ',')]
+    query_cycles: Option<Vec<u32>>,
+}
 
-    let filename_in: String = args[1].clone();
-    // used only for part1
-    let _query_cycles: VecDeque<u32> = [20, 60, 100, 140, 180, 220].into();
+fn main() {
+    let args = Args::parse();
+    let mut day10 = day10::Day10::default();
+    if let Some(query_cycles) = args.query_cycles {
+        day10.query_cycles = query_cycles;
+    }
 
-    if let Err(e) = day10::draw(filename_in) {
+    if let Err(e) = args.day.run(10, &day10) {
         eprintln!("Application error: {}", e);
         process::exit(1);
     }
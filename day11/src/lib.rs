@@ -1,37 +1,150 @@
-use fs_err as fs;
+use common::Solution;
+use log::info;
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
 
 mod monkey;
-use crate::monkey::monkey::Monkey;
+pub use crate::monkey::monkey::Monkey;
 
-pub fn run(filename: String) -> Result<(), Box<dyn std::error::Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
+/// What is wrong with the notes on a monkey
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // a monkey is described by six lines
+    IncompleteMonkey,
+    // the line does not start with the expected text
+    Expected(&'static str),
+    InvalidNumber,
+    // only `+` and `*` with a number or `old` are known
+    InvalidOperation,
+    ZeroDivisor,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::IncompleteMonkey => write!(f, "expected six lines describing a monkey"),
+            ErrorKind::Expected(prefix) => write!(f, "expected `{}` at", prefix),
+            ErrorKind::InvalidNumber => write!(f, "invalid number"),
+            ErrorKind::InvalidOperation => write!(f, "invalid operation"),
+            ErrorKind::ZeroDivisor => write!(f, "cannot test divisibility by zero"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<ErrorKind>;
+
+/// Inspection counts of the two most active monkeys, the monkey business
+/// is their product.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MonkeyBusiness {
+    pub most_active: u128,
+    pub second_most_active: u128,
+}
+
+impl fmt::Display for MonkeyBusiness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.most_active * self.second_most_active)
+    }
+}
+
+pub struct Day11 {
+    pub part1_rounds: usize,
+    pub part2_rounds: usize,
+}
+
+impl Default for Day11 {
+    fn default() -> Self {
+        Day11 {
+            part1_rounds: 20,
+            part2_rounds: 10000,
+        }
+    }
+}
 
+impl Solution for Day11 {
+    type Model = Vec<Monkey>;
+    type Answer1 = MonkeyBusiness;
+    type Answer2 = MonkeyBusiness;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        let component_lines = input.lines().collect::<Vec<_>>();
+
+        let mut monkeys: Vec<Monkey> = Vec::new();
+        for (idx, chunk) in component_lines.chunks(7).enumerate() {
+            let monkey = Monkey::new(chunk, idx * 7)?;
+            monkeys.push(monkey);
+        }
+
+        Ok(monkeys)
+    }
+
+    // worry levels are divided by three after each inspection
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        monkey_business(model, self.part1_rounds, Some(3))
+    }
+
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        monkey_business(model, self.part2_rounds, None)
+    }
+
+    fn details1(&self, answer: &Self::Answer1) -> Vec<(&'static str, String)> {
+        vec![
+            ("most_active", answer.most_active.to_string()),
+            ("second_most_active", answer.second_most_active.to_string()),
+        ]
+    }
+
+    fn details2(&self, answer: &Self::Answer2) -> Vec<(&'static str, String)> {
+        self.details1(answer)
+    }
+}
+
+/// Inspection counts of the two most active monkeys after `rounds` rounds,
+/// dividing the worry levels by `relief` after each inspection if any
+///
+/// ```
+/// use day11::Monkey;
+///
+/// let notes = "Monkey 0:\n  Starting items: 1\n  Operation: new = old + 1\n  Test: divisible by 2\n    \
+///              If true: throw to monkey 1\n    If false: throw to monkey 1\n\n\
+///              Monkey 1:\n  Starting items:\n  Operation: new = old * old\n  Test: divisible by 3\n    \
+///              If true: throw to monkey 0\n    If false: throw to monkey 0";
+/// let lines: Vec<&str> = notes.lines().collect();
+/// let troop = [Monkey::new(&lines[..6], 0).unwrap(), Monkey::new(&lines[7..], 7).unwrap()];
+/// let business = day11::monkey_business(&troop, 3, None).unwrap();
+/// assert_eq!((business.most_active, business.second_most_active), (3, 3));
+/// assert_eq!(business.to_string(), "9");
+/// assert!(day11::monkey_business(&troop[..1], 3, None).is_err());
+/// ```
+pub fn monkey_business(
+    troop: &[Monkey],
+    rounds: usize,
+    relief: Option<u128>,
+) -> Result<MonkeyBusiness, Box<dyn Error>> {
     let mut monkeys: HashMap<u32, Monkey> = HashMap::new();
     let mut monkeyids: Vec<u32> = Vec::new();
     let mut divisors: Vec<u128> = Vec::new();
 
-    for chunk in component_lines.chunks(7) {
-        let monkey = Monkey::new(chunk).expect("unable to parse monkey");
+    for monkey in troop {
         monkeyids.push(monkey.get_id());
         divisors.push(monkey.get_divisor());
-        monkeys.insert(monkey.get_id(), monkey);
+        monkeys.insert(monkey.get_id(), monkey.clone());
     }
     // we will get a common divisor for all the monkeys by multiplying their
     // divisors and use it to keep the worry levels from overflowing!
-    let common_divisor: u128 = divisors.iter().fold(1, |acc, x| acc * x);
-    println!("common divisor is : {}", common_divisor);
+    let common_divisor: u128 = divisors.iter().product::<u128>();
+    info!("common divisor is : {}", common_divisor);
 
-    for _ in 1..=10000 {
+    for _ in 1..=rounds {
         for monkeyid in &monkeyids {
-            let monkey = monkeys.get_mut(&monkeyid).expect("monkey has escaped!");
-            let transfers = monkey.process_items(common_divisor);
+            let monkey = monkeys.get_mut(monkeyid).ok_or("monkey has escaped!")?;
+            let transfers = monkey.process_items(relief, common_divisor);
             for (monkey_to_transfer_to, item_to_transfer) in transfers.iter() {
                 let dst_monkey = monkeys
-                    .get_mut(&monkey_to_transfer_to)
-                    .expect("monkey has escaped!");
-                dst_monkey.add_item(&item_to_transfer);
+                    .get_mut(monkey_to_transfer_to)
+                    .ok_or("monkey has escaped!")?;
+                dst_monkey.add_item(item_to_transfer);
             }
         }
     }
@@ -40,14 +153,18 @@ pub fn run(filename: String) -> Result<(), Box<dyn std::error::Error>> {
         .into_values()
         .map(|x| x.get_inspection_count())
         .collect();
+    if inspection_counts.len() < 2 {
+        return Err("need at least two monkeys for monkey business!".into());
+    }
     inspection_counts.sort();
-    println!(
+    let business = MonkeyBusiness {
+        most_active: inspection_counts[inspection_counts.len() - 1],
+        second_most_active: inspection_counts[inspection_counts.len() - 2],
+    };
+    info!(
         "monkey business is {} * {} : {}",
-        inspection_counts[inspection_counts.len() - 1],
-        inspection_counts[inspection_counts.len() - 2],
-        inspection_counts[inspection_counts.len() - 1]
-            * inspection_counts[inspection_counts.len() - 2]
+        business.most_active, business.second_most_active, business
     );
 
-    Ok(())
+    Ok(business)
 }
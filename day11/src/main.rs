@@ -1,16 +1,19 @@
-use std::env;
+use clap::Parser;
+use common::cli::DayArgs;
 use std::process;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() != 2 {
-        eprintln!("Provide only one input, the filename of values!")
-    }
+/// Day 11: Monkey in the Middle
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    day: DayArgs,
+}
 
-    let filename_in: String = args[1].clone();
+fn main() {
+    let args = Args::parse();
 
-    if let Err(e) = day11::run(filename_in) {
+    if let Err(e) = args.day.run(11, &day11::Day11::default()) {
         eprintln!("Application error: {}", e);
         process::exit(1);
     }
@@ -3,6 +3,7 @@ type MonkeyOp = fn(u128, Option<u128>) -> u128;
 mod parse {
 
     use crate::monkey;
+    use crate::ErrorKind;
     use nom::bytes::complete::tag;
     use nom::bytes::complete::take_till;
     use nom::sequence::tuple;
@@ -36,41 +37,52 @@ mod parse {
         tag("If false: throw to monkey ")(s)
     }
 
-    pub fn parse_operation(s: Vec<&str>) -> (Option<u128>, Option<monkey::MonkeyOp>) {
-        assert_eq!(s.len(), 2);
+    // operator and operand of `new = old * 19`, or what is wrong with them
+    pub fn parse_operation(
+        operation: &str,
+    ) -> Result<(Option<u128>, monkey::MonkeyOp), (ErrorKind, &str)> {
+        let s: Vec<&str> = operation.split_whitespace().collect();
+        if s.len() != 2 {
+            return Err((ErrorKind::InvalidOperation, operation));
+        }
 
         let y: Option<u128> = if s[1] == "old" {
             None
         } else {
-            Some(s[1].parse::<u128>().expect("error parsing monkeyop"))
+            Some(
+                s[1].parse::<u128>()
+                    .map_err(|_| (ErrorKind::InvalidNumber, s[1]))?,
+            )
         };
 
-        let monkeyop: Option<monkey::MonkeyOp> = match s[0] {
-            "+" => Some(|x: u128, y: Option<u128>| match y {
+        let monkeyop: monkey::MonkeyOp = match s[0] {
+            "+" => |x: u128, y: Option<u128>| match y {
                 Some(yval) => x + yval,
-                None => {
-                    return x * 2;
-                }
-            }),
-            "*" => Some(|x: u128, y: Option<u128>| match y {
+                None => x * 2,
+            },
+            "*" => |x: u128, y: Option<u128>| match y {
                 Some(yval) => x * yval,
                 None => x.pow(2),
-            }),
-            _ => None,
+            },
+            _ => return Err((ErrorKind::InvalidOperation, s[0])),
         };
 
-        return (y, monkeyop);
+        Ok((y, monkeyop))
     }
 }
 
+#[allow(clippy::module_inception)]
 pub mod monkey {
 
     use crate::monkey;
     use crate::monkey::parse;
+    use crate::{ErrorKind, ParseError};
+    use nom::Finish;
     use std::collections::VecDeque;
 
     use std::fmt;
 
+    #[derive(Clone)]
     pub struct Monkey {
         id: u32,
         inspections: u128,
@@ -88,8 +100,7 @@ pub mod monkey {
                 .iter()
                 .map(|id| id.to_string() + ",")
                 .collect::<Vec<_>>()
-                .join("")
-                .into();
+                .join("");
             write!(
                 f,
                 "id : {}, rhs: {}, divisor: {}, monkeys to transfer to : {},{}, items: {}",
@@ -107,32 +118,115 @@ pub mod monkey {
     }
 
     impl Monkey {
+        /// Number the monkey tests the worry levels against
+        ///
+        /// ```
+        /// let notes = [
+        ///     "Monkey 0:",
+        ///     "  Starting items: 79, 98",
+        ///     "  Operation: new = old * 19",
+        ///     "  Test: divisible by 23",
+        ///     "    If true: throw to monkey 2",
+        ///     "    If false: throw to monkey 3",
+        /// ];
+        /// assert_eq!(day11::Monkey::new(&notes, 0).unwrap().get_divisor(), 23);
+        /// ```
         pub fn get_divisor(&self) -> u128 {
             self.divisor
         }
 
+        /// Number of the monkey in its notes
+        ///
+        /// ```
+        /// let notes = [
+        ///     "Monkey 0:",
+        ///     "  Starting items: 79, 98",
+        ///     "  Operation: new = old * 19",
+        ///     "  Test: divisible by 23",
+        ///     "    If true: throw to monkey 2",
+        ///     "    If false: throw to monkey 3",
+        /// ];
+        /// assert_eq!(day11::Monkey::new(&notes, 0).unwrap().get_id(), 0);
+        /// ```
         pub fn get_id(&self) -> u32 {
             self.id
         }
 
+        /// Number of items inspected so far
+        ///
+        /// ```
+        /// let notes = [
+        ///     "Monkey 0:",
+        ///     "  Starting items: 79, 98",
+        ///     "  Operation: new = old * 19",
+        ///     "  Test: divisible by 23",
+        ///     "    If true: throw to monkey 2",
+        ///     "    If false: throw to monkey 3",
+        /// ];
+        /// let mut monkey = day11::Monkey::new(&notes, 0).unwrap();
+        /// assert_eq!(monkey.get_inspection_count(), 0);
+        /// monkey.process_items(Some(3), 23);
+        /// assert_eq!(monkey.get_inspection_count(), 2);
+        /// ```
         pub fn get_inspection_count(&self) -> u128 {
             self.inspections
         }
 
+        /// Catches an item thrown by another monkey, inspected after the items
+        /// already held
+        ///
+        /// ```
+        /// let notes = [
+        ///     "Monkey 0:",
+        ///     "  Starting items: 79, 98",
+        ///     "  Operation: new = old * 19",
+        ///     "  Test: divisible by 23",
+        ///     "    If true: throw to monkey 2",
+        ///     "    If false: throw to monkey 3",
+        /// ];
+        /// let mut monkey = day11::Monkey::new(&notes, 0).unwrap();
+        /// monkey.add_item(&1);
+        /// assert_eq!(monkey.process_items(Some(1), 23).last(), Some(&(3, 19)));
+        /// ```
         pub fn add_item(&mut self, item: &u128) {
             self.items.push_back(*item);
         }
 
-        // process items currently held and return a hashmap of items
-        // to give to other monkeys
-        pub fn process_items(&mut self, common_divisor: u128) -> Vec<(u32, u128)> {
+        /// Processes the items currently held and returns the monkey to throw
+        /// each of them to with its worry level. With relief the worry level is
+        /// divided after each inspection, without it the worry level is kept
+        /// bounded by the common divisor of all the monkeys.
+        ///
+        /// ```
+        /// let notes = [
+        ///     "Monkey 0:",
+        ///     "  Starting items: 79, 98",
+        ///     "  Operation: new = old * 19",
+        ///     "  Test: divisible by 23",
+        ///     "    If true: throw to monkey 2",
+        ///     "    If false: throw to monkey 3",
+        /// ];
+        /// let mut monkey = day11::Monkey::new(&notes, 0).unwrap();
+        /// assert_eq!(monkey.clone().process_items(Some(3), 23), [(3, 500), (3, 620)]);
+        /// // 79 * 19 and 98 * 19 modulo 23
+        /// assert_eq!(monkey.process_items(None, 23), [(3, 6), (3, 22)]);
+        /// ```
+        pub fn process_items(
+            &mut self,
+            relief: Option<u128>,
+            common_divisor: u128,
+        ) -> Vec<(u32, u128)> {
             let mut transfers: Vec<(u32, u128)> = Vec::with_capacity(self.items.len());
             while let Some(item) = self.items.pop_front() {
                 self.inspections += 1;
-                //let result: u32 = (self.process)(item, self.rhs) / 3;
-                let result: u128 = ((self.process)(item, self.rhs))
-                    .checked_rem(common_divisor)
-                    .expect("division panic");
+                let result: u128 = match relief {
+                    Some(relief) => ((self.process)(item, self.rhs))
+                        .checked_div(relief)
+                        .expect("division panic"),
+                    None => ((self.process)(item, self.rhs))
+                        .checked_rem(common_divisor)
+                        .expect("division panic"),
+                };
                 if result.checked_rem(self.divisor).expect("division panic") == 0 {
                     transfers.push((self.transfer_monkeys.0, result));
                 } else {
@@ -142,58 +236,106 @@ pub mod monkey {
             transfers
         }
 
-        pub fn new(lines: &[&str]) -> Option<Monkey> {
-            if lines.len() == 6 || lines.len() == 7 {
-                let (_, (_, id_str)) =
-                    parse::parse_monkey_id(lines[0]).expect("error parsing monkey id!");
-                let (items_str, _) =
-                    parse::parse_items(lines[1].trim()).expect("error parsing monkey items");
-                let (process_str, _) = parse::parse_operation_str(lines[2].trim())
-                    .expect("error parsing monkey opeartion");
-
-                let (rhs, monkeyop) =
-                    parse::parse_operation(process_str.trim().split_whitespace().collect());
-
-                let (divisor_str, _) =
-                    parse::parse_divisor(lines[3].trim()).expect("error parsing divisor");
-
-                let (true_monkey_str, _) = parse::parse_true_monkey(lines[4].trim())
-                    .expect("unable to parse monkey to pass to!");
-
-                let (false_monkey_str, _) = parse::parse_false_monkey(lines[5].trim())
-                    .expect("unable to parse monkey to pass to!");
-
-                Some(Monkey {
-                    id: id_str
-                        .parse::<u32>()
-                        .expect("non-numeric monkey id encountered"),
-                    inspections: 0,
-                    items: items_str
-                        .split(",")
-                        .map(|x| {
-                            x.trim()
-                                .parse::<u128>()
-                                .expect("non-numeric monkey starting item encountered")
-                        })
-                        .collect(),
-                    process: monkeyop.expect("error parsing monkey op"),
-                    rhs,
-                    divisor: divisor_str
-                        .parse::<u128>()
-                        .expect("non-numeric divisor encountered"),
-                    transfer_monkeys: (
-                        true_monkey_str
-                            .parse::<u32>()
-                            .expect("non-numeric monkey to pass ti"),
-                        false_monkey_str
-                            .parse::<u32>()
-                            .expect("non-numeric monkey to pass ti"),
-                    ),
-                })
-            } else {
-                eprintln!("could not parse monkey!");
-                None
+        /// Monkey of the six lines describing it, starting at line index
+        /// `first_lineidx` of the puzzle input
+        ///
+        /// ```
+        /// use day11::{ErrorKind, Monkey};
+        ///
+        /// let notes = [
+        ///     "Monkey 0:",
+        ///     "  Starting items: 79, 98",
+        ///     "  Operation: new = old * 19",
+        ///     "  Test: divisible by 23",
+        ///     "    If true: throw to monkey 2",
+        ///     "    If false: throw to monkey 3",
+        /// ];
+        /// assert!(Monkey::new(&notes, 0).is_ok());
+        /// let error = Monkey::new(&notes[..5], 7).err().unwrap();
+        /// assert_eq!((error.kind, error.line), (ErrorKind::IncompleteMonkey, 13));
+        /// let mut wrong = notes;
+        /// wrong[2] = "  Operation: new = old / 19";
+        /// let error = Monkey::new(&wrong, 7).err().unwrap();
+        /// assert_eq!((error.kind, error.line, error.column), (ErrorKind::InvalidOperation, 10, 24));
+        /// wrong[2] = "  Operation: old * 19";
+        /// assert_eq!(Monkey::new(&wrong, 7).err().unwrap().kind, ErrorKind::Expected("Operation: new = old "));
+        /// ```
+        pub fn new(lines: &[&str], first_lineidx: usize) -> Result<Monkey, ParseError> {
+            if lines.len() < 6 {
+                let lineidx = first_lineidx + lines.len();
+                return Err(ParseError::new(
+                    ErrorKind::IncompleteMonkey,
+                    lineidx + 1,
+                    1,
+                    "",
+                ));
             }
+
+            let error = |kind: ErrorKind, idx: usize, text: &str| {
+                ParseError::at(kind, first_lineidx + idx, lines[idx], text)
+            };
+            let expected = |prefix: &'static str, idx: usize| {
+                let line = lines[idx].trim();
+                move |_| error(ErrorKind::Expected(prefix), idx, line)
+            };
+
+            let (_, (_, id_str)) = parse::parse_monkey_id(lines[0])
+                .finish()
+                .map_err(expected("Monkey ", 0))?;
+            let (items_str, _) = parse::parse_items(lines[1].trim())
+                .finish()
+                .map_err(expected("Starting items:", 1))?;
+            let (process_str, _) = parse::parse_operation_str(lines[2].trim())
+                .finish()
+                .map_err(expected("Operation: new = old ", 2))?;
+
+            let (rhs, monkeyop) =
+                parse::parse_operation(process_str).map_err(|(kind, text)| error(kind, 2, text))?;
+
+            let (divisor_str, _) = parse::parse_divisor(lines[3].trim())
+                .finish()
+                .map_err(expected("Test: divisible by ", 3))?;
+
+            let (true_monkey_str, _) = parse::parse_true_monkey(lines[4].trim())
+                .finish()
+                .map_err(expected("If true: throw to monkey ", 4))?;
+
+            let (false_monkey_str, _) = parse::parse_false_monkey(lines[5].trim())
+                .finish()
+                .map_err(expected("If false: throw to monkey ", 5))?;
+
+            let number = |text: &str, idx: usize| {
+                text.trim()
+                    .parse::<u128>()
+                    .map_err(|_| error(ErrorKind::InvalidNumber, idx, text))
+            };
+            let monkey_id = |text: &str, idx: usize| {
+                text.parse::<u32>()
+                    .map_err(|_| error(ErrorKind::InvalidNumber, idx, text))
+            };
+
+            let divisor = number(divisor_str, 3)?;
+            if divisor == 0 {
+                return Err(error(ErrorKind::ZeroDivisor, 3, divisor_str));
+            }
+
+            Ok(Monkey {
+                id: monkey_id(id_str, 0)?,
+                inspections: 0,
+                items: items_str
+                    .split(',')
+                    // a monkey may start without items
+                    .filter(|x| !x.trim().is_empty())
+                    .map(|x| number(x, 1))
+                    .collect::<Result<_, _>>()?,
+                process: monkeyop,
+                rhs,
+                divisor,
+                transfer_monkeys: (
+                    monkey_id(true_monkey_str, 4)?,
+                    monkey_id(false_monkey_str, 5)?,
+                ),
+            })
         }
     }
 }
@@ -1,28 +1,100 @@
-use fs_err as fs;
-use itertools::Itertools;
+use common::{profile, Solution};
+use grid::{Adjacency, Grid};
+use log::info;
 use petgraph::algo::astar;
 //use petgraph::dot::Dot;
 use petgraph::graph::NodeIndex;
 use petgraph::Graph;
 use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+
+/// What is wrong with the heightmap
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // elevations are a-z, S and E
+    InvalidElevation,
+    // a row of a different width than the first row
+    RaggedRow { width: usize },
+    MissingStart,
+    MissingEnd,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidElevation => write!(f, "elevation is not a-z, S or E"),
+            ErrorKind::RaggedRow { width } => write!(f, "expected a row of {} squares", width),
+            ErrorKind::MissingStart => write!(f, "there is no start (S) in the heightmap"),
+            ErrorKind::MissingEnd => write!(f, "there is no end (E) in the heightmap"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<ErrorKind>;
 
 pub struct SignalGraph {
     graph: Graph<char, i32>,
-    locs: Vec<NodeIndex>,
-    rows: usize,
-    cols: usize,
-    source: (u32, u32),
-    dest: (u32, u32),
+    heightmap: Grid<char>,
+    // (row, col) of the source and the destination
+    source: (usize, usize),
+    dest: (usize, usize),
 }
 
-pub fn valid_neighbor(src_val: u32, dst: char) -> Option<i32> {
-    let dst_val = if dst == 'E' {
-        'z'.to_digit(36)
-            .expect("failed to calcualte destination value!")
-    } else {
-        dst.to_digit(36)
-            .expect("failed to calcualte destination value!")
+impl SignalGraph {
+    /// The heightmap the graph is built from, with its `S` and `E`
+    pub fn heightmap(&self) -> &Grid<char> {
+        &self.heightmap
+    }
+
+    /// (row, col) of the start `S`
+    pub fn source(&self) -> (usize, usize) {
+        self.source
+    }
+
+    /// (row, col) of the best signal `E`
+    pub fn dest(&self) -> (usize, usize) {
+        self.dest
+    }
+}
+
+/// Elevation of a location of the heightmap, the start `S` is at elevation
+/// `a` and the destination `E` at `z`
+///
+/// ```
+/// assert_eq!(day12::elevation('a'), 10);
+/// assert_eq!(day12::elevation('z'), 35);
+/// assert_eq!(day12::elevation('S'), day12::elevation('a'));
+/// assert_eq!(day12::elevation('E'), day12::elevation('z'));
+/// ```
+pub fn elevation(x: char) -> u32 {
+    let x = match x {
+        'S' => 'a',
+        'E' => 'z',
+        _ => x,
     };
+    x.to_digit(36).expect("elevations are a-z, S and E!")
+}
+
+/// Climb of a step from the elevation `src_val` to the location `dst`,
+/// negative going down, if the step can be taken: at most one higher, any
+/// lower
+///
+/// ```
+/// use day12::{elevation, valid_neighbor};
+///
+/// assert_eq!(valid_neighbor(elevation('a'), 'b'), Some(1));
+/// assert_eq!(valid_neighbor(elevation('m'), 'm'), Some(0));
+/// assert_eq!(valid_neighbor(elevation('m'), 'c'), Some(-10));
+/// assert_eq!(valid_neighbor(elevation('a'), 'c'), None);
+/// // the destination is as high as z, the start as low as a
+/// assert_eq!(valid_neighbor(elevation('y'), 'E'), Some(1));
+/// assert_eq!(valid_neighbor(elevation('x'), 'E'), None);
+/// assert_eq!(valid_neighbor(elevation('S'), 'b'), Some(1));
+/// assert_eq!(valid_neighbor(elevation('b'), 'S'), Some(-1));
+/// ```
+pub fn valid_neighbor(src_val: u32, dst: char) -> Option<i32> {
+    let dst_val = elevation(dst);
 
     let srcval = if src_val == u32::MAX {
         i32::MAX
@@ -32,7 +104,7 @@ pub fn valid_neighbor(src_val: u32, dst: char) -> Option<i32> {
     let dstval: i32 = dst_val.try_into().expect("error computing dest value!");
 
     let distance: i32 = dstval
-        .checked_sub(srcval as i32)
+        .checked_sub(srcval)
         .expect("subtraction overflow when calcuating distance!");
 
     if distance < 2 {
@@ -42,218 +114,203 @@ pub fn valid_neighbor(src_val: u32, dst: char) -> Option<i32> {
     }
 }
 
-pub fn get_graph(filename: String) -> Result<SignalGraph, Box<dyn Error>> {
-    let mut contents = fs::read_to_string(filename)?;
-    // remove any empty newlines at the end!
-    let mut component_lines = contents.lines().collect::<Vec<_>>();
-    if let Some(line) = component_lines.last() {
-        if line.is_empty() {
-            component_lines.pop();
-        }
-    }
+pub struct Day12;
 
-    let cols: usize = component_lines
-        .first()
-        .expect("first line of input is empty!")
-        .len();
-    let rows: usize = component_lines.len();
-    let size: usize = rows.checked_mul(cols).expect("rows * cols overflow!");
-    println!("rows: {}, cols: {}, size: {}", rows, cols, size);
+impl Solution for Day12 {
+    type Model = SignalGraph;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-    contents = contents.replace("\r\n", "\n"); // cool bear windows!
-    contents = contents.replace("\n", "");
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        parse_graph(input)
+    }
 
-    println!("length of contents vector is {}", contents.len());
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        shortest_path(model)
+    }
 
-    let mut field = Graph::<char, i32>::new();
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        shortest_path_any_start(model)
+    }
+}
 
-    let locs: Vec<NodeIndex> = contents.chars().map(|elem| field.add_node(elem)).collect();
+pub fn get_graph(filename: String) -> Result<SignalGraph, Box<dyn Error>> {
+    read_graph(common::input::open(filename)?)
+}
 
-    let mut sourceloc: Option<(u32, u32)> = None;
-    let mut destloc: Option<(u32, u32)> = None;
+pub fn read_graph<R: BufRead>(reader: R) -> Result<SignalGraph, Box<dyn Error>> {
+    parse_graph(&common::input::read(reader)?)
+}
 
-    let mut it = (0..rows).cartesian_product(0..cols);
-    while let Some((rowidx, colidx)) = it.next() {
-        let locidx = rowidx * cols + colidx;
-        if field.raw_nodes().get(locidx).expect("missing node!").weight == 'S' {
-            sourceloc = Some((
-                rowidx.try_into().expect("rowidx is too large!"),
-                colidx.try_into().expect("colidx is too large!"),
-            ));
-        }
-        if field.raw_nodes().get(locidx).expect("missing node!").weight == 'E' {
-            destloc = Some((
-                rowidx.try_into().expect("rowidx is too large!"),
-                colidx.try_into().expect("colidx is too large!"),
-            ));
-        }
+/// Graph of the steps that can be taken on the heightmap
+///
+/// ```
+/// // abc...z
+/// // Saa...aE
+/// let map = format!("{}\nS{}E\n", ('a'..='z').collect::<String>(), "a".repeat(24));
+/// let signalgraph = day12::parse_graph(&map).unwrap();
+/// assert_eq!(day12::shortest_path(&signalgraph).unwrap(), 27);
+/// // from the a next to S, below b
+/// assert_eq!(day12::shortest_path_any_start(&signalgraph).unwrap(), 26);
+///
+/// assert!(day12::parse_graph("Sbc\nab\n").is_err());
+/// assert!(day12::parse_graph("abc\nabE\n").is_err());
+/// assert!(day12::parse_graph("Sb1\nabE\n").is_err());
+/// ```
+pub fn parse_graph(contents: &str) -> Result<SignalGraph, Box<dyn Error>> {
+    let heightmap = profile::phase("heightmap", || {
+        Grid::parse(contents, |x| match x {
+            'a'..='z' | 'S' | 'E' => Some(x),
+            _ => None,
+        })
+    })
+    .map_err(|e| {
+        e.map_kind(|kind| match kind {
+            grid::ErrorKind::InvalidCell => ErrorKind::InvalidElevation,
+            grid::ErrorKind::RaggedRow { width } => ErrorKind::RaggedRow { width },
+        })
+    })?;
 
-        let src = field.raw_nodes()[locidx].weight;
-        let srcval = if src == 'S' {
-            'a'.to_digit(36)
-                .expect("failed to calcualte source location!")
-        } else {
-            src.to_digit(36)
-                .expect("failed to calcualte source location!")
-        };
-
-        // neighbor right
-        if colidx != cols - 1 {
-            let neighboridx = locidx + 1;
-            let elem = locs.get(locidx).expect("missing node!");
-            let neighbor = locs.get(neighboridx).expect("mising node!");
-            let dst = field.raw_nodes()[neighboridx].weight;
-            if let Some(dist) = valid_neighbor(srcval, dst) {
-                //println!("neighbor right at distance {}!", dist);
-                field.add_edge(*elem, *neighbor, dist);
-            }
-        }
+    let rows = heightmap.rows();
+    let cols = heightmap.cols();
+    info!("rows: {}, cols: {}, size: {}", rows, cols, rows * cols);
 
-        // neighbor left
-        if colidx != 0 {
-            let neighboridx = locidx - 1;
-            let elem = locs.get(locidx).expect("missing node!");
-            let neighbor = locs.get(neighboridx).expect("mising node!");
-            let dst = field.raw_nodes()[neighboridx].weight;
-            if let Some(dist) = valid_neighbor(srcval, dst) {
-                //println!("neighbor left at distance {}!", dist);
-                field.add_edge(*elem, *neighbor, dist);
-            }
-        }
+    let srcloc = heightmap
+        .position(|x| *x == 'S')
+        .ok_or_else(|| ParseError::new(ErrorKind::MissingStart, rows + 1, 1, ""))?;
+    let dstloc = heightmap
+        .position(|x| *x == 'E')
+        .ok_or_else(|| ParseError::new(ErrorKind::MissingEnd, rows + 1, 1, ""))?;
 
-        // neighbor up
-        if rowidx != 0 {
-            let neighboridx = locidx - cols;
-            let elem = locs.get(locidx).expect("missing node!");
-            let neighbor = locs.get(neighboridx).expect("mising node!");
-            let dst = field.raw_nodes()[neighboridx].weight;
-            if let Some(dist) = valid_neighbor(srcval, dst) {
-                //println!("neighbor up at distance {}!", dist);
-                field.add_edge(*elem, *neighbor, dist);
-            }
-        }
-
-        // neighbor down
-        if rowidx != rows - 1 {
-            let neighboridx = locidx + cols;
-            let elem = locs.get(locidx).expect("missing node!");
-            let neighbor = locs.get(neighboridx).expect("mising node!");
-            let dst = field.raw_nodes()[neighboridx].weight;
-            if let Some(dist) = valid_neighbor(srcval, dst) {
-                //println!("neighbor down at distance {}!", dist);
-                field.add_edge(*elem, *neighbor, dist);
-            }
-        }
-    }
-    let srcloc = sourceloc.expect("there is no source in input!");
-    let dstloc = destloc.expect("there is no destination in input1");
+    // the nodes are added in the order of the cells of the heightmap, so
+    // the index of a cell is also the index of its node
+    let field = profile::phase("graph", || build_graph(&heightmap));
 
     Ok(SignalGraph {
         graph: field,
-        locs,
-        rows,
-        cols,
+        heightmap,
         source: srcloc,
         dest: dstloc,
     })
 }
 
-pub fn run_part2(filename: String) -> Result<(), Box<dyn Error>> {
-    let signalgraph = get_graph(filename).expect("could not construct graph!");
-
-    let field = signalgraph.graph;
-    let locs = signalgraph.locs;
-    let rows = signalgraph.rows;
-    let cols = signalgraph.cols;
-    let _srcloc = signalgraph.source;
-    let dstloc = signalgraph.dest;
+// a node per cell and an edge to each neighbour that can be climbed
+fn build_graph(heightmap: &Grid<char>) -> Graph<char, i32> {
+    let mut field = Graph::<char, i32>::new();
+    for (_, elem) in heightmap.iter() {
+        field.add_node(*elem);
+    }
 
-    // optionally visualize field
-    // println!("{}", Dot::new(&field));
+    for (loc, src) in heightmap.iter() {
+        let srcval = elevation(*src);
 
-    let mut sources: Vec<(u32, u32)> = Vec::new();
-    let mut it = (0..rows).cartesian_product(0..cols);
-    while let Some((rowidx, colidx)) = it.next() {
-        let locidx = rowidx * cols + colidx;
-        if field.raw_nodes().get(locidx).expect("missing node!").weight == 'S' {
-            sources.push((
-                rowidx.try_into().expect("rowidx is too large!"),
-                colidx.try_into().expect("colidx is too large!"),
-            ));
-        }
-        if field.raw_nodes().get(locidx).expect("missing node!").weight == 'a' {
-            sources.push((
-                rowidx.try_into().expect("rowidx is too large!"),
-                colidx.try_into().expect("colidx is too large!"),
-            ));
+        for neighbor in heightmap.neighbours(loc, Adjacency::Four) {
+            if let Some(dist) = valid_neighbor(srcval, heightmap[neighbor]) {
+                field.add_edge(node(heightmap, loc), node(heightmap, neighbor), dist);
+            }
         }
     }
+    field
+}
 
-    let shortest_distance = sources
-        .iter()
-        .filter_map(|srcloc| {
-            let srclocidx = srcloc.0 as usize * cols + srcloc.1 as usize;
-            let dstlocidx = dstloc.0 as usize * cols + dstloc.1 as usize;
-
-            if let Some((distance, _path)) = astar(
-                &field,
-                *locs.get(srclocidx).expect("missing source!"),
-                |finish| finish == *locs.get(dstlocidx).expect("missing dst!"),
-                |_| 1,
-                |_| 1,
-            ) {
-                return Some(distance);
-            } else {
-                return None;
-            }
-        })
-        .min();
-
-    println!(
-        "shortest distance for all possible starting points is {}",
-        shortest_distance.expect("no shortest distance found!")
-    );
+fn node(heightmap: &Grid<char>, loc: (usize, usize)) -> NodeIndex {
+    NodeIndex::new(
+        heightmap
+            .index_of(loc)
+            .expect("location outside of heightmap!"),
+    )
+}
 
-    Ok(())
+// shortest path to the destination from any location with the lowest elevation
+pub fn shortest_path_any_start(signalgraph: &SignalGraph) -> Result<i32, Box<dyn Error>> {
+    Ok(steps(&shortest_route_any_start(signalgraph)?))
 }
 
-pub fn run_part1(filename: String) -> Result<(), Box<dyn Error>> {
-    let signalgraph = get_graph(filename).expect("could not construct graph!");
-
-    let field = signalgraph.graph;
-    let locs = signalgraph.locs;
-    let _rows = signalgraph.rows;
-    let cols = signalgraph.cols;
-    let srcloc = signalgraph.source;
-    let dstloc = signalgraph.dest;
+// locations along the shortest path to the destination from any location
+// with the lowest elevation
+pub fn shortest_route_any_start(
+    signalgraph: &SignalGraph,
+) -> Result<Vec<(usize, usize)>, Box<dyn Error>> {
+    let heightmap = &signalgraph.heightmap;
 
     // optionally visualize field
     // println!("{}", Dot::new(&field));
 
-    let srclocidx = srcloc.0 as usize * cols + srcloc.1 as usize;
-    let dstlocidx = dstloc.0 as usize * cols + dstloc.1 as usize;
+    let shortest_route = heightmap
+        .iter()
+        .filter(|(_, elem)| **elem == 'S' || **elem == 'a')
+        .filter_map(|(srcloc, _)| route(signalgraph, srcloc))
+        .min_by_key(|route| route.len());
+
+    shortest_route.ok_or_else(|| "no shortest distance found!".into())
+}
+
+// shortest path from the source 'S' to the destination 'E'
+pub fn shortest_path(signalgraph: &SignalGraph) -> Result<i32, Box<dyn Error>> {
+    Ok(steps(&shortest_route(signalgraph)?))
+}
+
+// locations along the shortest path from the source 'S' to the destination 'E'
+pub fn shortest_route(signalgraph: &SignalGraph) -> Result<Vec<(usize, usize)>, Box<dyn Error>> {
+    let heightmap = &signalgraph.heightmap;
+    let srcloc = signalgraph.source;
+    let dstloc = signalgraph.dest;
 
-    println!(
+    info!(
         "source is at : {},{}; srcloc-idx is {}",
-        srcloc.0, srcloc.1, srclocidx
+        srcloc.0,
+        srcloc.1,
+        node(heightmap, srcloc).index()
     );
-    println!(
+    info!(
         "dst is at : {},{}; dstloc-idx is {}",
-        dstloc.0, dstloc.1, dstlocidx
+        dstloc.0,
+        dstloc.1,
+        node(heightmap, dstloc).index()
     );
 
-    if let Some((distance, _path)) = astar(
-        &field,
-        *locs.get(srclocidx).expect("missing source!"),
-        |finish| finish == *locs.get(dstlocidx).expect("missing dst!"),
-        |_| 1,
-        |_| 1,
-    ) {
-        println!("shortest path between S and E is {} steps", distance);
-        //dbg!(path);
-    } else {
-        return Err("error calculating shortest path".into());
-    }
+    route(signalgraph, srcloc).ok_or_else(|| "error calculating shortest path".into())
+}
+
+// every step costs the same, so the shortest path is the one with the
+// fewest locations
+fn route(signalgraph: &SignalGraph, srcloc: (usize, usize)) -> Option<Vec<(usize, usize)>> {
+    let heightmap = &signalgraph.heightmap;
+    let dst = node(heightmap, signalgraph.dest);
+    let (_, path) = profile::phase("astar", || {
+        astar(
+            &signalgraph.graph,
+            node(heightmap, srcloc),
+            |finish| finish == dst,
+            |_| 1,
+            |_| 1,
+        )
+    })?;
+    // the nodes were added in the order of the cells
+    let cols = heightmap.cols();
+    Some(
+        path.into_iter()
+            .map(|nidx| (nidx.index() / cols, nidx.index() % cols))
+            .collect(),
+    )
+}
 
-    Ok(())
+fn steps(route: &[(usize, usize)]) -> i32 {
+    route.len() as i32 - 1
+}
+
+/// The heightmap with the first `length` locations of the route marked `*`,
+/// but for the source and the destination
+pub fn route_picture(
+    signalgraph: &SignalGraph,
+    route: &[(usize, usize)],
+    length: usize,
+) -> Grid<char> {
+    let mut picture = signalgraph.heightmap.clone();
+    for loc in route.iter().take(length) {
+        if !matches!(picture[*loc], 'S' | 'E') {
+            picture[*loc] = '*';
+        }
+    }
+    picture
 }
@@ -1,16 +1,19 @@
-use std::env;
+use clap::Parser;
+use common::cli::DayArgs;
 use std::process;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() != 2 {
-        eprintln!("Provide only one input, the filename of values!")
-    }
+/// Day 12: Hill Climbing Algorithm
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    day: DayArgs,
+}
 
-    let filename_in: String = args[1].clone();
+fn main() {
+    let args = Args::parse();
 
-    if let Err(e) = day12::run_part2(filename_in) {
+    if let Err(e) = args.day.run(12, &day12::Day12) {
         eprintln!("Application error: {}", e);
         process::exit(1);
     }
@@ -1,15 +1,67 @@
-use fs_err as fs;
+use common::Solution;
 use id_tree::InsertBehavior::*;
 use id_tree::*;
 use itertools::EitherOrBoth::{Both, Left, Right};
 use itertools::Itertools;
+use log::{debug, log_enabled, trace, warn, Level};
 use std::cmp::Ordering;
 use std::error::Error;
+use std::fmt;
 
-mod packet;
+pub mod packet;
 use crate::packet::parse::{compare, parse_tree};
 use crate::packet::parse::{Correct, Incorrect, Order};
 
+/// What is wrong with a packet
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // a packet is a list, starting with [
+    ExpectedList,
+    UnexpectedCharacter,
+    InvalidValue,
+    UnclosedList,
+    // text after the list closes
+    TrailingText,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::ExpectedList => write!(f, "expected a list starting with [ at"),
+            ErrorKind::UnexpectedCharacter => write!(f, "unexpected character"),
+            ErrorKind::InvalidValue => write!(f, "invalid value"),
+            ErrorKind::UnclosedList => write!(f, "list is not closed"),
+            ErrorKind::TrailingText => write!(f, "unexpected text after the packet"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<ErrorKind>;
+
+/// Order of a pair of packets, None if they cannot be told apart
+///
+/// ```
+/// use day13::packet::parse::{parse_tree, Correct, Incorrect, Order};
+///
+/// let order = |left, right| day13::test(parse_tree(left).unwrap(), parse_tree(right).unwrap());
+/// assert_eq!(
+///     order("[1,1,3,1,1]", "[1,1,5,1,1]"),
+///     Some(Order::Correct(Correct::LeftSideIsSmaller))
+/// );
+/// // a value is compared with a list as the list of that value
+/// assert_eq!(
+///     order("[[1],[2,3,4]]", "[[1],4]"),
+///     Some(Order::Correct(Correct::LeftSideIsSmaller))
+/// );
+/// assert_eq!(
+///     order("[7,7,7,7]", "[7,7,7]"),
+///     Some(Order::Incorrect(Incorrect::RightSideRanOutItems))
+/// );
+/// assert_eq!(
+///     order("[]", "[3]"),
+///     Some(Order::Correct(Correct::LeftSideRanOutItems))
+/// );
+/// ```
 pub fn test(mut left_tree: Tree<i32>, mut right_tree: Tree<i32>) -> Option<Order> {
     let left_root = left_tree
         .root_node_id()
@@ -20,31 +72,24 @@ pub fn test(mut left_tree: Tree<i32>, mut right_tree: Tree<i32>) -> Option<Order
 
     let mut left_children: Vec<NodeId> = Vec::new();
     let mut right_children: Vec<NodeId> = Vec::new();
-    for lchild in left_tree.children_ids(&left_root).unwrap() {
+    for lchild in left_tree.children_ids(left_root).unwrap() {
         left_children.push(lchild.clone());
     }
-    for rchild in right_tree.children_ids(&right_root).unwrap() {
+    for rchild in right_tree.children_ids(right_root).unwrap() {
         right_children.push(rchild.clone());
     }
 
-    let it = left_children
-        .into_iter()
-        .zip_longest(right_children.into_iter());
+    let it = left_children.into_iter().zip_longest(right_children);
 
     for elem in it {
         match elem {
-            Both(left_child, right_child) => loop {
+            Both(left_child, right_child) => {
                 let result = compare(&mut left_tree, &left_child, &mut right_tree, &right_child);
-                match result {
-                    Some(conclusion) => {
-                        return Some(conclusion);
-                    }
-                    None => {
-                        //println!("at lib.rs no conclusion reached, checking next item!");
-                        break;
-                    }
+                if let Some(conclusion) = result {
+                    return Some(conclusion);
                 }
-            },
+                //println!("at lib.rs no conclusion reached, checking next item!");
+            }
             Left(_) => {
                 return Some(Order::Incorrect(Incorrect::RightSideRanOutItems));
             }
@@ -57,138 +102,147 @@ pub fn test(mut left_tree: Tree<i32>, mut right_tree: Tree<i32>) -> Option<Order
     None
 }
 
-pub fn run_part2(filename: String) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let mut packets: Vec<Tree<i32>> = contents
-        .lines()
-        .filter_map(|line| {
-            if line.is_empty() {
-                None
-            } else {
-                let packet = parse_tree(line);
-                return Some(packet);
-            }
-        })
-        .collect();
-
-    let mut marker1: Tree<i32> = TreeBuilder::new().with_node_capacity(3).build();
-    let mut marker2: Tree<i32> = TreeBuilder::new().with_node_capacity(3).build();
-    let rootid_1: NodeId = marker1.insert(Node::new(-1), AsRoot).unwrap();
-    let rootid_2 = marker2.insert(Node::new(-1), AsRoot).unwrap();
-    let childid_1 = marker1.insert(Node::new(-1), UnderNode(&rootid_1)).unwrap();
-    let childid_2 = marker2.insert(Node::new(-1), UnderNode(&rootid_2)).unwrap();
-    marker1.insert(Node::new(2), UnderNode(&childid_1)).unwrap();
-    marker2.insert(Node::new(6), UnderNode(&childid_2)).unwrap();
-
-    packets.push(marker1.clone());
-    packets.push(marker2.clone());
-
-    packets.sort_by(|a, b| {
-        let a_tmp = a.clone();
-        let b_tmp = b.clone();
-        let result = test(a_tmp, b_tmp);
-        if result.is_none() {
-            eprintln!("packet comparision unsuccessfull!");
-            let mut leftstr = String::new();
-            a.clone()
-                .write_formatted(&mut leftstr)
-                .expect("cannot format tree");
-            println!("left tree is \n{}", leftstr);
-            let mut rightstr = String::new();
-            b.clone()
-                .write_formatted(&mut rightstr)
-                .expect("cannot format tree");
-            println!("right tree is \n{}", rightstr);
-        }
-        match result {
-            Some(Order::Correct(..)) => Ordering::Less,
-            Some(Order::Incorrect(..)) => Ordering::Greater,
-            None => Ordering::Equal,
-        }
-    });
+/// Positions (counting from 1) of the two divider packets once all the
+/// packets are sorted, the decoder key is their product.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DecoderKey {
+    pub marker1: usize,
+    pub marker2: usize,
+}
 
-    let mut pos1: Option<usize> = None;
-    let mut pos2: Option<usize> = None;
-    for (pos, elem) in packets.iter().enumerate() {
-        if *elem == marker1 {
-            pos1 = Some(pos + 1);
-        }
-        if *elem == marker2 {
-            pos2 = Some(pos + 1);
-        }
+impl fmt::Display for DecoderKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.marker1 * self.marker2)
     }
+}
 
-    println!(
-        "position of first marker is {}",
-        pos1.expect("first marker packet missing!")
-    );
-    println!(
-        "position of second marker is {}",
-        pos2.expect("first marker packet missing!")
-    );
+pub struct Day13;
 
-    Ok(())
+// formatting the trees is slow, only do it when they are logged
+fn trace_trees(left_tree: &Tree<i32>, right_tree: &Tree<i32>) {
+    if !log_enabled!(Level::Trace) {
+        return;
+    }
+    let mut leftstr = String::new();
+    left_tree
+        .write_formatted(&mut leftstr)
+        .expect("cannot format tree");
+    trace!("left tree is \n{}", leftstr);
+    let mut rightstr = String::new();
+    right_tree
+        .write_formatted(&mut rightstr)
+        .expect("cannot format tree");
+    trace!("right tree is \n{}", rightstr);
 }
 
-pub fn run_part1(filename: String) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
-    let right_pair_ids: usize = component_lines
-        .chunks(3)
-        .enumerate()
-        .filter_map(|(pair_id, chunk)| {
-            println!("--------------------------------");
-            println!("processing pair_id: {}", pair_id + 1);
-
-            if !(chunk.len() == 2 || chunk.len() == 3) {
-                eprintln!("packets must occur in pairs");
-                return None::<usize>;
+impl Solution for Day13 {
+    // all the packets in input order, pairs are consecutive packets
+    type Model = Vec<Tree<i32>>;
+    type Answer1 = usize;
+    type Answer2 = DecoderKey;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        let mut packets: Vec<Tree<i32>> = Vec::new();
+        for (lineidx, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                continue;
             }
+            let packet = parse_tree(line)
+                .map_err(|(kind, text)| ParseError::at(kind, lineidx, line, text))?;
+            packets.push(packet);
+        }
+        Ok(packets)
+    }
 
-            let left_packet: String = chunk.get(0).expect("no packet on left!").to_string();
-            let right_packet: String = chunk.get(1).expect("no packet on right!").to_string();
-
-            let left_tree = parse_tree(&left_packet);
-            let right_tree = parse_tree(&right_packet);
-
-            let mut leftstr = String::new();
-            left_tree
-                .clone()
-                .write_formatted(&mut leftstr)
-                .expect("cannot format tree");
-            println!("left tree is \n{}", leftstr);
-            let mut rightstr = String::new();
-            right_tree
-                .clone()
-                .write_formatted(&mut rightstr)
-                .expect("cannot format tree");
-            println!("right tree is \n{}", rightstr);
-
-            let result = test(left_tree, right_tree);
-            match result {
-                None => {
-                    eprintln!("failed to reach conclusion for pair id {}", pair_id + 1);
-                    return None;
-                }
-                Some(conclusion) => {
-                    println!("result is {:?}", conclusion);
-                    match conclusion {
-                        Order::Correct(..) => {
-                            println!("found correct ordering at count {}", pair_id + 1);
-                            return Some(pair_id + 1);
-                        }
-                        _ => {
-                            return None;
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        if model.len() % 2 != 0 {
+            return Err("packets must occur in pairs".into());
+        }
+
+        let right_pair_ids: usize = model
+            .chunks(2)
+            .enumerate()
+            .filter_map(|(pair_id, chunk)| {
+                debug!("processing pair_id: {}", pair_id + 1);
+
+                let left_tree = chunk[0].clone();
+                let right_tree = chunk[1].clone();
+                trace_trees(&left_tree, &right_tree);
+
+                let result = test(left_tree, right_tree);
+                match result {
+                    None => {
+                        warn!("failed to reach conclusion for pair id {}", pair_id + 1);
+                        None
+                    }
+                    Some(conclusion) => {
+                        debug!("result is {:?}", conclusion);
+                        match conclusion {
+                            Order::Correct(..) => {
+                                debug!("found correct ordering at count {}", pair_id + 1);
+                                Some(pair_id + 1)
+                            }
+                            _ => None,
                         }
                     }
                 }
+            })
+            .sum();
+
+        Ok(right_pair_ids)
+    }
+
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        let mut packets: Vec<Tree<i32>> = model.clone();
+
+        let mut marker1: Tree<i32> = TreeBuilder::new().with_node_capacity(3).build();
+        let mut marker2: Tree<i32> = TreeBuilder::new().with_node_capacity(3).build();
+        let rootid_1: NodeId = marker1.insert(Node::new(-1), AsRoot).unwrap();
+        let rootid_2 = marker2.insert(Node::new(-1), AsRoot).unwrap();
+        let childid_1 = marker1.insert(Node::new(-1), UnderNode(&rootid_1)).unwrap();
+        let childid_2 = marker2.insert(Node::new(-1), UnderNode(&rootid_2)).unwrap();
+        marker1.insert(Node::new(2), UnderNode(&childid_1)).unwrap();
+        marker2.insert(Node::new(6), UnderNode(&childid_2)).unwrap();
+
+        packets.push(marker1.clone());
+        packets.push(marker2.clone());
+
+        packets.sort_by(|a, b| {
+            let a_tmp = a.clone();
+            let b_tmp = b.clone();
+            let result = test(a_tmp, b_tmp);
+            if result.is_none() {
+                warn!("packet comparision unsuccessfull!");
+                trace_trees(a, b);
             }
-        })
-        .sum();
+            match result {
+                Some(Order::Correct(..)) => Ordering::Less,
+                Some(Order::Incorrect(..)) => Ordering::Greater,
+                None => Ordering::Equal,
+            }
+        });
+
+        let mut pos1: Option<usize> = None;
+        let mut pos2: Option<usize> = None;
+        for (pos, elem) in packets.iter().enumerate() {
+            if *elem == marker1 {
+                pos1 = Some(pos + 1);
+            }
+            if *elem == marker2 {
+                pos2 = Some(pos + 1);
+            }
+        }
 
-    println!("");
-    println!("--------------------------------");
-    println!("sum of right pair ids is : {}", right_pair_ids);
+        Ok(DecoderKey {
+            marker1: pos1.ok_or("first marker packet missing!")?,
+            marker2: pos2.ok_or("second marker packet missing!")?,
+        })
+    }
 
-    Ok(())
+    fn details2(&self, answer: &Self::Answer2) -> Vec<(&'static str, String)> {
+        vec![
+            ("marker1", answer.marker1.to_string()),
+            ("marker2", answer.marker2.to_string()),
+        ]
+    }
 }
@@ -4,6 +4,7 @@ const RIGHTBRACE: char = ']';
 pub mod parse {
 
     use crate::packet::{LEFTBRACE, RIGHTBRACE};
+    use crate::ErrorKind;
     use id_tree::InsertBehavior::*;
     use id_tree::RemoveBehavior::DropChildren;
     use id_tree::*;
@@ -26,17 +27,22 @@ pub mod parse {
         Incorrect(Incorrect),
     }
 
+    /// Order of two values of a pair of packets, None when they are equal
+    /// and the next values decide
+    ///
+    /// ```
+    /// use day13::packet::parse::{compare_vals, Correct, Incorrect, Order};
+    ///
+    /// assert_eq!(compare_vals(3, 5), Some(Order::Correct(Correct::LeftSideIsSmaller)));
+    /// assert_eq!(compare_vals(5, 3), Some(Order::Incorrect(Incorrect::RightSideIsSmaller)));
+    /// assert_eq!(compare_vals(4, 4), None);
+    /// assert_eq!(compare_vals(0, 0), None);
+    /// ```
     pub fn compare_vals(left_val: i32, right_val: i32) -> Option<Order> {
         match left_val.cmp(&right_val) {
-            std::cmp::Ordering::Less => {
-                return Some(Order::Correct(Correct::LeftSideIsSmaller));
-            }
-            std::cmp::Ordering::Greater => {
-                return Some(Order::Incorrect(Incorrect::RightSideIsSmaller));
-            }
-            std::cmp::Ordering::Equal => {
-                return None;
-            }
+            std::cmp::Ordering::Less => Some(Order::Correct(Correct::LeftSideIsSmaller)),
+            std::cmp::Ordering::Greater => Some(Order::Incorrect(Incorrect::RightSideIsSmaller)),
+            std::cmp::Ordering::Equal => None,
         }
     }
 
@@ -89,7 +95,7 @@ pub mod parse {
                 }
 
                 (Some(lchild), None) => {
-                    if rtree.get(&rcurr).unwrap().data().clone() != -1 {
+                    if *rtree.get(&rcurr).unwrap().data() != -1 {
                         lcurr_parent = lcurr.clone();
                         lcurr = lchild.clone();
                         continue;
@@ -98,7 +104,7 @@ pub mod parse {
                     }
                 }
                 (None, Some(rchild)) => {
-                    if ltree.get(&lcurr).unwrap().data().clone() != -1 {
+                    if *ltree.get(&lcurr).unwrap().data() != -1 {
                         rcurr_parent = rcurr.clone();
                         rcurr = rchild.clone();
                         continue;
@@ -112,8 +118,8 @@ pub mod parse {
             }
         }
 
-        let lval = ltree.get(&lcurr).unwrap().data().clone();
-        let rval = rtree.get(&rcurr).unwrap().data().clone();
+        let lval = *ltree.get(&lcurr).unwrap().data();
+        let rval = *rtree.get(&rcurr).unwrap().data();
 
         /*
         println!(
@@ -140,9 +146,7 @@ pub mod parse {
 
         let result = compare_vals(lval, rval);
         match result {
-            Some(conclusion) => {
-                return Some(conclusion);
-            }
+            Some(conclusion) => Some(conclusion),
             None => {
                 let valid_left = lcurr.clone() == lcurr_parent.clone();
                 let valid_right = rcurr.clone() == rcurr_parent.clone();
@@ -156,96 +160,98 @@ pub mod parse {
                 );*/
 
                 match (valid_left, valid_right) {
-                    (true, true) => {
-                        return None;
-                    }
+                    (true, true) => None,
                     (false, false) => compare(ltree, &lcurr_parent, rtree, &rcurr_parent),
 
-                    (false, true) => {
-                        return Some(Order::Correct(Correct::LeftSideRanOutItems));
-                    }
-                    (true, false) => {
-                        return Some(Order::Incorrect(Incorrect::RightSideRanOutItems));
-                    }
+                    (false, true) => Some(Order::Correct(Correct::LeftSideRanOutItems)),
+                    (true, false) => Some(Order::Incorrect(Incorrect::RightSideRanOutItems)),
                 }
             }
         }
     }
 
-    pub fn parse_tree(line: &str) -> Tree<i32> {
+    /// The packet as a tree, or what is wrong with it and the offending
+    /// text. Lists are nodes of value -1, with their elements as children.
+    ///
+    /// ```
+    /// use day13::packet::parse::parse_tree;
+    /// use day13::ErrorKind;
+    ///
+    /// let tree = parse_tree("[1,[2,10],[]]").unwrap();
+    /// let root = tree.root_node_id().unwrap();
+    /// let values: Vec<i32> = tree.traverse_pre_order(root).unwrap().map(|x| *x.data()).collect();
+    /// assert_eq!(values, [-1, 1, -1, 2, 10, -1]);
+    ///
+    /// assert_eq!(parse_tree("[1,a]").unwrap_err(), (ErrorKind::UnexpectedCharacter, "a"));
+    /// assert_eq!(parse_tree("[1,[2]").unwrap_err(), (ErrorKind::UnclosedList, "[1,[2]"));
+    /// assert_eq!(parse_tree("[1]]").unwrap_err(), (ErrorKind::TrailingText, "]"));
+    /// assert_eq!(parse_tree("1").unwrap_err(), (ErrorKind::ExpectedList, "1"));
+    /// ```
+    pub fn parse_tree(line: &str) -> Result<Tree<i32>, (ErrorKind, &str)> {
         let mut tree: Tree<i32> = TreeBuilder::new().with_node_capacity(5).build();
         // let -2 represent the root node
         //let root_id: NodeId = tree.insert(Node::new(-2), AsRoot).unwrap();
         let mut curr: Option<NodeId> = None;
 
-        let mut input = line.chars();
-        let mut val_str = String::new();
-        let mut outer_brace = true;
-        while let Some(in_char) = input.next() {
+        // byte offset where the value being read starts
+        let mut val_start: Option<usize> = None;
+        // set once the outer list is closed
+        let mut closed = false;
+        for (idx, in_char) in line.char_indices() {
             //println!("in_char is : {}", in_char);
+            if closed {
+                return Err((ErrorKind::TrailingText, &line[idx..]));
+            }
             match in_char {
                 LEFTBRACE => {
+                    if let Some(start) = val_start {
+                        return Err((ErrorKind::InvalidValue, &line[start..=idx]));
+                    }
                     // let -1 represent a [] layer
-                    if outer_brace {
-                        curr = Some(tree.insert(Node::new(-1), AsRoot).unwrap());
-                        outer_brace = false;
-                        continue;
-                    } else {
-                        curr = Some(
-                            tree.insert(
-                                Node::new(-1),
-                                InsertBehavior::UnderNode(&curr.as_ref().unwrap()),
-                            )
+                    curr = Some(match &curr {
+                        None => tree.insert(Node::new(-1), AsRoot).unwrap(),
+                        Some(parent) => tree
+                            .insert(Node::new(-1), InsertBehavior::UnderNode(parent))
                             .unwrap(),
-                        );
-                        continue;
-                    }
+                    });
                 }
-                RIGHTBRACE => {
-                    if let Ok(elem) = val_str.trim().parse::<i32>() {
-                        // there will always be a parentval for a well formed
-                        // packet!
-                        tree.insert(
-                            Node::new(elem),
-                            InsertBehavior::UnderNode(&curr.as_ref().unwrap()),
-                        )
-                        .unwrap();
-                        val_str.clear();
+                RIGHTBRACE | ',' => {
+                    let parent = curr
+                        .clone()
+                        .ok_or((ErrorKind::ExpectedList, &line[idx..]))?;
+                    if let Some(start) = val_start.take() {
+                        let elem = line[start..idx]
+                            .trim()
+                            .parse::<i32>()
+                            .map_err(|_| (ErrorKind::InvalidValue, &line[start..idx]))?;
+                        tree.insert(Node::new(elem), InsertBehavior::UnderNode(&parent))
+                            .unwrap();
                     }
 
-                    if tree
-                        .get(&curr.as_ref().unwrap())
-                        .unwrap()
-                        .parent()
-                        .is_some()
-                    {
-                        curr = Some(
-                            tree.get(&curr.as_ref().unwrap())
-                                .unwrap()
-                                .parent()
-                                .unwrap()
-                                .clone(),
-                        );
+                    if in_char == RIGHTBRACE {
+                        match tree.get(&parent).unwrap().parent() {
+                            Some(grandparent) => curr = Some(grandparent.clone()),
+                            None => closed = true,
+                        }
                     }
-                    continue;
                 }
-                ',' => {
-                    if let Ok(elem) = val_str.trim().parse::<i32>() {
-                        tree.insert(
-                            Node::new(elem),
-                            InsertBehavior::UnderNode(&curr.as_ref().unwrap()),
-                        )
-                        .unwrap();
-                        val_str.clear();
+                '0'..='9' => {
+                    if curr.is_none() {
+                        return Err((ErrorKind::ExpectedList, &line[idx..]));
                     }
-                    continue;
+                    val_start.get_or_insert(idx);
                 }
+                ' ' => {}
                 _ => {
-                    val_str.push(in_char);
+                    let end = idx + in_char.len_utf8();
+                    return Err((ErrorKind::UnexpectedCharacter, &line[idx..end]));
                 }
             }
         }
 
-        tree
+        if !closed {
+            return Err((ErrorKind::UnclosedList, line));
+        }
+        Ok(tree)
     }
 }
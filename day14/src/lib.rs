@@ -1,27 +1,109 @@
+use common::Solution;
+use geometry::Point;
+use grid::Grid;
 use std::error::Error;
+use std::fmt;
 mod reservoir;
-use crate::reservoir::reservoir::Canvas;
+pub use crate::reservoir::reservoir::Canvas;
 
-pub fn run1(filename: String, source_location: (u32, u32)) -> Result<(), Box<dyn Error>> {
-    let mut canvas = Canvas::new(filename, source_location);
-    println!("canvas is {}", canvas);
-    canvas.render();
+/// What is wrong with a path of rock
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    InvalidCoordinate,
+    // points are given as x,y
+    ExpectedPair,
+    // consecutive points share either x or y
+    DiagonalPath,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidCoordinate => write!(f, "invalid coordinate"),
+            ErrorKind::ExpectedPair => write!(f, "expected a point x,y at"),
+            ErrorKind::DiagonalPath => write!(f, "path is neither horizontal nor vertical at"),
+        }
+    }
+}
 
-    canvas.fill_sand();
-    println!("canvas after fill is {}", canvas);
-    canvas.render();
+pub type ParseError = common::ParseError<ErrorKind>;
+
+pub struct Day14 {
+    pub source_location: Point,
+}
 
-    Ok(())
+impl Default for Day14 {
+    fn default() -> Self {
+        Day14 {
+            source_location: Point::new(500, 0),
+        }
+    }
 }
 
-pub fn run2(filename: String, source_location: (u32, u32)) -> Result<(), Box<dyn Error>> {
-    let mut canvas = Canvas::new(filename, source_location);
-    println!("canvas is {}", canvas);
-    canvas.render();
+impl Solution for Day14 {
+    type Model = Canvas;
+    // units of sand at rest once the sand stops settling
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        Ok(Canvas::parse(input, self.source_location)?)
+    }
+
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        let mut canvas = model.clone();
+        canvas.fill_sand();
+        canvas.render();
+        Ok(canvas.sand_count())
+    }
+
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        let mut canvas = model.clone();
+        canvas.fill_sand_infinite();
+        canvas.render();
+        Ok(canvas.sand_count())
+    }
+}
 
-    canvas.fill_sand_infinite();
-    println!("canvas after fill is {}", canvas);
-    canvas.render();
+/// The cave before any sand falls, every `every` units of sand and once the
+/// sand stops settling, on the floor of part 2 if `floor` is set
+///
+/// ```
+/// use geometry::Point;
+///
+/// let scan = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+/// let canvas = day14::Canvas::parse(scan, Point::new(500, 0)).unwrap();
+/// let frames = day14::sand_frames(&canvas, false, 10);
+/// // before any sand, after 10 and 20 units, and the 24 units at rest
+/// assert_eq!(frames.len(), 4);
+/// let sand = frames.last().unwrap().iter().filter(|(_, cell)| **cell == 'o').count();
+/// assert_eq!(sand, 24);
+/// // all the frames are of the size of the last one
+/// assert!(frames.iter().all(|frame| frame.rows() == frames[3].rows()));
+/// assert_eq!(day14::sand_frames(&canvas, true, 1).len(), 94);
+/// ```
+pub fn sand_frames(model: &Canvas, floor: bool, every: usize) -> Vec<Grid<char>> {
+    let mut canvas = model.clone();
+    let mut states = vec![canvas.clone()];
+    let mut grains: usize = 0;
+    let keep = |state: &Canvas| {
+        grains += 1;
+        if grains.is_multiple_of(every.max(1)) {
+            states.push(state.clone());
+        }
+    };
+    if floor {
+        canvas.fill_sand_infinite_with(keep);
+    } else {
+        canvas.fill_sand_with(keep);
+    }
+    if !grains.is_multiple_of(every.max(1)) {
+        states.push(canvas.clone());
+    }
 
-    Ok(())
+    let bounds = canvas.picture_bounds();
+    states
+        .iter()
+        .map(|state| state.picture_within(bounds))
+        .collect()
 }
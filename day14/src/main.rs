@@ -1,18 +1,19 @@
-use std::env;
+use clap::Parser;
+use common::cli::DayArgs;
 use std::process;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() != 2 {
-        eprintln!("Provide only one input, the filename of values!")
-    }
-
-    let filename_in: String = args[1].clone();
+/// Day 14: Regolith Reservoir
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    day: DayArgs,
+}
 
-    let source_loc = (500, 0);
+fn main() {
+    let args = Args::parse();
 
-    if let Err(e) = day14::run2(filename_in, source_loc) {
+    if let Err(e) = args.day.run(14, &day14::Day14::default()) {
         eprintln!("Application error: {}", e);
         process::exit(1);
     }
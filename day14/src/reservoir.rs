@@ -1,8 +1,13 @@
+#[allow(clippy::module_inception)]
 pub mod reservoir {
 
-    use fs_err as fs;
-    use std::collections::HashMap;
+    use crate::{ErrorKind, ParseError};
+    use geometry::{Bounds, Direction, Point};
+    use grid::{Grid, SparseGrid};
+    use log::{debug, log_enabled, trace, warn, Level};
+    use std::error::Error;
     use std::fmt;
+    use std::io::BufRead;
 
     #[derive(Debug, Clone, Copy, PartialEq)]
     enum Matter {
@@ -10,14 +15,15 @@ pub mod reservoir {
         Sand,
     }
 
+    // a sand particle tries to move down, down and left, then down and right
+    const FALL: [Direction; 3] = [Direction::Down, Direction::DownLeft, Direction::DownRight];
+
+    #[derive(Clone)]
     pub struct Canvas {
-        x_min: u32,
-        x_max: u32,
-        y_min: u32,
-        y_max: u32,
-        source_x: u32,
-        source_y: u32,
-        fill: HashMap<(u32, u32), Matter>,
+        // bounds of the rocks and the source, the floor is right below them
+        bounds: Bounds,
+        source: Point,
+        fill: SparseGrid<Matter>,
     }
 
     impl fmt::Display for Canvas {
@@ -25,323 +31,351 @@ pub mod reservoir {
             let num_rocks: usize = self
                 .fill
                 .values()
-                .filter_map(|value| {
-                    if *value == Matter::Rock {
-                        Some(1)
-                    } else {
-                        None
-                    }
-                })
-                .sum();
-            let num_sand_particles: usize = self
-                .fill
-                .values()
-                .filter_map(|value| {
-                    if *value == Matter::Sand {
-                        Some(1)
-                    } else {
-                        None
-                    }
-                })
-                .sum();
+                .filter(|value| **value == Matter::Rock)
+                .count();
 
             write!(
                 f,
-                "corners are ({},{}); ({},{}), with source at ({},{}) with {} rocks and {} sand particles",
-                self.x_min,
-                self.y_min,
-                self.x_max,
-                self.y_max,
-                self.source_x,
-                self.source_y,
+                "corners are {}; {}, with source at {} with {} rocks and {} sand particles",
+                self.bounds.top_left(),
+                self.bounds.bottom_right(),
+                self.source,
                 num_rocks,
-                num_sand_particles
+                self.sand_count()
             )
         }
     }
 
     impl Canvas {
+        /// Units of sand at rest in the cave
+        ///
+        /// ```
+        /// use geometry::Point;
+        ///
+        /// let scan = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        /// let mut canvas = day14::Canvas::parse(scan, Point::new(500, 0)).unwrap();
+        /// assert_eq!(canvas.sand_count(), 0);
+        /// canvas.fill_sand();
+        /// assert_eq!(canvas.sand_count(), 24);
+        /// ```
+        pub fn sand_count(&self) -> usize {
+            self.fill
+                .values()
+                .filter(|value| **value == Matter::Sand)
+                .count()
+        }
+
+        /// Logs the picture of the cave, which is only worked out when it is
+        /// logged
+        ///
+        /// ```
+        /// use geometry::Point;
+        ///
+        /// let scan = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        /// let canvas = day14::Canvas::parse(scan, Point::new(500, 0)).unwrap();
+        /// // nothing to log to
+        /// canvas.render();
+        /// ```
         pub fn render(&self) {
-            for y in self.y_min..(self.y_max + 2) {
-                let mut tmp: String = String::new();
-                for x in self.x_min..self.x_max {
-                    let elem = &self.fill.get(&(x, y));
-                    match elem {
-                        None => tmp.push('.'),
-                        Some(Matter::Rock) => tmp.push('#'),
-                        Some(Matter::Sand) => tmp.push('o'),
-                    }
-                }
-                println!("{tmp}");
+            if !log_enabled!(Level::Debug) {
+                return;
             }
+            debug!("\n{}", self.picture().render(|x| *x));
+        }
+
+        /// Bounds of the rocks, the sand and the source, one row lower for
+        /// the sand on the floor lying outside the bounds of the rocks
+        ///
+        /// ```
+        /// use geometry::Point;
+        ///
+        /// let scan = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        /// let mut canvas = day14::Canvas::parse(scan, Point::new(500, 0)).unwrap();
+        /// let bounds = canvas.picture_bounds();
+        /// assert_eq!((bounds.top_left(), bounds.bottom_right()), (Point::new(494, 0), Point::new(503, 10)));
+        /// canvas.fill_sand_infinite();
+        /// assert_eq!(canvas.picture_bounds().top_left(), Point::new(490, 0));
+        /// ```
+        pub fn picture_bounds(&self) -> Bounds {
+            let mut bounds = match self.fill.bounds() {
+                Some(fill) => self.bounds.union(fill),
+                None => self.bounds,
+            };
+            bounds.y_max += 1;
+            bounds
         }
 
-        fn possible_move(&self, test_loc: (u32, u32)) -> bool {
-            if (test_loc.0 < self.x_min) || (test_loc.0 > self.x_max) || (test_loc.1 > self.y_max) {
-                false
-            } else {
-                true
+        /// The cave as rocks `#`, sand `o` and its source `+`
+        ///
+        /// ```
+        /// use geometry::Point;
+        ///
+        /// let scan = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        /// let canvas = day14::Canvas::parse(scan, Point::new(500, 0)).unwrap();
+        /// let picture = canvas.picture();
+        /// assert_eq!((picture.rows(), picture.cols()), (11, 10));
+        /// assert_eq!((picture[(0, 6)], picture[(4, 4)], picture[(0, 0)]), ('+', '#', '.'));
+        /// ```
+        pub fn picture(&self) -> Grid<char> {
+            self.picture_within(self.picture_bounds())
+        }
+
+        /// The cave within `bounds`, the same bounds for each state of the
+        /// cave keep the frames of an animation aligned
+        ///
+        /// ```
+        /// use geometry::{Bounds, Point};
+        ///
+        /// let scan = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        /// let canvas = day14::Canvas::parse(scan, Point::new(500, 0)).unwrap();
+        /// let mut bounds = Bounds::new(Point::new(496, 4));
+        /// bounds.extend(Point::new(500, 6));
+        /// let picture = canvas.picture_within(bounds);
+        /// assert_eq!((picture.rows(), picture.cols()), (3, 5));
+        /// assert_eq!((picture[(2, 0)], picture[(2, 3)]), ('#', '.'));
+        /// ```
+        pub fn picture_within(&self, bounds: Bounds) -> Grid<char> {
+            let map = self.fill.render_within(bounds, '.', |elem| match elem {
+                Matter::Rock => '#',
+                Matter::Sand => 'o',
+            });
+            let mut picture = Grid::parse(&map, Some).expect("map is rectangular");
+            let source = self.source - bounds.top_left();
+            if let (Ok(row), Ok(col)) = (usize::try_from(source.dy), usize::try_from(source.dx)) {
+                if picture.get((row, col)) == Some(&'.') {
+                    picture[(row, col)] = '+';
+                }
             }
+            picture
         }
 
+        fn possible_move(&self, test_loc: Point) -> bool {
+            !((test_loc.x < self.bounds.x_min)
+                || (test_loc.x > self.bounds.x_max)
+                || (test_loc.y > self.bounds.y_max))
+        }
+
+        /// Lets sand fall onto the floor two rows below the lowest rock until
+        /// it blocks the source
+        ///
+        /// ```
+        /// use geometry::Point;
+        ///
+        /// let scan = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        /// let mut canvas = day14::Canvas::parse(scan, Point::new(500, 0)).unwrap();
+        /// canvas.fill_sand_infinite();
+        /// assert_eq!(canvas.sand_count(), 93);
+        /// ```
         pub fn fill_sand_infinite(&mut self) {
-            let mut sand_loc = (self.source_x, self.source_y);
-            let mut test_loc: (u32, u32);
+            self.fill_sand_infinite_with(|_| {})
+        }
 
-            loop {
-                // try to move down
-                test_loc = (sand_loc.0, sand_loc.1 + 1);
-                match self.fill.get(&(test_loc)) {
-                    Some(_) => {}
-                    None => {
-                        sand_loc = test_loc;
-                        if test_loc.1 == self.y_max + 1 {
-                            self.fill.insert(sand_loc, Matter::Sand);
-                            if test_loc.0 < self.x_min {
-                                self.x_min = test_loc.0
-                            }
-                            if test_loc.1 > self.x_max {
-                                self.x_max = test_loc.0
-                            }
-                            sand_loc = (self.source_x, self.source_y);
-                        }
-                        continue;
-                    }
-                }
+        /// Same as `fill_sand_infinite`, `after_grain` sees the cave each time
+        /// a unit of sand comes to rest
+        ///
+        /// ```
+        /// use geometry::Point;
+        ///
+        /// let scan = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        /// let mut canvas = day14::Canvas::parse(scan, Point::new(500, 0)).unwrap();
+        /// let mut counts = Vec::new();
+        /// canvas.fill_sand_infinite_with(|canvas| counts.push(canvas.sand_count()));
+        /// assert_eq!(counts, (1..=93).collect::<Vec<_>>());
+        /// ```
+        pub fn fill_sand_infinite_with<F: FnMut(&Canvas)>(&mut self, mut after_grain: F) {
+            let mut sand_loc = self.source;
+            let floor = self.bounds.y_max + 2;
 
-                // try to move down and left
-                test_loc = (sand_loc.0 - 1, sand_loc.1 + 1);
-                match self.fill.get(&test_loc) {
-                    Some(_) => {}
-                    None => {
+            'particle: loop {
+                for step in FALL {
+                    let test_loc = sand_loc + step.vector();
+                    if test_loc.y < floor && !self.fill.contains(test_loc) {
                         sand_loc = test_loc;
-                        if test_loc.1 == self.y_max + 1 {
-                            self.fill.insert(sand_loc, Matter::Sand);
-                            if test_loc.0 < self.x_min {
-                                self.x_min = test_loc.0
-                            }
-                            if test_loc.1 > self.x_max {
-                                self.x_max = test_loc.0
-                            }
-                            sand_loc = (self.source_x, self.source_y);
-                        }
-                        continue;
+                        continue 'particle;
                     }
                 }
 
-                // try to move down and right
-                test_loc = (sand_loc.0 + 1, sand_loc.1 + 1);
-                match self.fill.get(&test_loc) {
-                    Some(_) => {}
-                    None => {
-                        sand_loc = test_loc;
-                        if test_loc.1 == self.y_max + 1 {
-                            self.fill.insert(sand_loc, Matter::Sand);
-                            if test_loc.0 < self.x_min {
-                                self.x_min = test_loc.0
-                            }
-                            if test_loc.1 > self.x_max {
-                                self.x_max = test_loc.0
-                            }
-                            sand_loc = (self.source_x, self.source_y);
-                        }
-                        continue;
-                    }
+                self.fill.insert(sand_loc, Matter::Sand);
+                after_grain(self);
+                if sand_loc == self.source {
+                    trace!("last sand particle inserted at source {}", sand_loc);
+                    break;
                 }
 
-                match (sand_loc.0 == self.source_x) && (sand_loc.1 == self.source_y) {
-                    false => {
-                        println!("inserting sand particle! at {}/{}", sand_loc.0, sand_loc.1);
-                        self.fill.insert(sand_loc, Matter::Sand);
-
-                        //reset to new sand particle
-                        sand_loc = (self.source_x, self.source_y);
-
-                        // optionally see where the particle was added!
-                        //self.render();
-                        continue;
-                    }
-                    true => {
-                        println!(
-                            "last sand particle inserted at source {}/{}",
-                            sand_loc.0, sand_loc.1
-                        );
-                        self.fill.insert(sand_loc, Matter::Sand);
+                trace!("inserting sand particle! at {}", sand_loc);
+                //reset to new sand particle
+                sand_loc = self.source;
 
-                        break;
-                    }
-                }
+                // optionally see where the particle was added!
+                //self.render();
             }
         }
 
+        /// Lets sand fall until it falls past the lowest rock, or comes to
+        /// rest at the source
+        ///
+        /// ```
+        /// use geometry::Point;
+        ///
+        /// let scan = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        /// let mut canvas = day14::Canvas::parse(scan, Point::new(500, 0)).unwrap();
+        /// canvas.fill_sand();
+        /// assert_eq!(canvas.sand_count(), 24);
+        /// ```
         pub fn fill_sand(&mut self) {
-            let mut sand_loc = (self.source_x, self.source_y);
-            let mut test_loc: (u32, u32);
+            self.fill_sand_with(|_| {})
+        }
 
-            loop {
-                // try to move down
-                test_loc = (sand_loc.0, sand_loc.1 + 1);
-                match self.fill.get(&(test_loc)) {
-                    Some(_) => {}
-                    None => match self.possible_move(test_loc) {
-                        true => {
-                            sand_loc = test_loc;
-                            continue;
-                        }
-                        false => {
-                            break;
-                        }
-                    },
-                }
+        /// Same as `fill_sand`, `after_grain` sees the cave each time a unit of
+        /// sand comes to rest
+        ///
+        /// ```
+        /// use geometry::Point;
+        ///
+        /// let scan = "498,4 -> 498,6 -> 496,6\n503,4 -> 502,4 -> 502,9 -> 494,9\n";
+        /// let mut canvas = day14::Canvas::parse(scan, Point::new(500, 0)).unwrap();
+        /// let mut grains = 0;
+        /// canvas.fill_sand_with(|_| grains += 1);
+        /// assert_eq!(grains, 24);
+        /// ```
+        pub fn fill_sand_with<F: FnMut(&Canvas)>(&mut self, mut after_grain: F) {
+            let mut sand_loc = self.source;
 
-                // try to move down and left
-                test_loc = (sand_loc.0 - 1, sand_loc.1 + 1);
-                match self.fill.get(&test_loc) {
-                    Some(_) => {}
-                    None => match self.possible_move(test_loc) {
-                        true => {
-                            sand_loc = test_loc;
-                            continue;
-                        }
-                        false => {
-                            break;
+            'particle: loop {
+                for step in FALL {
+                    let test_loc = sand_loc + step.vector();
+                    if !self.fill.contains(test_loc) {
+                        if !self.possible_move(test_loc) {
+                            // the particle falls into the abyss
+                            break 'particle;
                         }
-                    },
-                }
-
-                // try to move down and right
-                test_loc = (sand_loc.0 + 1, sand_loc.1 + 1);
-                match self.fill.get(&test_loc) {
-                    Some(_) => {}
-                    None => match self.possible_move(test_loc) {
-                        true => {
-                            sand_loc = test_loc;
-                            continue;
-                        }
-                        false => {
-                            break;
-                        }
-                    },
+                        sand_loc = test_loc;
+                        continue 'particle;
+                    }
                 }
 
-                println!("inserting sand particle! at {}/{}", sand_loc.0, sand_loc.1);
+                trace!("inserting sand particle! at {}", sand_loc);
                 self.fill.insert(sand_loc, Matter::Sand);
+                after_grain(self);
+                if sand_loc == self.source {
+                    // the rocks hold all the sand up to the source
+                    break;
+                }
 
                 //reset to new sand particle
-                sand_loc = (self.source_x, self.source_y);
+                sand_loc = self.source;
 
                 //self.render();
-                continue;
             }
         }
 
-        pub fn new(filename: String, source_location: (u32, u32)) -> Canvas {
-            let contents = fs::read_to_string(filename).expect("input file not found!");
-            let mut lines = contents.lines();
+        /// Cave of the rock paths in the file `filename`
+        ///
+        /// ```
+        /// use geometry::Point;
+        ///
+        /// assert!(day14::Canvas::new("missing.txt".to_string(), Point::new(500, 0)).is_err());
+        /// ```
+        pub fn new(filename: String, source_location: Point) -> Result<Canvas, Box<dyn Error>> {
+            Canvas::from_reader(common::input::open(filename)?, source_location)
+        }
 
-            let mut x_min = u32::MAX;
-            let mut x_max = u32::MIN;
-            let mut y_min = u32::MAX;
-            let mut y_max = u32::MIN;
+        /// Cave of the rock paths read from `reader`
+        ///
+        /// ```
+        /// use geometry::Point;
+        ///
+        /// let canvas = day14::Canvas::from_reader("499,2 -> 501,2\n".as_bytes(), Point::new(500, 0)).unwrap();
+        /// assert_eq!(canvas.picture()[(2, 1)], '#');
+        /// ```
+        pub fn from_reader<R: BufRead>(
+            reader: R,
+            source_location: Point,
+        ) -> Result<Canvas, Box<dyn Error>> {
+            let contents = common::input::read(reader)?;
+            Ok(Canvas::parse(&contents, source_location)?)
+        }
 
-            let mut fill: HashMap<(u32, u32), Matter> = HashMap::new();
+        /// Cave of the rock paths, one path of points `x,y` joined by `->` per
+        /// line, with sand falling from `source_location`
+        ///
+        /// ```
+        /// use day14::{Canvas, ErrorKind};
+        /// use geometry::Point;
+        ///
+        /// let source = Point::new(500, 0);
+        /// assert!(Canvas::parse("498,4 -> 498,6 -> 496,6\n", source).is_ok());
+        /// let error = Canvas::parse("498,4 -> 496,6\n", source).err().unwrap();
+        /// assert_eq!((error.kind, error.column), (ErrorKind::DiagonalPath, 9));
+        /// assert_eq!(Canvas::parse("498,4 -> 498\n", source).err().unwrap().kind, ErrorKind::ExpectedPair);
+        /// assert_eq!(Canvas::parse("498,x\n", source).err().unwrap().kind, ErrorKind::InvalidCoordinate);
+        /// ```
+        pub fn parse(contents: &str, source_location: Point) -> Result<Canvas, ParseError> {
+            let mut fill: SparseGrid<Matter> = SparseGrid::new();
 
-            while let Some(line) = lines.next() {
-                let mut start: Option<(u32, u32)> = None;
-                let mut end: (u32, u32);
+            for (lineidx, line) in contents.lines().enumerate() {
+                if line.is_empty() {
+                    continue;
+                }
+                let mut start: Option<Point> = None;
                 //println!("parsing line {}", line);
                 let elems = line.split("->");
                 for elem in elems {
                     let pair: Vec<_> = elem
-                        .split(",")
+                        .split(',')
                         .map(|x| {
-                            x.trim()
-                                .parse::<u32>()
-                                .expect("non-numeric coordinate given!")
+                            x.trim().parse::<u32>().map_err(|_| {
+                                ParseError::at(ErrorKind::InvalidCoordinate, lineidx, line, x)
+                            })
                         })
-                        .collect();
-                    assert_eq!(
-                        pair.len(),
-                        2,
-                        "coordinates should be specified as pairs of numbers x,y!"
-                    );
-                    //println!("elem is {}/{}", pair[0], pair[1]);
-
-                    if pair[0] > x_max {
-                        x_max = pair[0];
-                    }
-                    if pair[0] < x_min {
-                        x_min = pair[0];
-                    }
-
-                    if pair[1] > y_max {
-                        y_max = pair[1];
-                    }
-                    if pair[1] < y_min {
-                        y_min = pair[1];
+                        .collect::<Result<_, _>>()?;
+                    // coordinates should be specified as pairs of numbers x,y
+                    if pair.len() != 2 {
+                        return Err(ParseError::at(ErrorKind::ExpectedPair, lineidx, line, elem));
                     }
+                    let end = Point::new(i64::from(pair[0]), i64::from(pair[1]));
 
-                    match start {
-                        None => {
-                            start = Some((pair[0], pair[1]));
+                    if let Some(start_loc) = start {
+                        if start_loc.x != end.x && start_loc.y != end.y {
+                            return Err(ParseError::at(
+                                ErrorKind::DiagonalPath,
+                                lineidx,
+                                line,
+                                elem,
+                            ));
                         }
-                        Some(start_loc) => {
-                            end = (pair[0], pair[1]);
-                            // line along y
-                            if start_loc.0 == end.0 {
-                                if start_loc.1 < end.1 {
-                                    for y in start_loc.1..=end.1 {
-                                        fill.insert((start_loc.0, y), Matter::Rock);
-                                    }
-                                } else {
-                                    for y in end.1..=start_loc.1 {
-                                        fill.insert((start_loc.0, y), Matter::Rock);
-                                    }
-                                }
-                            }
-                            // line along x
-                            if start_loc.1 == end.1 {
-                                if start_loc.0 < end.0 {
-                                    for x in start_loc.0..=end.0 {
-                                        fill.insert((x, start_loc.1), Matter::Rock);
-                                    }
-                                } else {
-                                    for x in end.0..=start_loc.0 {
-                                        fill.insert((x, start_loc.1), Matter::Rock);
-                                    }
-                                }
-                            }
-                            start = Some(end);
+                        // the path runs along either x or y
+                        let step = (end - start_loc).signum();
+                        let mut rock = start_loc;
+                        fill.insert(rock, Matter::Rock);
+                        while rock != end {
+                            rock += step;
+                            fill.insert(rock, Matter::Rock);
                         }
                     }
+                    start = Some(end);
                 }
             }
 
-            if source_location.0 > x_max {
-                x_max = source_location.0;
-                eprintln!("source not above any rocks!")
-            }
-            if source_location.0 < x_min {
-                x_min = source_location.0;
-                eprintln!("source not above any rocks!")
-            }
-            if source_location.1 > y_max {
-                y_max = source_location.1;
-                eprintln!("source not above any rocks!")
-            }
-            if source_location.1 < y_min {
-                y_min = source_location.1;
-            }
+            let source = source_location;
+            let bounds = match fill.bounds() {
+                Some(mut bounds) => {
+                    if !bounds.contains(Point::new(source.x, bounds.y_min))
+                        || source.y > bounds.y_max
+                    {
+                        warn!("source not above any rocks!")
+                    }
+                    bounds.extend(source);
+                    bounds
+                }
+                None => Bounds::new(source),
+            };
 
-            Canvas {
-                x_min,
-                x_max,
-                y_min,
-                y_max,
-                source_x: source_location.0,
-                source_y: source_location.1,
+            Ok(Canvas {
+                bounds,
+                source,
                 fill,
-            }
+            })
         }
     }
 }
@@ -1,32 +1,105 @@
+use common::Solution;
 use std::error::Error;
-mod sensors;
+use std::fmt;
+pub mod sensors;
 use crate::sensors::sensor_beacon_pairs::Sensors;
 
-pub fn run1(filename: String, y_loc: i64) -> Result<(), Box<dyn Error>> {
-    let sensor_beacon_pairs = Sensors::new(filename);
-    let num_locs: usize = sensor_beacon_pairs.get_empty_locs(y_loc);
-    println!("num-locs is {}", num_locs);
-    Ok(())
-}
-
-pub fn run2(filename: String, y_min: i64, y_max: i64) -> Result<(), Box<dyn Error>> {
-    println!("given ymin/ymax : {}/{}", y_min, y_max);
-    let sensor_beacon_pairs = Sensors::new(filename);
-    let distress_beacon_loc = sensor_beacon_pairs
-        .get_distress_beacon_loc(y_min, y_max)
-        .expect("could not find distress beacon location!");
-    println!(
-        "distress beacon location is {}/{}",
-        distress_beacon_loc.0, distress_beacon_loc.1
-    );
-
-    let tuning_freq = distress_beacon_loc
-        .0
-        .checked_mul(4000000)
-        .expect("tuning freq overflow!")
-        .checked_add(distress_beacon_loc.1)
-        .expect("tuning freq overflow!");
-    println!("tuning frequency is {}", tuning_freq);
-
-    Ok(())
+/// What is wrong with a line of the sensor report
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // not of the form `Sensor at x=2, y=18: closest beacon is at x=-2, y=15`
+    InvalidReport,
+    TrailingText,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidReport => write!(f, "expected a sensor and beacon location at"),
+            ErrorKind::TrailingText => write!(f, "unexpected text after the beacon location"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<ErrorKind>;
+
+const TUNING_MULTIPLIER: i64 = 4000000;
+
+/// Location of the distress beacon, displayed as its tuning frequency.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DistressBeacon {
+    pub x: i64,
+    pub y: i64,
+    pub tuning_frequency: i64,
+}
+
+impl DistressBeacon {
+    /// Beacon at `x`, `y`, failing when its tuning frequency overflows
+    ///
+    /// ```
+    /// let beacon = day15::DistressBeacon::new(14, 11).unwrap();
+    /// assert_eq!(beacon.to_string(), "56000011");
+    /// assert!(day15::DistressBeacon::new(i64::MAX / 1000, 0).is_err());
+    /// ```
+    pub fn new(x: i64, y: i64) -> Result<DistressBeacon, Box<dyn Error>> {
+        let tuning_frequency = x
+            .checked_mul(TUNING_MULTIPLIER)
+            .ok_or("tuning freq overflow!")?
+            .checked_add(y)
+            .ok_or("tuning freq overflow!")?;
+        Ok(DistressBeacon {
+            x,
+            y,
+            tuning_frequency,
+        })
+    }
+}
+
+impl fmt::Display for DistressBeacon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.tuning_frequency)
+    }
+}
+
+pub struct Day15 {
+    // row to count the locations where a beacon cannot be present
+    pub yloc: i64,
+    // bounds of both coordinates of the distress beacon
+    pub ymin: i64,
+    pub ymax: i64,
+}
+
+impl Default for Day15 {
+    fn default() -> Self {
+        Day15 {
+            yloc: 2000000,
+            ymin: 0,
+            ymax: 4000000,
+        }
+    }
+}
+
+impl Solution for Day15 {
+    type Model = Sensors;
+    type Answer1 = usize;
+    type Answer2 = DistressBeacon;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        Ok(Sensors::parse(input)?)
+    }
+
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        Ok(model.get_empty_locs(self.yloc))
+    }
+
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        let beacon = model
+            .get_distress_beacon_loc(self.ymin, self.ymax)
+            .ok_or("could not find distress beacon location!")?;
+        DistressBeacon::new(beacon.x, beacon.y)
+    }
+
+    fn details2(&self, answer: &Self::Answer2) -> Vec<(&'static str, String)> {
+        vec![("x", answer.x.to_string()), ("y", answer.y.to_string())]
+    }
 }
@@ -1,9 +1,13 @@
-mod parse {
+pub mod parse {
 
+    use crate::ErrorKind;
+    use geometry::Point;
     use nom::bytes::complete::tag;
     use nom::character::complete::i64;
+    use nom::combinator::map;
     use nom::sequence::preceded;
     use nom::sequence::separated_pair;
+    use nom::Finish;
     use nom::IResult;
 
     fn parserx(s: &str) -> IResult<&str, i64> {
@@ -14,153 +18,183 @@ mod parse {
         preceded(tag("y="), i64)(s)
     }
 
-    fn parser_basic(s: &str) -> IResult<&str, (i64, i64)> {
-        separated_pair(parserx, tag(", "), parsery)(s)
+    fn parser_basic(s: &str) -> IResult<&str, Point> {
+        map(separated_pair(parserx, tag(", "), parsery), Point::from)(s)
     }
 
-    fn parser_sensor(s: &str) -> IResult<&str, (i64, i64)> {
+    fn parser_sensor(s: &str) -> IResult<&str, Point> {
         preceded(tag("Sensor at "), parser_basic)(s)
     }
 
-    fn parser_beacon(s: &str) -> IResult<&str, (i64, i64)> {
+    fn parser_beacon(s: &str) -> IResult<&str, Point> {
         preceded(tag("closest beacon is at "), parser_basic)(s)
     }
 
-    pub fn parser(s: &str) -> ((i64, i64), (i64, i64)) {
-        let (_, (sensor_loc, beacon_loc)) =
+    /// Sensor and beacon locations, or what is wrong with the line and the
+    /// offending text
+    ///
+    /// ```
+    /// use day15::sensors::parse::parser;
+    /// use day15::ErrorKind;
+    /// use geometry::Point;
+    ///
+    /// let report = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+    /// assert_eq!(parser(report), Ok((Point::new(2, 18), Point::new(-2, 15))));
+    /// assert_eq!(parser("Sensor at x=2, y=18: beacon at x=-2, y=15"), Err((ErrorKind::InvalidReport, "beacon at x=-2, y=15")));
+    /// assert_eq!(parser(&format!("{}!", report)), Err((ErrorKind::TrailingText, "!")));
+    /// ```
+    pub fn parser(s: &str) -> Result<(Point, Point), (ErrorKind, &str)> {
+        let (remaining, (sensor_loc, beacon_loc)) =
             separated_pair(parser_sensor, tag(": "), parser_beacon)(s)
-                .expect("could not parse sensor/beacon pair!");
+                .finish()
+                .map_err(|e| (ErrorKind::InvalidReport, e.input))?;
+        if !remaining.is_empty() {
+            return Err((ErrorKind::TrailingText, remaining));
+        }
 
-        (sensor_loc, beacon_loc)
+        Ok((sensor_loc, beacon_loc))
     }
 }
 
 pub mod sensor_beacon_pairs {
 
     use crate::sensors::parse::parser;
-    use fs_err as fs;
+    use crate::ParseError;
+    use geometry::Point;
     use hashbrown::HashMap;
-    use indicatif::ProgressBar;
+    use log::debug;
     use rayon::prelude::*;
     use std::collections::HashSet;
+    use std::error::Error;
+    use std::io::BufRead;
 
     pub struct Sensors {
-        pairs: HashMap<(i64, i64), (i64, i64)>,
+        pairs: HashMap<Point, Point>,
     }
 
     impl Sensors {
-        pub fn new(filename: String) -> Sensors {
-            let contents = fs::read_to_string(filename).expect("input file missing!");
+        /// Sensors of the report in the file `filename`
+        ///
+        /// ```
+        /// use day15::sensors::sensor_beacon_pairs::Sensors;
+        ///
+        /// assert!(Sensors::new("missing.txt".to_string()).is_err());
+        /// ```
+        pub fn new(filename: String) -> Result<Sensors, Box<dyn Error>> {
+            Sensors::from_reader(common::input::open(filename)?)
+        }
+
+        /// Sensors of the report read from `reader`
+        ///
+        /// ```
+        /// use day15::sensors::sensor_beacon_pairs::Sensors;
+        ///
+        /// let report = "Sensor at x=8, y=7: closest beacon is at x=2, y=10\n";
+        /// assert_eq!(Sensors::from_reader(report.as_bytes()).unwrap().get_empty_locs(10), 12);
+        /// ```
+        pub fn from_reader<R: BufRead>(reader: R) -> Result<Sensors, Box<dyn Error>> {
+            let contents = common::input::read(reader)?;
+            Ok(Sensors::parse(&contents)?)
+        }
+
+        /// Sensors of the report, one sensor and its closest beacon per line
+        ///
+        /// ```
+        /// use day15::sensors::sensor_beacon_pairs::Sensors;
+        /// use day15::ErrorKind;
+        ///
+        /// assert!(Sensors::parse("Sensor at x=8, y=7: closest beacon is at x=2, y=10\n").is_ok());
+        /// let error = Sensors::parse("\nSensor at x=8, y=7: closest beacon is at x=2, y=ten").err().unwrap();
+        /// assert_eq!((error.kind, error.line, error.column), (ErrorKind::InvalidReport, 2, 49));
+        /// ```
+        pub fn parse(contents: &str) -> Result<Sensors, ParseError> {
             let component_lines = contents.lines().collect::<Vec<_>>();
-            let mut sensor_beacon_pairs: HashMap<(i64, i64), (i64, i64)> = HashMap::new();
+            let mut sensor_beacon_pairs: HashMap<Point, Point> = HashMap::new();
 
-            for line in component_lines {
-                let (sensor_loc, beacon_loc) = parser(line);
+            for (lineidx, line) in component_lines.into_iter().enumerate() {
+                if line.is_empty() {
+                    continue;
+                }
+                let (sensor_loc, beacon_loc) = parser(line)
+                    .map_err(|(kind, text)| ParseError::at(kind, lineidx, line, text))?;
                 sensor_beacon_pairs.insert(sensor_loc, beacon_loc);
             }
 
-            Sensors {
+            Ok(Sensors {
                 pairs: sensor_beacon_pairs,
-            }
+            })
         }
 
-        pub fn get_distress_beacon_loc(&self, ymin: i64, ymax: i64) -> Option<(i64, i64)> {
-            let bar_size: u64 = (ymax + 1 - ymin)
+        /// Location within `ymin..=ymax` on both axes that no sensor covers
+        ///
+        /// ```
+        /// use day15::sensors::sensor_beacon_pairs::Sensors;
+        ///
+        /// let sensors = Sensors::parse("Sensor at x=5, y=5: closest beacon is at x=5, y=7").unwrap();
+        /// assert!(sensors.get_distress_beacon_loc(6, 6).is_none());
+        /// let beacon = sensors.get_distress_beacon_loc(7, 7).unwrap();
+        /// assert_eq!((beacon.x, beacon.y), (7, 7));
+        /// assert!(sensors.get_distress_beacon_loc(7, 6).is_none());
+        /// ```
+        pub fn get_distress_beacon_loc(&self, ymin: i64, ymax: i64) -> Option<Point> {
+            if ymin > ymax {
+                return None;
+            }
+            let size: usize = (ymax - ymin + 1)
                 .try_into()
-                .expect("progress bar size calc. failed");
-            //let bar = ProgressBar::new(bar_size);
+                .expect("search area does not fit in memory!");
 
-            let result = (ymin..=ymax).into_par_iter().find_map_any(|yloc| {
-                let mut locs: Vec<bool> =
-                    vec![true; bar_size.try_into().expect("u64->usize overflow")];
+            (ymin..=ymax).into_par_iter().find_map_any(|yloc| {
+                // locs[idx] is for x = ymin + idx
+                let mut locs: Vec<bool> = vec![true; size];
 
                 for (sensor, beacon) in self.pairs.iter() {
-                    /*
-                    let idx = locs.iter().position(|elem| *elem == true);
-                    if idx.is_none() {
-                        continue;
-                    }*/
-
-                    let dist_x: u64 = u64::try_from((sensor.0).abs_diff(beacon.0)).expect(
-                        "failed to convert distance between sensor/beacon on x-axis to u64",
-                    );
-                    let dist_y: u64 = u64::try_from((sensor.1).abs_diff(beacon.1)).expect(
-                        "failed to convert distance between sensor/beacon on y-axis to u64",
-                    );
-                    let dist = dist_x
-                        .checked_add(dist_y)
-                        .expect("distance computation overflow!");
-                    let test: u64 = u64::try_from((sensor.1).abs_diff(yloc))
-                        .expect("failed to convert distance between sensor/yloc on y-axis to u64");
+                    let dist = sensor.manhattan(*beacon);
+                    let test: u64 = sensor.y.abs_diff(yloc);
 
                     if test < dist {
-                        let x_loc: u64 = dist - test;
-                        let loc1_i64: i64 = (sensor.0)
-                            .checked_sub(
-                                x_loc
-                                    .try_into()
-                                    .expect("i64->u64 overflow when computing x-locs!"),
-                            )
-                            .expect("cannot convert potential loc to i64");
-                        let loc1: usize = if loc1_i64 < 0 {
-                            0_usize
-                        } else {
-                            usize::try_from(loc1_i64)
-                                .expect("i64->usize overflow when computing index")
-                        };
-                        let loc2_i64: i64 = (sensor.0)
-                            .checked_add(
-                                x_loc
-                                    .try_into()
-                                    .expect("i64->u64 overflow when computing x-locs!"),
-                            )
-                            .expect("cannot convert potential loc to i64");
-                        let loc2: usize = if loc2_i64 > ymax {
-                            usize::try_from(ymax).expect("ymax cannot be converted to usize!")
-                        } else {
-                            usize::try_from(loc2_i64)
-                                .expect("loc2_i64 cannot be converted to usize!")
-                        };
-
-                        let slice = &mut locs[loc1..(loc2 + 1)];
+                        let x_loc: i64 = (dist - test)
+                            .try_into()
+                            .expect("i64->u64 overflow when computing x-locs!");
+                        // the covered locations, clamped to the search area
+                        let loc1 = sensor.x.saturating_sub(x_loc).max(ymin);
+                        let loc2 = sensor.x.saturating_add(x_loc).min(ymax);
+                        if loc1 > loc2 {
+                            continue;
+                        }
+                        let slice = &mut locs[(loc1 - ymin) as usize..=(loc2 - ymin) as usize];
                         slice.fill(false);
                     }
                 }
 
-                match locs.iter().position(|elem| *elem == true) {
-                    None => None,
-                    Some(val_idx) => {
-                        println!("val_idx is {}", val_idx);
-                        let tmp: i64 = i64::try_from(val_idx).expect("result does not fit in i64!");
-                        Some((tmp, yloc))
-                    }
-                }
-            });
-
-            result
+                locs.iter().position(|elem| *elem).map(|val_idx| {
+                    debug!("val_idx is {}", val_idx);
+                    Point::new(ymin + val_idx as i64, yloc)
+                })
+            })
         }
 
+        /// Number of locations on row `yloc` where no beacon can be, as they
+        /// are closer to a sensor than its closest beacon
+        ///
+        /// ```
+        /// use day15::sensors::sensor_beacon_pairs::Sensors;
+        ///
+        /// let sensors = Sensors::parse("Sensor at x=8, y=7: closest beacon is at x=2, y=10").unwrap();
+        /// // x from 2 to 14 but the beacon at x=2
+        /// assert_eq!(sensors.get_empty_locs(10), 12);
+        /// assert_eq!(sensors.get_empty_locs(7), 19);
+        /// assert_eq!(sensors.get_empty_locs(17), 0);
+        /// ```
         pub fn get_empty_locs(&self, yloc: i64) -> usize {
             let mut locs: HashSet<i64> = HashSet::new();
 
             for (sensor, beacon) in self.pairs.iter() {
-                println!("-------------------------------");
-                println!(
-                    "procesing sensor/beacon pair: ({},{})/({},{})",
-                    sensor.0, sensor.1, beacon.0, beacon.1
-                );
+                debug!("procesing sensor/beacon pair: {}/{}", sensor, beacon);
 
-                let dist_x: u64 = u64::try_from((sensor.0).abs_diff(beacon.0))
-                    .expect("failed to convert distance between sensor/beacon on x-axis to u64");
-                let dist_y: u64 = u64::try_from((sensor.1).abs_diff(beacon.1))
-                    .expect("failed to convert distance between sensor/beacon on y-axis to u64");
+                let dist = sensor.manhattan(*beacon);
 
-                let dist = dist_x
-                    .checked_add(dist_y)
-                    .expect("distance computation overflow!");
-
-                let test: u64 = u64::try_from((sensor.1).abs_diff(yloc))
-                    .expect("failed to convert distance between sensor/yloc on y-axis to u64");
+                let test: u64 = sensor.y.abs_diff(yloc);
 
                 if test < dist {
                     let x_locs: u64 = dist - test;
@@ -168,18 +202,20 @@ pub mod sensor_beacon_pairs {
                         let x1: i64 = x
                             .try_into()
                             .expect("cannot convert potential x-location to i64");
-                        let loc1 = (sensor.0)
+                        let loc1 = sensor
+                            .x
                             .checked_add(x1)
                             .expect("cannot convert potential loc to i64");
                         let _ = locs.insert(loc1);
-                        let loc2 = (sensor.0)
+                        let loc2 = sensor
+                            .x
                             .checked_sub(x1)
                             .expect("cannot convert potential loc to i64");
                         let _ = locs.insert(loc2);
                         //println!("adding locs {}/{}", loc1, loc2);
                     }
                 } else {
-                    println!(
+                    debug!(
                         "distance to y-loc from sensor {} is larger than distance to beacon {}!",
                         test, dist
                     );
@@ -188,19 +224,17 @@ pub mod sensor_beacon_pairs {
 
             // remove any beacon locations form locs
             for (_, beacon) in self.pairs.iter() {
-                if beacon.1 == yloc {
-                    let is_present = locs.get(&beacon.0);
+                if beacon.y == yloc {
+                    let is_present = locs.get(&beacon.x);
                     match is_present {
                         None => {}
                         Some(_) => {
-                            locs.remove(&beacon.0);
+                            locs.remove(&beacon.x);
                         }
                     }
                 }
             }
 
-            println!("-------------------------------");
-
             /*
             let mut locs_vec = locs.into_iter().collect::<Vec<_>>();
             locs_vec.sort();
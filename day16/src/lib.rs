@@ -1,14 +1,61 @@
+use common::Solution;
 use std::error::Error;
-mod valves;
+use std::fmt;
+pub mod valves;
 use crate::valves::network::Network;
 
-pub fn run1(filename: String) -> Result<(), Box<dyn Error>> {
-    let valve_network =
-        Network::new(filename).expect("could not create valve network from input file!");
-    println!("{}", valve_network);
+/// What is wrong with a line of the valve scan
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // not of the form `Valve BB has flow rate=13; tunnels lead to valves CC, AA`
+    InvalidScan,
+    // valve names are two capital letters
+    InvalidValveName,
+    // there is no valve AA to start from
+    MissingStartValve,
+    // no tunnels lead from the valve to the named valve
+    Unreachable(String),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidScan => write!(f, "expected a valve and its tunnels at"),
+            ErrorKind::InvalidValveName => write!(f, "invalid valve name"),
+            ErrorKind::MissingStartValve => write!(f, "missing starting valve (AA)"),
+            ErrorKind::Unreachable(name) => write!(f, "valve {} cannot be reached from", name),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<ErrorKind>;
+
+pub struct Day16 {
+    // minutes before the volcano erupts
+    pub minutes: u32,
+}
+
+impl Default for Day16 {
+    fn default() -> Self {
+        Day16 { minutes: 30 }
+    }
+}
+
+impl Solution for Day16 {
+    type Model = Network;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        Ok(Network::parse(input)?)
+    }
 
-    let pressure_release = valve_network.pressure_release();
-    println!("max possible pressure release is {}", pressure_release);
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        model.pressure_release(self.minutes)
+    }
 
-    Ok(())
+    // four of the minutes go to teaching an elephant to open valves
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        model.pressure_release_together(self.minutes.saturating_sub(4))
+    }
 }
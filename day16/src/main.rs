@@ -1,36 +1,28 @@
 use clap::Parser;
+use common::cli::DayArgs;
 use std::process;
 
-/// Simple program to greet a person
+/// Day 16: Proboscidea Volcanium
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    /// filename
-    #[arg(short, long)]
-    filename: String,
+    #[command(flatten)]
+    day: DayArgs,
 
-    /// flag for part 1
+    /// minutes before the volcano erupts
     #[arg(long)]
-    part1: bool,
-
-    /// flag for part 2
-    #[arg(long)]
-    part2: bool,
+    minutes: Option<u32>,
 }
 
 fn main() {
     let args = Args::parse();
+    let mut day16 = day16::Day16::default();
+    if let Some(minutes) = args.minutes {
+        day16.minutes = minutes;
+    }
 
-    match (args.part1, args.part2) {
-        (true, false) => {
-            if let Err(e) = day16::run1(args.filename) {
-                eprintln!("Application error: {}", e);
-                process::exit(1);
-            }
-        }
-        (_, _) => {
-            eprintln!("Please choose either part1 or part2!");
-            process::exit(1);
-        }
+    if let Err(e) = args.day.run(16, &day16) {
+        eprintln!("Application error: {}", e);
+        process::exit(1);
     }
 }
@@ -1,23 +1,49 @@
 pub mod valve {
+    use log::warn;
     use std::fmt;
 
-    // convert a string of 2 characters to a u32 number
-    // AA -> 10*100+10 -> 1010
-    // EK -> 14*100+20 -> 1420
+    /// Number standing for a valve name of two letters, the base 36
+    /// digit of the first letter times 100 plus that of the second
+    ///
+    /// ```
+    /// use day16::valves::valve::{convert_name, convert_num};
+    ///
+    /// assert_eq!(convert_name("AA".to_string()), 1010);
+    /// assert_eq!(convert_name("EK".to_string()), 1420);
+    /// assert_eq!(convert_name("ZZ".to_string()), 3535);
+    /// // letters of either case give the same valve
+    /// assert_eq!(convert_name("ek".to_string()), 1420);
+    ///
+    /// // every name comes back from its number
+    /// for first in 'A'..='Z' {
+    ///     for second in 'A'..='Z' {
+    ///         let name = format!("{}{}", first, second);
+    ///         assert_eq!(convert_num(convert_name(name.clone())), name);
+    ///     }
+    /// }
+    /// ```
     pub fn convert_name(name_in: String) -> u32 {
         let mut val: u32 = 0;
         let mut factor: i32 = 100;
         for y in name_in.chars() {
             if factor < 1 {
-                eprintln!("input is greater than 2 chars!");
+                warn!("input is greater than 2 chars!");
             }
-            val = val + u32::try_from(factor).unwrap() * y.to_digit(36).unwrap();
-            factor = factor / 100;
+            val += u32::try_from(factor).unwrap() * y.to_digit(36).unwrap();
+            factor /= 100;
         }
         val
     }
 
-    // convert a u32 number to valve name
+    /// Valve name of a number given by `convert_name`, in capitals
+    ///
+    /// ```
+    /// use day16::valves::valve::convert_num;
+    ///
+    /// assert_eq!(convert_num(1010), "AA");
+    /// assert_eq!(convert_num(1420), "EK");
+    /// assert_eq!(convert_num(3535), "ZZ");
+    /// ```
     pub fn convert_num(num_in: u32) -> String {
         let digi1: u32 = num_in % 100;
         let digi2: u32 = num_in / 100;
@@ -55,10 +81,11 @@ pub mod valve {
     }
 }
 
-mod parse {
+pub mod parse {
 
     use crate::valves::valve::convert_name;
     use crate::valves::valve::Valve;
+    use crate::ErrorKind;
     use nom::branch::alt;
     use nom::bytes::complete::tag;
     use nom::bytes::complete::take_till;
@@ -101,21 +128,36 @@ mod parse {
         )(s)
     }
 
-    pub fn parse_input(s: &str) -> Option<(Valve, Vec<&str>)> {
-        match tuple((parser_valve_first, parser_flow_rate, parser_valves))(s).finish() {
-            Ok((_, (valve, flow_rate, tunnels))) => {
-                let this_valve = Valve {
-                    name: convert_name(valve.to_string()),
-                    flow_rate,
-                };
+    // valve names are two capital letters
+    fn valid_name(name: &str) -> bool {
+        name.len() == 2 && name.chars().all(|x| x.is_ascii_uppercase())
+    }
 
-                return Some((this_valve, tunnels));
-            }
-            Err(e) => {
-                eprintln!("{}", e);
-                return None;
-            }
+    // the valve and the names of the valves its tunnels lead to, or what is
+    // wrong with the line and the offending text
+    pub fn parse_input(s: &str) -> Result<(Valve, Vec<&str>), (ErrorKind, &str)> {
+        let (_, (valve, flow_rate, tunnels)) =
+            tuple((parser_valve_first, parser_flow_rate, parser_valves))(s)
+                .finish()
+                .map_err(|e| (ErrorKind::InvalidScan, e.input))?;
+
+        if !valid_name(valve) {
+            return Err((ErrorKind::InvalidValveName, valve));
+        }
+        let tunnels: Vec<&str> = tunnels
+            .into_iter()
+            .map(|tunnel| tunnel.trim_end_matches(','))
+            .collect();
+        if let Some(tunnel) = tunnels.iter().find(|tunnel| !valid_name(tunnel)) {
+            return Err((ErrorKind::InvalidValveName, tunnel));
         }
+
+        let this_valve = Valve {
+            name: convert_name(valve.to_string()),
+            flow_rate,
+        };
+
+        Ok((this_valve, tunnels))
     }
 }
 
@@ -125,26 +167,18 @@ pub mod network {
     use crate::valves::valve::convert_name;
     use crate::valves::valve::convert_num;
     use crate::valves::valve::Valve;
-    use bit_set::BitSet;
-    use fs_err as fs;
+    use crate::{ErrorKind, ParseError};
+    use common::profile;
     use itertools::Itertools;
+    use log::debug;
     use petgraph::algo::astar;
     use petgraph::dot::Dot;
     use petgraph::graph::NodeIndex;
     use petgraph::Graph;
     use std::collections::{HashMap, HashSet};
+    use std::error::Error;
     use std::fmt;
-
-    #[derive(Eq, Hash, PartialEq)]
-    pub struct Path {
-        elems: BitSet,
-        start_valve: Valve,
-    }
-
-    pub struct Result {
-        next: Valve,
-        cost: u32,
-    }
+    use std::io::BufRead;
 
     pub struct Network {
         graph: Graph<Valve, u32>,
@@ -168,406 +202,232 @@ number of active valves is {}
     }
 
     impl Network {
-        fn get_valve_from_name(&self, name_in: u32) -> Valve {
+        #[allow(dead_code)]
+        fn get_valve_from_name(&self, name_in: u32) -> std::result::Result<Valve, Box<dyn Error>> {
             let res = self.to_open.iter().find(|x| x.name == name_in);
 
             match res {
-                None => panic!("requested valve not in list of valves to open!"),
-                Some(res_valve) => return *res_valve,
+                None => Err(format!(
+                    "requested valve {} not in list of valves to open!",
+                    convert_num(name_in)
+                )
+                .into()),
+                Some(res_valve) => Ok(*res_valve),
             }
         }
 
-        //https://en.wikipedia.org/wiki/Held%E2%80%93Karp_algorithm#Example[3]
-        //
-        // re-interpret the problem of finding the opening order of valves
-        // as a TSP. For example, given valves A,{B,C,D}, with A->B->C->D being
-        // the best order, we have the final pressure release as:
-        // 30 * A + (30 - d'AB) * B + (30 - d'AB - d'BC) * C +
-        // (30 - d'AB -d'BC - d'CD) * D
-        // = 30 * (A + B + C + D) - ( d'AB * (B + C + D)
-        //                            + d'BC * (C + D)
-        //                            + d'CD * D
-        //                          )
-        // where d'AB is just dAB + 1.
-        // and to determine the above, we could have minized the function
-        // min{B, C, D} =  min {
-        // (path1) B + 2C + 3D + dBC * (C + D) + dCD * D
-        // (path2) B + 2D + 3C + dBD * (C + D) + dCD * C
-        // ....
-        pub fn pressure_release(&self) -> u32 {
-            // cache the best travelsal path given a set of valves
-            // to traverse and a starting valve
-            let mut cache: HashMap<Path, Result> = HashMap::new();
-
-            for length in 2..(self.to_open.len() + 1) {
-                println!("solving for problem of length: {}", length);
-
-                let iter_comb = self.to_open.iter().combinations(length);
-
-                iter_comb.for_each(|combination| {
-                    let mut all_valves_in_combination = BitSet::with_capacity(length);
-                    combination.iter().for_each(|valve| {
-                        all_valves_in_combination.insert(valve.to_owned().name as usize);
-                    });
-
-                    combination.iter().for_each(|start_valve| {
-                        let mut traversal_valves = all_valves_in_combination.clone();
-                        traversal_valves.remove(start_valve.name as usize);
-
-                        let path: Path = Path {
-                            elems: traversal_valves.clone(),
-                            start_valve: **start_valve,
-                        };
-
-                        let next = traversal_valves
-                            .clone()
-                            .iter()
-                            .min_by_key(|test_val| {
-                                let test_valve: Valve = *(self
-                                    .to_open
-                                    .iter()
-                                    .find(|&&x| x.name == *test_val as u32)
-                                    .expect("missing valve"));
-
-                                let next_dist = self
-                                    .distances_among_to_open
-                                    .get(&(**start_valve, test_valve))
-                                    .expect("missing distance!");
-
-                                let mut remaining_traversal_valves = traversal_valves.clone();
-                                remaining_traversal_valves.remove(*test_val);
-                                let sum_rel: u32 = traversal_valves.iter().fold(0, |acc, tmp| {
-                                    let val_test: Valve = *(self
-                                        .to_open
-                                        .iter()
-                                        .find(|&&x| x.name == tmp as u32)
-                                        .expect("missing valve"));
-                                    acc + val_test.flow_rate
-                                });
-                                let remaining_path: Path = Path {
-                                    elems: remaining_traversal_valves,
-                                    start_valve: test_valve,
-                                };
-                                if length == 2 {
-                                    (next_dist + 1) * sum_rel
-                                } else {
-                                    let res: &Result =
-                                        cache.get(&(remaining_path)).expect("cache missing path!");
-                                    ((next_dist + 1) * sum_rel) + res.cost
-                                }
-                            })
-                            .expect("cannot find next valve to traverse to!");
-
-                        let next_valve: Valve = *(self
-                            .to_open
-                            .iter()
-                            .find(|&&x| x.name == next as u32)
-                            .expect("missing valve"));
-                        let next_dist = self
-                            .distances_among_to_open
-                            .get(&(**start_valve, next_valve))
-                            .expect("missing distance!");
-
-                        let sum_rel: u32 = traversal_valves.iter().fold(0, |acc, tmp| {
-                            let val_test: Valve = *(self
-                                .to_open
-                                .iter()
-                                .find(|&&x| x.name == tmp as u32)
-                                .expect("missing valve"));
-
-                            acc + val_test.flow_rate
-                        });
-
-                        let mut remaining_traversal_valves = traversal_valves.clone();
-                        remaining_traversal_valves.remove(next_valve.name as usize);
-
-                        let remaining_path: Path = Path {
-                            elems: remaining_traversal_valves,
-                            start_valve: next_valve,
-                        };
-
-                        if length == 2 {
-                            let next_res: Result = Result {
-                                next: next_valve,
-                                cost: ((*next_dist + 1) * sum_rel),
-                            };
-                            cache.insert(path, next_res);
-                        } else {
-                            let res: &Result =
-                                cache.get(&(remaining_path)).expect("cache missing path!");
-                            let next_res: Result = Result {
-                                next: next_valve,
-                                cost: ((*next_dist + 1) * sum_rel) + res.cost,
-                            };
-                            cache.insert(path, next_res);
-                        }
-                    });
-                });
-            }
-
-            let mut valves_to_open = BitSet::with_capacity(self.to_open.len());
-            self.to_open.iter().for_each(|valve| {
-                valves_to_open.insert(valve.to_owned().name as usize);
-            });
-
-            let sum_rel: u32 = valves_to_open.iter().fold(0, |acc, tmp| {
-                let val_test: Valve = *(self
-                    .to_open
-                    .iter()
-                    .find(|&&x| x.name == tmp as u32)
-                    .expect("missing valve"));
-                acc + val_test.flow_rate
-            });
-            println!("sum-rel is {}", sum_rel);
-
-            let next = valves_to_open
-                .clone()
-                .iter()
-                .min_by_key(|test_val| {
-                    let test_valve: Valve = *(self
-                        .to_open
-                        .iter()
-                        .find(|&&x| x.name == *test_val as u32)
-                        .expect("missing valve"));
-                    let next_dist = self
-                        .distances_among_to_open
-                        .get(&(self.start_valve, test_valve))
-                        .expect("missing distance!");
-                    let mut remaining_traversal_valves = valves_to_open.clone();
-                    remaining_traversal_valves.remove(*test_val);
-
-                    let remaining_path: Path = Path {
-                        elems: remaining_traversal_valves.clone(),
-                        start_valve: test_valve,
-                    };
-
-                    let res: &Result = cache.get(&(remaining_path)).expect("cache missing path!");
-
-                    let dist = ((*next_dist + 1) * sum_rel) + res.cost;
-
-                    println!(
-                        "next-dist for test-valve {} is {}, dist is {}, res-cost is {}",
-                        convert_num(test_valve.name),
-                        next_dist,
-                        dist,
-                        res.cost
-                    );
-
-                    dist
-                })
-                .expect("cannot find next valve to traverse to!");
-
-            let next_valve: Valve = *(self
+        // the valves releasing pressure once opened, at most 64 so that a set
+        // of them fits the bits of a u64
+        fn worth_opening(&self) -> std::result::Result<Vec<Valve>, Box<dyn Error>> {
+            let valves: Vec<Valve> = self
                 .to_open
                 .iter()
-                .find(|&&x| x.name == next as u32)
-                .expect("missing valve"));
-
-            let next_dist = self
-                .distances_among_to_open
-                .get(&(self.start_valve, next_valve))
-                .expect("missing distance!");
-
-            let mut press_release = 0;
-            let mut time = 30;
-
-            let mut path: Path = Path {
-                elems: valves_to_open.clone(),
-                start_valve: self.start_valve,
-            };
-
-            print!(
-                "path status: start_valve is {}, time is {}, pressure released is {}, remaining valves are:", 
-                convert_num(self.start_valve.name), time, press_release
-            );
-            for elem in &path.elems {
-                print!(" {},", convert_num(elem as u32));
+                .filter(|valve| valve.flow_rate > 0)
+                .copied()
+                .collect();
+            if valves.len() > 64 {
+                return Err("too many valves worth opening!".into());
             }
-            print!("\n");
-
-            time = time - 1 - next_dist;
+            Ok(valves)
+        }
 
-            press_release = press_release + time * next_valve.flow_rate;
+        // the most pressure released by opening valves for `minutes`, trying
+        // every order that opens them in time
+        pub fn pressure_release(&self, minutes: u32) -> std::result::Result<u32, Box<dyn Error>> {
+            let valves = self.worth_opening()?;
+            let best = profile::phase("orders", || self.pressure_by_set(&valves, minutes));
+            Ok(best.into_values().max().unwrap_or(0))
+        }
 
-            // reset search path
-            path.elems.remove(next);
-            path.start_valve = next_valve;
+        // the most pressure released by each set of valves opened in turn
+        // within `minutes`, the valves given as bits of their index in
+        // `valves`
+        fn pressure_by_set(&self, valves: &[Valve], minutes: u32) -> HashMap<u64, u32> {
+            let distance = |from: Valve, to: Valve| -> u32 {
+                if from == to {
+                    0
+                } else {
+                    self.distances_among_to_open[&(from, to)]
+                }
+            };
 
-            print!(
-                "path status: start_valve is {}, time is {}, pressure released is {}, remaining valves are:", 
-                convert_num(path.start_valve.name), time, press_release
-            );
-            for elem in &path.elems {
-                print!(" {},", convert_num(elem as u32));
+            let mut best: HashMap<u64, u32> = HashMap::new();
+            // valve reached, minutes left, valves opened and pressure released
+            let mut stack: Vec<(Valve, u32, u64, u32)> = vec![(self.start_valve, minutes, 0, 0)];
+            while let Some((at, left, opened, released)) = stack.pop() {
+                let entry = best.entry(opened).or_insert(0);
+                *entry = (*entry).max(released);
+                for (idx, &valve) in valves.iter().enumerate() {
+                    if opened & (1 << idx) != 0 {
+                        continue;
+                    }
+                    // a minute to open the valve once there
+                    let Some(left) = left.checked_sub(distance(at, valve) + 1) else {
+                        continue;
+                    };
+                    stack.push((
+                        valve,
+                        left,
+                        opened | (1 << idx),
+                        released + left * valve.flow_rate,
+                    ));
+                }
             }
-            print!("\n");
-
-            while path.elems.len() > 1 {
-                let res = cache.get(&(path)).expect("missing path from cache!");
-
-                let next_dist = self
-                    .distances_among_to_open
-                    .get(&(path.start_valve, res.next))
-                    .expect("missing distance!");
+            best
+        }
 
-                let test = time.checked_sub(next_dist + 1);
-                match test {
-                    Some(nonnegtime) => {
-                        time = nonnegtime;
-                        press_release = press_release + time * res.next.flow_rate;
+        // the most pressure released by two working side by side for
+        // `minutes`, each opening valves the other does not
+        pub fn pressure_release_together(
+            &self,
+            minutes: u32,
+        ) -> std::result::Result<u32, Box<dyn Error>> {
+            let valves = self.worth_opening()?;
+            let best = profile::phase("orders", || self.pressure_by_set(&valves, minutes));
+            let together = profile::phase("pairs", || {
+                let mut sets: Vec<(u64, u32)> = best.into_iter().collect();
+                sets.sort_by_key(|&(_, released)| std::cmp::Reverse(released));
+                let mut together = 0;
+                for (idx, &(mine, released)) in sets.iter().enumerate() {
+                    // the best pairs with this set are no better
+                    if released * 2 < together {
+                        break;
                     }
-                    None => {
-                        return press_release;
+                    for &(theirs, also) in &sets[idx..] {
+                        if released + also <= together {
+                            break;
+                        }
+                        if mine & theirs == 0 {
+                            together = released + also;
+                        }
                     }
                 }
+                together
+            });
+            Ok(together)
+        }
 
-                path.elems.remove(res.next.name as usize);
-                path.start_valve = res.next;
-
-                print!(
-                "path status: start_valve is {}, time is {}, pressure released is {}, remaining valves are:", 
-                convert_num(path.start_valve.name), time, press_release
-                );
-                for elem in &path.elems {
-                    print!(" {},", convert_num(elem as u32));
-                }
-                print!("\n");
-            }
-
-            assert_eq!(path.elems.len(), 1);
-
-            let bitvec = path.elems.iter().collect_vec();
-            let lastval = bitvec
-                .get(0)
-                .expect("empty bit vec found, when expecting 2 elements!");
-
-            let lastvalve: Valve = *(self
-                .to_open
-                .iter()
-                .find(|&&x| x.name == *lastval as u32)
-                .expect("missing valve"));
-
-            let lastdist = self
-                .distances_among_to_open
-                .get(&(path.start_valve, lastvalve))
-                .expect("missing distance!");
-
-            let test = time.checked_sub(lastdist + 1);
-            match test {
-                Some(nonnegtime) => {
-                    time = nonnegtime;
-                    press_release = press_release + time * lastvalve.flow_rate;
-                }
-                None => {
-                    return press_release;
-                }
-            }
+        pub fn new(filename: String) -> std::result::Result<Network, Box<dyn Error>> {
+            Network::from_reader(common::input::open(filename)?)
+        }
 
-            press_release
+        pub fn from_reader<R: BufRead>(reader: R) -> std::result::Result<Network, Box<dyn Error>> {
+            let contents = common::input::read(reader)?;
+            Ok(Network::parse(&contents)?)
         }
 
-        pub fn new(filename: String) -> Option<Network> {
+        pub fn parse(contents: &str) -> std::result::Result<Network, ParseError> {
             let mut valve_network = Graph::<Valve, u32>::new();
             let mut to_open: HashSet<Valve> = HashSet::new();
             let mut distances_among_to_open: HashMap<(Valve, Valve), u32> = HashMap::new();
             let mut possible_start_valve: Option<Valve> = None;
+            // line of the scan describing each valve, to point at in errors
+            let mut valve_lines: HashMap<u32, (usize, &str)> = HashMap::new();
 
             // traverse through the file, adding nodes with a default weight(flow_rate) of 0
             // until the true flow_rate is found
-            let contents = fs::read_to_string(filename).expect("input file missing!");
-            let component_lines = contents.lines().collect::<Vec<_>>();
-            for line in component_lines {
-                //println!("------------------------");
-                let (this_valve, tunnels) = parse_input(line).expect("could not parse input!");
-                /*println!( "valve:{}; flow_rate:{}", this_valve.name, this_valve.flow_rate);*/
-
-                if this_valve.name == convert_name("AA".to_string()) {
-                    println!("valve to open found! details: {}", this_valve);
-                    to_open.insert(this_valve.clone());
-                    possible_start_valve = Some(this_valve);
-                }
-                if this_valve.flow_rate > 0 {
-                    println!("valve to open found! details: {}", this_valve);
-                    to_open.insert(this_valve.clone());
-                }
-                let mut this_valve_nidx: Option<NodeIndex> = None;
-                match valve_network
-                    .node_indices()
-                    .position(|x| valve_network[x].name == this_valve.name)
-                {
-                    None => this_valve_nidx = Some(valve_network.add_node(this_valve)),
-                    Some(idx) => {
-                        this_valve_nidx = Some(NodeIndex::new(idx));
-                        let weight = valve_network
-                            .node_weight_mut(this_valve_nidx.unwrap())
-                            .expect("missing node!");
-                        *weight = this_valve;
+            // the valves and their tunnels
+            profile::phase("scan", || -> std::result::Result<(), ParseError> {
+                let component_lines = contents.lines().collect::<Vec<_>>();
+                for (lineidx, line) in component_lines.into_iter().enumerate() {
+                    if line.is_empty() {
+                        continue;
                     }
-                }
-
-                tunnels.iter().for_each(|tunnel_valve| {
-                    //println!("adding connection to {};", tunnel_valve);
-                    let this_tunnel_valve = Valve {
-                        name: convert_name(tunnel_valve.to_string().replace(",", "")),
-                        flow_rate: 0,
-                    };
-                    let mut this_tunnel_valve_nidx: Option<NodeIndex> = None;
-                    match valve_network
+                    //println!("------------------------");
+                    let (this_valve, tunnels) = parse_input(line)
+                        .map_err(|(kind, text)| ParseError::at(kind, lineidx, line, text))?;
+                    valve_lines.insert(this_valve.name, (lineidx, line));
+                    /*println!( "valve:{}; flow_rate:{}", this_valve.name, this_valve.flow_rate);*/
+
+                    if this_valve.name == convert_name("AA".to_string()) {
+                        debug!("valve to open found! details: {}", this_valve);
+                        to_open.insert(this_valve);
+                        possible_start_valve = Some(this_valve);
+                    }
+                    if this_valve.flow_rate > 0 {
+                        debug!("valve to open found! details: {}", this_valve);
+                        to_open.insert(this_valve);
+                    }
+                    let this_valve_nidx: NodeIndex = match valve_network
                         .node_indices()
-                        .position(|x| valve_network[x].name == this_tunnel_valve.name)
+                        .position(|x| valve_network[x].name == this_valve.name)
                     {
-                        None => {
-                            this_tunnel_valve_nidx = Some(valve_network.add_node(this_tunnel_valve))
-                        }
+                        None => valve_network.add_node(this_valve),
                         Some(idx) => {
-                            this_tunnel_valve_nidx = Some(NodeIndex::new(idx));
+                            let nidx = NodeIndex::new(idx);
+                            let weight =
+                                valve_network.node_weight_mut(nidx).expect("missing node!");
+                            *weight = this_valve;
+                            nidx
                         }
-                    }
-                    _ = valve_network.add_edge(
-                        this_valve_nidx.expect("current node index is empty!"),
-                        this_tunnel_valve_nidx.expect("tunnel node index is empty"),
-                        1,
-                    );
-                });
-            }
+                    };
 
-            to_open.iter().permutations(2).for_each(|valve_pair| {
-                if distances_among_to_open
-                    .contains_key(&(valve_pair[0].clone(), valve_pair[1].clone()))
-                {
-                    return;
+                    tunnels.iter().for_each(|tunnel_valve| {
+                        //println!("adding connection to {};", tunnel_valve);
+                        let this_tunnel_valve = Valve {
+                            name: convert_name(tunnel_valve.to_string()),
+                            flow_rate: 0,
+                        };
+                        let this_tunnel_valve_nidx: NodeIndex = match valve_network
+                            .node_indices()
+                            .position(|x| valve_network[x].name == this_tunnel_valve.name)
+                        {
+                            None => valve_network.add_node(this_tunnel_valve),
+                            Some(idx) => NodeIndex::new(idx),
+                        };
+                        _ = valve_network.add_edge(this_valve_nidx, this_tunnel_valve_nidx, 1);
+                    });
                 }
+                Ok(())
+            })?;
+
+            // distances between the valves worth opening
+            profile::phase("distances", || -> std::result::Result<(), ParseError> {
+                for valve_pair in to_open.iter().permutations(2) {
+                    if distances_among_to_open.contains_key(&(*valve_pair[0], *valve_pair[1])) {
+                        continue;
+                    }
 
-                let start_idx = valve_network
-                    .node_indices()
-                    .position(|x| valve_network[x].name == valve_pair[0].name)
-                    .expect("end loc not in network!");
-                let end_idx = valve_network
-                    .node_indices()
-                    .position(|x| valve_network[x].name == valve_pair[1].name)
-                    .expect("end loc not in network!");
-
-                let (distance, _) = astar(
-                    &valve_network,
-                    NodeIndex::new(start_idx),
-                    |finish| finish == NodeIndex::new(end_idx),
-                    |_| 1,
-                    |_| 0,
-                )
-                .expect("could not find a path between start and end locations!");
-
-                distances_among_to_open.insert(
-                    (valve_pair[0].to_owned(), valve_pair[1].to_owned()),
-                    distance,
-                );
-            });
+                    let start_idx = valve_network
+                        .node_indices()
+                        .position(|x| valve_network[x].name == valve_pair[0].name)
+                        .expect("end loc not in network!");
+                    let end_idx = valve_network
+                        .node_indices()
+                        .position(|x| valve_network[x].name == valve_pair[1].name)
+                        .expect("end loc not in network!");
+
+                    let (distance, _) = astar(
+                        &valve_network,
+                        NodeIndex::new(start_idx),
+                        |finish| finish == NodeIndex::new(end_idx),
+                        |_| 1,
+                        |_| 0,
+                    )
+                    .ok_or_else(|| {
+                        // to_open only holds valves described by a line of the scan
+                        let (lineidx, line) = valve_lines[&valve_pair[0].name];
+                        let kind = ErrorKind::Unreachable(convert_num(valve_pair[1].name));
+                        ParseError::line(kind, lineidx, line)
+                    })?;
+
+                    distances_among_to_open.insert(
+                        (valve_pair[0].to_owned(), valve_pair[1].to_owned()),
+                        distance,
+                    );
+                }
+                Ok(())
+            })?;
 
-            let start_valve = possible_start_valve.expect("missing starting valve (AA)!");
+            let start_valve = possible_start_valve.ok_or_else(|| {
+                let lineidx = contents.lines().count();
+                ParseError::new(ErrorKind::MissingStartValve, lineidx + 1, 1, "")
+            })?;
 
             // now remove AA from the to_open set
             to_open.remove(&start_valve);
 
-            Some(Network {
+            Ok(Network {
                 graph: valve_network,
                 start_valve,
                 to_open,
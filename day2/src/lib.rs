@@ -1,169 +1,165 @@
+use common::Solution;
 use std::error::Error;
-use std::fs;
-
-//A for Rock, B for Paper, and C for Scissors
-//X for Rock, Y for Paper, and Z for Scissors
-
-enum PlayChoice {
-    Rock,
-    Paper,
-    Scissors,
+use std::fmt;
+pub mod rules;
+use crate::rules::engine::Rules;
+
+/// What is wrong with a round of the strategy guide
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    MissingOpponentChoice,
+    MissingSecondColumn,
+    InvalidOpponentChoice,
+    InvalidSecondColumn,
+    // the second column fits the other part only
+    NotAMove,
+    NotAnOutcome,
 }
 
-enum GameChoice {
-    Win,
-    Draw,
-    Lose,
-}
-
-fn letter_to_play_choice(input: char) -> Option<PlayChoice> {
-    if (input == 'A') | (input == 'X') {
-        return Some(PlayChoice::Rock);
-    } else if (input == 'B') | (input == 'Y') {
-        return Some(PlayChoice::Paper);
-    } else if (input == 'C') | (input == 'Z') {
-        return Some(PlayChoice::Scissors);
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::MissingOpponentChoice => write!(f, "missing opponent choice"),
+            ErrorKind::MissingSecondColumn => write!(f, "missing second column"),
+            ErrorKind::InvalidOpponentChoice => write!(f, "unknown opponent move"),
+            ErrorKind::InvalidSecondColumn => write!(f, "unknown move or outcome"),
+            ErrorKind::NotAMove => write!(f, "not a move to play in part 1"),
+            ErrorKind::NotAnOutcome => write!(f, "not an outcome to reach in part 2"),
+        }
     }
-
-    None
 }
 
-fn letter_to_game_choice(input: char) -> Option<GameChoice> {
-    if input == 'X' {
-        return Some(GameChoice::Lose);
-    } else if input == 'Y' {
-        return Some(GameChoice::Draw);
-    } else if input == 'Z' {
-        return Some(GameChoice::Win);
-    }
-
-    None
+pub type ParseError = common::ParseError<ErrorKind>;
+
+/// A round of the strategy guide, the letters of both columns and where the
+/// second one is, as a letter fitting one part only is an error of that part
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Round {
+    pub opponent: char,
+    pub second: char,
+    // line and column of the second letter, from 1
+    pub line: usize,
+    pub column: usize,
 }
 
-fn score_game(player1: PlayChoice, game: GameChoice) -> i32 {
-    let opponent_choice: i32 = match player1 {
-        PlayChoice::Rock => 1,
-        PlayChoice::Paper => 2,
-        PlayChoice::Scissors => 3,
-    };
-
-    let score_for_winning = 6;
-    let score_for_draw = 3;
-
-    match game {
-        GameChoice::Lose => {
-            if opponent_choice == 1 {
-                return 3;
-            } else if opponent_choice == 2 {
-                return 1;
-            } else {
-                return 2;
-            }
-        }
-        GameChoice::Draw => {
-            if opponent_choice == 1 {
-                return 1 + score_for_draw;
-            } else if opponent_choice == 2 {
-                return 2 + score_for_draw;
-            } else {
-                return 3 + score_for_draw;
-            }
-        }
-        GameChoice::Win => {
-            if opponent_choice == 1 {
-                return 2 + score_for_winning;
-            } else if opponent_choice == 2 {
-                return 3 + score_for_winning;
-            } else {
-                return 1 + score_for_winning;
-            }
-        }
+impl Round {
+    fn error(&self, kind: ErrorKind) -> ParseError {
+        ParseError::new(kind, self.line, self.column, &self.second.to_string())
     }
 }
 
-#[allow(dead_code)]
-fn score_plays(player1: PlayChoice, player2: PlayChoice) -> i32 {
-    let score_played_choice: i32 = match player2 {
-        PlayChoice::Rock => 1,
-        PlayChoice::Paper => 2,
-        PlayChoice::Scissors => 3,
-    };
-
-    let opponent_choice: i32 = match player1 {
-        PlayChoice::Rock => 1,
-        PlayChoice::Paper => 2,
-        PlayChoice::Scissors => 3,
-    };
-
-    let combined_choice = score_played_choice * opponent_choice;
-
-    let score_for_winning = 6;
-    let score_for_draw = 3;
-
-    // check if the match was a draw
-    if (combined_choice == 1) | (combined_choice == 4) | (combined_choice == 9) {
-        return score_played_choice + score_for_draw;
-    }
-
-    // rock + scissors
-    if combined_choice == 3 {
-        if score_played_choice == 1 {
-            return score_played_choice + score_for_winning;
-        }
-    }
-
-    // rock + paper
-    if combined_choice == 2 {
-        if score_played_choice == 2 {
-            return score_played_choice + score_for_winning;
-        }
-    }
-
-    // paper + scissors
-    if combined_choice == 6 {
-        if score_played_choice == 3 {
-            return score_played_choice + score_for_winning;
-        }
-    }
-
-    return score_played_choice;
+#[derive(Default)]
+pub struct Day2 {
+    // the variant the strategy guide is for
+    pub rules: Rules,
 }
 
-pub fn run(filename: String) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let mut component_lines = contents.lines();
+impl Solution for Day2 {
+    type Model = Vec<Round>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-    let mut scores: Vec<i32> = Vec::new();
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        let mut rounds: Vec<Round> = Vec::new();
 
-    loop {
-        if let Some(item) = component_lines.next() {
-            let mut iter = item.split_whitespace();
+        for (lineidx, item) in input.lines().enumerate() {
             // break on empty line, means we have reached the end of the game sequence!
             if item.is_empty() {
                 break;
             }
-            let element = iter.next().unwrap();
-            let elem1: char = element.parse::<char>().unwrap();
-            let element = iter.next().unwrap();
-            let elem2: char = element.parse::<char>().unwrap();
-
-            let play1: PlayChoice = letter_to_play_choice(elem1).expect("invalid input choice!");
-            let game: GameChoice = letter_to_game_choice(elem2).expect("invalid input choice!");
-
-            //let this_round_score: i32 = score_plays(play1, play2);
-            let this_round_score: i32 = score_game(play1, game);
+            let mut iter = item.split_whitespace();
+            let element = iter
+                .next()
+                .ok_or_else(|| ParseError::line(ErrorKind::MissingOpponentChoice, lineidx, item))?;
+            let elem1: char = match element.parse::<char>() {
+                Ok(x) if self.rules.opponent_move(x).is_some() => x,
+                _ => {
+                    return Err(ParseError::at(
+                        ErrorKind::InvalidOpponentChoice,
+                        lineidx,
+                        item,
+                        element,
+                    )
+                    .into())
+                }
+            };
+            let element = iter
+                .next()
+                .ok_or_else(|| ParseError::line(ErrorKind::MissingSecondColumn, lineidx, item))?;
+            let elem2: char = match element.parse::<char>() {
+                Ok(x)
+                    if self.rules.player_move(x).is_some()
+                        || self.rules.outcome_for(x).is_some() =>
+                {
+                    x
+                }
+                _ => {
+                    return Err(ParseError::at(
+                        ErrorKind::InvalidSecondColumn,
+                        lineidx,
+                        item,
+                        element,
+                    )
+                    .into())
+                }
+            };
+
+            // counted as for any error of the line
+            let column = ParseError::at(ErrorKind::NotAMove, lineidx, item, element).column;
+            rounds.push(Round {
+                opponent: elem1,
+                second: elem2,
+                line: lineidx + 1,
+                column,
+            });
+        }
 
-            scores.push(this_round_score);
+        Ok(rounds)
+    }
 
-            continue;
+    // second column is the choice to play
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        let mut score: i32 = 0;
+        for round in model {
+            let opponent = self
+                .rules
+                .opponent_move(round.opponent)
+                .ok_or("unknown opponent move!")?;
+            let player = self
+                .rules
+                .player_move(round.second)
+                .ok_or_else(|| round.error(ErrorKind::NotAMove))?;
+            score = self
+                .rules
+                .score(player, opponent)
+                .and_then(|round| score.checked_add(round))
+                .ok_or("score does not fit in an i32!")?;
         }
-        break;
+        Ok(score)
     }
 
-    println!(
-        "Total score for this set of games is {}",
-        scores.iter().sum::<i32>()
-    );
-
-    Ok(())
+    // second column is the desired outcome of the game
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        let mut score: i32 = 0;
+        for round in model {
+            let opponent = self
+                .rules
+                .opponent_move(round.opponent)
+                .ok_or("unknown opponent move!")?;
+            let outcome = self
+                .rules
+                .outcome_for(round.second)
+                .ok_or_else(|| round.error(ErrorKind::NotAnOutcome))?;
+            let player = self
+                .rules
+                .play_for(opponent, outcome)
+                .ok_or_else(|| format!("no move can {} against {}!", outcome, round.opponent))?;
+            score = self
+                .rules
+                .score(player, opponent)
+                .and_then(|round| score.checked_add(round))
+                .ok_or("score does not fit in an i32!")?;
+        }
+        Ok(score)
+    }
 }
@@ -1,20 +1,34 @@
-use std::env;
+use clap::Parser;
+use common::cli::DayArgs;
+use day2::rules::engine::Rules;
+use day2::Day2;
+use std::error::Error;
 use std::process;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+/// Day 2: Rock Paper Scissors
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    day: DayArgs,
 
-    if args.len() != 2 {
-        eprintln!("Provide only one input, the filename of values!")
-    }
+    /// rules of the game: a file laid out as day2/rules/rps.toml, else rps or rpsls
+    #[arg(long, default_value = "rps")]
+    rules: String,
+}
 
-    let filename_in: String = args[1].clone();
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let day2 = Day2 {
+        rules: Rules::load(&args.rules)?,
+    };
+    args.day.run(2, &day2)
+}
+
+fn main() {
+    let args = Args::parse();
 
-    match day2::run(filename_in) {
-        Err(e) => {
-            eprintln!("Application error: {}", e);
-            process::exit(1);
-        }
-        _ => (),
+    if let Err(e) = run(&args) {
+        eprintln!("Application error: {}", e);
+        process::exit(1);
     }
 }
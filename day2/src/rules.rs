@@ -0,0 +1,282 @@
+pub mod engine {
+    use serde::Deserialize;
+    use std::error::Error;
+    use std::fmt;
+
+    // definitions shipped with the puzzle, see the files for their format
+    const RPS: &str = include_str!("../rules/rps.toml");
+    const RPSLS: &str = include_str!("../rules/rpsls.toml");
+
+    #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+    pub enum Outcome {
+        Lose,
+        Draw,
+        Win,
+    }
+
+    impl fmt::Display for Outcome {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Outcome::Lose => write!(f, "lose"),
+                Outcome::Draw => write!(f, "draw"),
+                Outcome::Win => write!(f, "win"),
+            }
+        }
+    }
+
+    /// A move, the letters standing for it in each column of the strategy
+    /// guide and what playing it scores
+    #[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    pub struct Move {
+        pub name: String,
+        pub opponent: char,
+        pub player: char,
+        pub score: i32,
+        // names of the moves this one beats
+        #[serde(default)]
+        beats: Vec<String>,
+    }
+
+    // letter of the second column standing for the outcome in part 2, and
+    // what reaching the outcome scores
+    #[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct OutcomeRule {
+        letter: char,
+        score: i32,
+    }
+
+    #[derive(Clone, Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Outcomes {
+        lose: OutcomeRule,
+        draw: OutcomeRule,
+        win: OutcomeRule,
+    }
+
+    #[derive(Clone, Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Definition {
+        outcomes: Outcomes,
+        moves: Vec<Move>,
+    }
+
+    /// A game of the rock paper scissors family: its moves, which move
+    /// beats which, and the scores of the moves and of the outcomes
+    ///
+    /// ```
+    /// use day2::rules::engine::{Outcome, Rules};
+    ///
+    /// let rules = Rules::rpsls();
+    /// let spock = rules.player_move('Z').unwrap();
+    /// let rock = rules.opponent_move('A').unwrap();
+    /// assert_eq!(rules.outcome(spock, rock), Outcome::Win);
+    /// assert_eq!(rules.score(spock, rock), Some(5 + 6));
+    /// ```
+    #[derive(Clone, Debug)]
+    pub struct Rules {
+        moves: Vec<Move>,
+        // beats[a][b] if move a beats move b
+        beats: Vec<Vec<bool>>,
+        outcomes: Outcomes,
+    }
+
+    impl Default for Rules {
+        fn default() -> Self {
+            Rules::rps()
+        }
+    }
+
+    impl Rules {
+        /// Rock paper scissors, as in the puzzle
+        pub fn rps() -> Rules {
+            Rules::parse(RPS).expect("rps rules are valid")
+        }
+
+        /// Rock paper scissors lizard spock
+        pub fn rpsls() -> Rules {
+            Rules::parse(RPSLS).expect("rpsls rules are valid")
+        }
+
+        /// The rules defined in that file, or else the built-in rules of
+        /// that name
+        pub fn load(name: &str) -> Result<Rules, Box<dyn Error>> {
+            // a file named as the built-in rules is not shadowed by them
+            if std::path::Path::new(name).exists() {
+                return Rules::parse(&common::input::read_to_string(name)?);
+            }
+            match name {
+                "rps" => Ok(Rules::rps()),
+                "rpsls" => Ok(Rules::rpsls()),
+                filename => Rules::parse(&common::input::read_to_string(filename)?),
+            }
+        }
+
+        /// Rules from a definition in toml, laid out as `rules/rps.toml`
+        ///
+        /// ```
+        /// use day2::rules::engine::Rules;
+        ///
+        /// let definition = r#"
+        ///     [outcomes]
+        ///     lose = { letter = "L", score = 0 }
+        ///     draw = { letter = "D", score = 1 }
+        ///     win = { letter = "W", score = 2 }
+        ///
+        ///     [[moves]]
+        ///     name = "odd"
+        ///     opponent = "A"
+        ///     player = "X"
+        ///     score = 1
+        ///     beats = ["even"]
+        ///
+        ///     [[moves]]
+        ///     name = "even"
+        ///     opponent = "B"
+        ///     player = "Y"
+        ///     score = 2
+        /// "#;
+        /// let rules = Rules::parse(definition).unwrap();
+        /// assert_eq!(rules.moves().len(), 2);
+        ///
+        /// // every two moves are decided one way
+        /// let undecided = definition.replace(r#"beats = ["even"]"#, "");
+        /// assert!(Rules::parse(&undecided).is_err());
+        /// ```
+        pub fn parse(definition: &str) -> Result<Rules, Box<dyn Error>> {
+            let definition: Definition = toml::from_str(definition)?;
+            let Definition { outcomes, moves } = definition;
+            if moves.is_empty() {
+                return Err("rules need at least one move!".into());
+            }
+
+            let outcome_letters = [
+                outcomes.lose.letter,
+                outcomes.draw.letter,
+                outcomes.win.letter,
+            ];
+            let columns: [(&str, Vec<char>); 3] = [
+                ("opponent", moves.iter().map(|x| x.opponent).collect()),
+                ("player", moves.iter().map(|x| x.player).collect()),
+                ("outcome", outcome_letters.to_vec()),
+            ];
+            for (column, letters) in &columns {
+                for (idx, letter) in letters.iter().enumerate() {
+                    // the columns of the strategy guide are split on whitespace
+                    if letter.is_whitespace() {
+                        return Err(format!("{} letter cannot be blank!", column).into());
+                    }
+                    if letters[..idx].contains(letter) {
+                        return Err(format!("{} letter {} is used twice!", column, letter).into());
+                    }
+                }
+            }
+
+            let index = |name: &str| moves.iter().position(|x| x.name == name);
+            let mut beats = vec![vec![false; moves.len()]; moves.len()];
+            for (idx, played) in moves.iter().enumerate() {
+                if index(&played.name) != Some(idx) {
+                    return Err(format!("move {} is defined twice!", played.name).into());
+                }
+                for beaten in &played.beats {
+                    let beaten = index(beaten)
+                        .ok_or_else(|| format!("{} beats unknown move {}!", played.name, beaten))?;
+                    if beaten == idx {
+                        return Err(format!("{} cannot beat itself!", played.name).into());
+                    }
+                    beats[idx][beaten] = true;
+                }
+            }
+            for a in 0..moves.len() {
+                for b in a + 1..moves.len() {
+                    let (x, y) = (&moves[a].name, &moves[b].name);
+                    match (beats[a][b], beats[b][a]) {
+                        (true, true) => {
+                            return Err(format!("{} and {} beat each other!", x, y).into())
+                        }
+                        (false, false) => {
+                            return Err(format!("neither {} nor {} beats the other!", x, y).into())
+                        }
+                        _ => (),
+                    }
+                }
+            }
+
+            Ok(Rules {
+                moves,
+                beats,
+                outcomes,
+            })
+        }
+
+        pub fn moves(&self) -> &[Move] {
+            &self.moves
+        }
+
+        /// Move the opponent plays for a letter of the first column
+        pub fn opponent_move(&self, letter: char) -> Option<usize> {
+            self.moves.iter().position(|x| x.opponent == letter)
+        }
+
+        /// Move to play for a letter of the second column in part 1
+        pub fn player_move(&self, letter: char) -> Option<usize> {
+            self.moves.iter().position(|x| x.player == letter)
+        }
+
+        /// Outcome to reach for a letter of the second column in part 2
+        pub fn outcome_for(&self, letter: char) -> Option<Outcome> {
+            [Outcome::Lose, Outcome::Draw, Outcome::Win]
+                .into_iter()
+                .find(|&outcome| self.outcome_rule(outcome).letter == letter)
+        }
+
+        fn outcome_rule(&self, outcome: Outcome) -> OutcomeRule {
+            match outcome {
+                Outcome::Lose => self.outcomes.lose,
+                Outcome::Draw => self.outcomes.draw,
+                Outcome::Win => self.outcomes.win,
+            }
+        }
+
+        /// Outcome for the player of a round
+        pub fn outcome(&self, player: usize, opponent: usize) -> Outcome {
+            if self.beats[player][opponent] {
+                Outcome::Win
+            } else if self.beats[opponent][player] {
+                Outcome::Lose
+            } else {
+                Outcome::Draw
+            }
+        }
+
+        /// Score of the player for a round, that of its move and of the
+        /// outcome, none if it does not fit in an i32
+        pub fn score(&self, player: usize, opponent: usize) -> Option<i32> {
+            let outcome = self.outcome(player, opponent);
+            self.moves[player]
+                .score
+                .checked_add(self.outcome_rule(outcome).score)
+        }
+
+        /// Move reaching the outcome against the opponent, the best scoring
+        /// one if several do
+        ///
+        /// ```
+        /// use day2::rules::engine::{Outcome, Rules};
+        ///
+        /// let rules = Rules::rpsls();
+        /// let rock = rules.opponent_move('A').unwrap();
+        /// // both paper and spock beat rock
+        /// let spock = rules.play_for(rock, Outcome::Win).unwrap();
+        /// assert_eq!(rules.moves()[spock].name, "spock");
+        /// let rock = rules.play_for(rock, Outcome::Draw).unwrap();
+        /// assert_eq!(rules.moves()[rock].name, "rock");
+        /// ```
+        pub fn play_for(&self, opponent: usize, outcome: Outcome) -> Option<usize> {
+            (0..self.moves.len())
+                .filter(|&player| self.outcome(player, opponent) == outcome)
+                .max_by_key(|&player| (self.moves[player].score, std::cmp::Reverse(player)))
+        }
+    }
+}
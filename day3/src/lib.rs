@@ -1,80 +1,119 @@
-use fs_err as fs;
+use common::Solution;
 use itertools::Itertools;
 use std::error::Error;
+use std::fmt;
 
-pub fn run_part2(filename: String) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
-    let component_groups = component_lines.chunks_exact(3);
+/// What is wrong with the contents of a rucksack
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // items are the letters a-z and A-Z
+    InvalidItem,
+    // both compartments hold the same number of items
+    OddItemCount,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidItem => write!(f, "item is not a letter"),
+            ErrorKind::OddItemCount => write!(f, "odd number of items in rucksack"),
+        }
+    }
+}
 
-    let mut results: Vec<u32> = Vec::new();
+pub type ParseError = common::ParseError<ErrorKind>;
 
-    for group in component_groups {
-        if group.len() != 3 {
-            break;
+pub struct Day3;
+
+impl Solution for Day3 {
+    // contents of each rucksack
+    type Model = Vec<String>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        let mut rucksacks: Vec<String> = Vec::new();
+        for (lineidx, item) in input.lines().enumerate() {
+            if item.is_empty() {
+                break;
+            }
+            if let Some((idx, _)) = item.char_indices().find(|(_, x)| !x.is_ascii_alphabetic()) {
+                let end = idx + item[idx..].chars().next().map_or(0, |x| x.len_utf8());
+                return Err(
+                    ParseError::at(ErrorKind::InvalidItem, lineidx, item, &item[idx..end]).into(),
+                );
+            }
+            if item.len() % 2 != 0 {
+                return Err(ParseError::line(ErrorKind::OddItemCount, lineidx, item).into());
+            }
+            rucksacks.push(item.to_string());
         }
+        Ok(rucksacks)
+    }
+
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        let mut results: Vec<u32> = Vec::new();
+
+        for item in model {
+            let mut chars: Vec<char> = item.chars().collect();
+            let length = chars.len() / 2;
+            let (left, right) = chars.split_at_mut(length);
 
-        let elf0: Vec<_> = group[0].chars().collect();
-
-        let common: Vec<_> = elf0
-            .iter()
-            .map(|x| {
-                let mut matchval: u32 = 0;
-                for (y1, y2) in group[1].chars().cartesian_product(group[2].chars()) {
-                    if (*x == y1) && (y1 == y2) {
-                        matchval = x.to_digit(36).unwrap() - 9;
-                        if x.is_ascii_uppercase() {
-                            matchval = matchval + 26
+            let common: Vec<_> = left
+                .iter()
+                .map(|x| {
+                    let mut matchval: u32 = 0;
+                    for y in right.iter() {
+                        if x == y {
+                            matchval = x.to_digit(36).unwrap() - 9;
+                            if x.is_ascii_uppercase() {
+                                matchval += 26
+                            }
                         }
                     }
-                }
-                matchval
-            })
-            .filter(|x| *x > 0)
-            .collect::<Vec<_>>();
+                    matchval
+                })
+                .filter(|x| *x > 0)
+                .collect::<Vec<_>>();
 
-        results.push(common[0]);
+            results.push(
+                *common
+                    .first()
+                    .ok_or("no item common to both compartments!")?,
+            );
+        }
+
+        Ok(results.iter().sum::<u32>())
     }
 
-    println!("result sum is {}", results.iter().sum::<u32>());
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        let component_groups = model.chunks_exact(3);
 
-    Ok(())
-}
-#[allow(unused)]
-pub fn run_part1(filename: String) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
-    let mut results: Vec<u32> = Vec::new();
-
-    for item in component_lines {
-        if item.is_empty() {
-            break;
-        }
-        let mut chars: Vec<char> = item.chars().collect();
-        let length = chars.len() / 2;
-        let (left, right) = chars.split_at_mut(length);
-
-        let common: Vec<_> = left
-            .iter()
-            .map(|x| {
-                let mut matchval: u32 = 0;
-                for y in right.iter() {
-                    if x == y {
-                        matchval = x.to_digit(36).unwrap() - 9;
-                        if x.is_ascii_uppercase() {
-                            matchval = matchval + 26
+        let mut results: Vec<u32> = Vec::new();
+
+        for group in component_groups {
+            let elf0: Vec<_> = group[0].chars().collect();
+
+            let common: Vec<_> = elf0
+                .iter()
+                .map(|x| {
+                    let mut matchval: u32 = 0;
+                    for (y1, y2) in group[1].chars().cartesian_product(group[2].chars()) {
+                        if (*x == y1) && (y1 == y2) {
+                            matchval = x.to_digit(36).unwrap() - 9;
+                            if x.is_ascii_uppercase() {
+                                matchval += 26
+                            }
                         }
                     }
-                }
-                matchval
-            })
-            .filter(|x| *x > 0)
-            .collect::<Vec<_>>();
-
-        results.push(common[0]);
-    }
+                    matchval
+                })
+                .filter(|x| *x > 0)
+                .collect::<Vec<_>>();
 
-    println!("result sum is {}", results.iter().sum::<u32>());
+            results.push(*common.first().ok_or("no badge common to the group!")?);
+        }
 
-    Ok(())
+        Ok(results.iter().sum::<u32>())
+    }
 }
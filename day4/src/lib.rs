@@ -1,62 +1,80 @@
-use fs_err as fs;
+use common::Solution;
 use nom::bytes::complete::tag;
 use nom::character::complete::u32;
 use nom::sequence::separated_pair;
+use nom::Finish;
 use nom::IResult;
 use std::collections::HashSet;
 use std::error::Error;
+use std::fmt;
 
-fn parse_integer_pair(input: &str) -> IResult<&str, (u32, u32)> {
+type Assignment = (u32, u32);
+
+fn parse_integer_pair(input: &str) -> IResult<&str, Assignment> {
     separated_pair(u32, tag("-"), u32)(input)
 }
 
-fn parse(input: &str) -> IResult<&str, ((u32, u32), (u32, u32))> {
+fn parse(input: &str) -> IResult<&str, (Assignment, Assignment)> {
     separated_pair(parse_integer_pair, tag(","), parse_integer_pair)(input)
 }
 
-pub fn run_part2(filename: String) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
-
-    let result: u32 = component_lines
-        .iter()
-        .map(|item| {
-            if item.is_empty() {
-                0
-            } else {
-                let (_, parsed) = parse(item).expect("parsing error, please check input");
-                let (range1_start, range1_end) = parsed.0;
-                let (range2_start, range2_end) = parsed.1;
+/// What is wrong with the assignments of a pair of elves
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // not of the form `2-4,6-8`
+    InvalidAssignment,
+    TrailingText,
+    // a range ending before it starts
+    ReversedRange,
+}
 
-                let set1: HashSet<u32> =
-                    HashSet::from_iter((range1_start..=range1_end).collect::<Vec<_>>());
-                let set2: HashSet<u32> =
-                    HashSet::from_iter((range2_start..=range2_end).collect::<Vec<_>>());
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidAssignment => write!(f, "expected a pair of ranges like 2-4,6-8 at"),
+            ErrorKind::TrailingText => write!(f, "unexpected text after the pair of ranges"),
+            ErrorKind::ReversedRange => write!(f, "range ends before it starts"),
+        }
+    }
+}
 
-                if set1.intersection(&set2).count() > 0 {
-                    1
-                } else {
-                    0
-                }
-            }
-        })
-        .sum();
+pub type ParseError = common::ParseError<ErrorKind>;
 
-    println!("result is {}", result);
+pub struct Day4;
 
-    Ok(())
-}
+impl Solution for Day4 {
+    // section assignments of each pair of elves
+    type Model = Vec<(Assignment, Assignment)>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-pub fn run_part1(filename: String) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
-    let result: u32 = component_lines
-        .iter()
-        .map(|item| {
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        let mut pairs: Vec<(Assignment, Assignment)> = Vec::new();
+        for (lineidx, item) in input.lines().enumerate() {
             if item.is_empty() {
-                0
-            } else {
-                let (_, parsed) = parse(item).unwrap();
+                continue;
+            }
+            let (remaining, parsed) = parse(item).finish().map_err(|e| {
+                ParseError::at(ErrorKind::InvalidAssignment, lineidx, item, e.input)
+            })?;
+            if !remaining.is_empty() {
+                return Err(
+                    ParseError::at(ErrorKind::TrailingText, lineidx, item, remaining).into(),
+                );
+            }
+            let ((start1, end1), (start2, end2)) = parsed;
+            if start1 > end1 || start2 > end2 {
+                return Err(ParseError::line(ErrorKind::ReversedRange, lineidx, item).into());
+            }
+            pairs.push(parsed);
+        }
+        Ok(pairs)
+    }
+
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        let result: u32 = model
+            .iter()
+            .map(|parsed| {
                 let (range1_start, range1_end) = parsed.0;
                 let (range2_start, range2_end) = parsed.1;
 
@@ -74,18 +92,37 @@ pub fn run_part1(filename: String) -> Result<(), Box<dyn Error>> {
                     } else {
                         0
                     }
+                } else if set2.is_subset(&set1) {
+                    1
                 } else {
-                    if set2.is_subset(&set1) {
-                        1
-                    } else {
-                        0
-                    }
+                    0
                 }
-            }
-        })
-        .sum();
+            })
+            .sum();
+
+        Ok(result)
+    }
+
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        let result: u32 = model
+            .iter()
+            .map(|parsed| {
+                let (range1_start, range1_end) = parsed.0;
+                let (range2_start, range2_end) = parsed.1;
 
-    println!("result is {}", result);
+                let set1: HashSet<u32> =
+                    HashSet::from_iter((range1_start..=range1_end).collect::<Vec<_>>());
+                let set2: HashSet<u32> =
+                    HashSet::from_iter((range2_start..=range2_end).collect::<Vec<_>>());
+
+                if set1.intersection(&set2).count() > 0 {
+                    1
+                } else {
+                    0
+                }
+            })
+            .sum();
 
-    Ok(())
+        Ok(result)
+    }
 }
@@ -1,119 +1,231 @@
-use fs_err as fs;
+use common::Solution;
 use itertools::Itertools;
 use std::collections::HashMap;
 use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
 
-pub fn move_stacks_part_2(
-    mut container_ship: HashMap<u32, Vec<char>>,
-    filename: String,
-) -> Result<(), Box<dyn Error>> {
-    //dbg!(container_ship);
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
-    for line in component_lines {
-        if line.is_empty() {
-            // account for any extra newline at end
-            break;
-        } else {
-            let input_vec: Vec<_> = line.split_whitespace().collect();
-            let move_quantity: u32 = input_vec[1]
-                .parse::<u32>()
-                .expect("Erronous input instruction!");
-            let src_stack_idx: u32 = input_vec[3]
-                .parse::<u32>()
-                .expect("Erronous input instruction!");
-            let dst_stack_idx: u32 = input_vec[5]
-                .parse::<u32>()
-                .expect("Erronous input instruction!");
+/// What is wrong with the drawing of the stacks or the list of moves
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // no empty line between the drawing and the moves
+    MissingMoves,
+    InvalidStackNumber,
+    // not of the form `move 1 from 2 to 1`
+    InvalidMove,
+    InvalidNumber,
+}
 
-            let src_stack = container_ship
-                .get_mut(&(src_stack_idx as u32))
-                .expect("no input stack at source!");
-            let mut moved_items: Vec<_> = src_stack
-                .drain(src_stack.len() - move_quantity as usize..)
-                .collect();
-            let dst_stack = container_ship
-                .get_mut(&(dst_stack_idx as u32))
-                .expect("no input stack at destination!");
-            dst_stack.append(&mut moved_items);
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::MissingMoves => {
+                write!(f, "expected an empty line between the stacks and the moves")
+            }
+            ErrorKind::InvalidStackNumber => write!(f, "invalid stack number"),
+            ErrorKind::InvalidMove => write!(f, "expected a move like `move 1 from 2 to 1`"),
+            ErrorKind::InvalidNumber => write!(f, "invalid number"),
         }
     }
+}
+
+pub type ParseError = common::ParseError<ErrorKind>;
 
+#[derive(Copy, Clone, Debug)]
+pub struct Move {
+    pub quantity: u32,
+    pub src: u32,
+    pub dst: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct Ship {
+    pub stacks: HashMap<u32, Vec<char>>,
+    pub moves: Vec<Move>,
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Model = Ship;
+    type Answer1 = String;
+    type Answer2 = String;
+
+    // the starting configuration and the moves are separated by an empty line
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        let (configuration, moves) = input.split_once("\n\n").ok_or_else(|| {
+            ParseError::new(ErrorKind::MissingMoves, input.lines().count() + 1, 1, "")
+        })?;
+        // the moves start after the drawing and the empty line
+        let first_move = configuration.lines().count() + 1;
+
+        Ok(Ship {
+            stacks: parse_configuration(configuration)?,
+            moves: parse_moves_from(moves, first_move)?,
+        })
+    }
+
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        let mut container_ship = model.stacks.clone();
+        apply_moves_part_1(&mut container_ship, &model.moves)?;
+        top_of_stacks(&container_ship)
+    }
+
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        let mut container_ship = model.stacks.clone();
+        apply_moves_part_2(&mut container_ship, &model.moves)?;
+        top_of_stacks(&container_ship)
+    }
+}
+
+fn top_of_stacks(container_ship: &HashMap<u32, Vec<char>>) -> Result<String, Box<dyn Error>> {
+    let mut tops = String::with_capacity(container_ship.len());
     for key in container_ship.keys().sorted() {
-        let stack = container_ship.get(key).expect("no stack at this key!");
-        println!("{}", stack.last().expect("empty stack!"));
+        let stack = container_ship.get(key).ok_or("no stack at this key!")?;
+        tops.push(*stack.last().ok_or("empty stack!")?);
     }
+    Ok(tops)
+}
 
-    Ok(())
+/// Moves like `move 1 from 2 to 1`, one per line up to the first empty line
+///
+/// ```
+/// let moves = day5::parse_moves("move 1 from 2 to 1\nmove 3 from 1 to 3\n").unwrap();
+/// assert_eq!(moves.len(), 2);
+/// assert_eq!((moves[1].quantity, moves[1].src, moves[1].dst), (3, 1, 3));
+/// assert!(day5::parse_moves("move 1 from 2\n").is_err());
+/// ```
+pub fn parse_moves(contents: &str) -> Result<Vec<Move>, Box<dyn Error>> {
+    parse_moves_from(contents, 0)
 }
 
-pub fn move_stacks_part_1(
-    mut container_ship: HashMap<u32, Vec<char>>,
-    filename: String,
-) -> Result<(), Box<dyn Error>> {
-    //dbg!(container_ship);
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
-    for line in component_lines {
+// contents start at line index first_lineidx of the puzzle input
+fn parse_moves_from(contents: &str, first_lineidx: usize) -> Result<Vec<Move>, Box<dyn Error>> {
+    let mut moves: Vec<Move> = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        let lineidx = first_lineidx + idx;
         if line.is_empty() {
             // account for any extra newline at end
             break;
         } else {
             let input_vec: Vec<_> = line.split_whitespace().collect();
-            let move_quantity: u32 = input_vec[1]
-                .parse::<u32>()
-                .expect("Erronous input instruction!");
-            let src_stack_idx: u32 = input_vec[3]
-                .parse::<u32>()
-                .expect("Erronous input instruction!");
-            let dst_stack_idx: u32 = input_vec[5]
-                .parse::<u32>()
-                .expect("Erronous input instruction!");
-
-            for _ in 0..move_quantity {
-                let src_stack = container_ship
-                    .get_mut(&(src_stack_idx as u32))
-                    .expect("no input stack at source!");
-                let elem = src_stack
-                    .last()
-                    .expect("erronous instruction, source stack has too few elements!")
-                    .clone();
-                src_stack.pop();
-                let dst_stack = container_ship
-                    .get_mut(&(dst_stack_idx as u32))
-                    .expect("no input stack at destination!");
-                dst_stack.push(elem);
-                //println!("moved elem : {}", elem);
+            if input_vec.len() != 6
+                || input_vec[0] != "move"
+                || input_vec[2] != "from"
+                || input_vec[4] != "to"
+            {
+                return Err(ParseError::line(ErrorKind::InvalidMove, lineidx, line).into());
             }
+            let number = |elem: &str| {
+                elem.parse::<u32>()
+                    .map_err(|_| ParseError::at(ErrorKind::InvalidNumber, lineidx, line, elem))
+            };
+            moves.push(Move {
+                quantity: number(input_vec[1])?,
+                src: number(input_vec[3])?,
+                dst: number(input_vec[5])?,
+            });
         }
     }
+    Ok(moves)
+}
 
-    for key in container_ship.keys().sorted() {
-        let stack = container_ship.get(key).expect("no stack at this key!");
-        println!("{}", stack.last().expect("empty stack!"));
+/// Moves the crates of each move all at once, keeping their order
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// let mut stacks = HashMap::from([(1, vec!['A', 'B', 'C']), (2, vec![])]);
+/// let moves = day5::parse_moves("move 2 from 1 to 2").unwrap();
+/// day5::apply_moves_part_2(&mut stacks, &moves).unwrap();
+/// assert_eq!(stacks[&2], ['B', 'C']);
+/// // not enough crates to move
+/// assert!(day5::apply_moves_part_2(&mut stacks, &moves.repeat(2)).is_err());
+/// ```
+pub fn apply_moves_part_2(
+    container_ship: &mut HashMap<u32, Vec<char>>,
+    moves: &[Move],
+) -> Result<(), Box<dyn Error>> {
+    for instruction in moves {
+        let src_stack = container_ship
+            .get_mut(&instruction.src)
+            .ok_or("no input stack at source!")?;
+        let remaining = src_stack
+            .len()
+            .checked_sub(instruction.quantity as usize)
+            .ok_or("erronous instruction, source stack has too few elements!")?;
+        let mut moved_items: Vec<_> = src_stack.drain(remaining..).collect();
+        let dst_stack = container_ship
+            .get_mut(&instruction.dst)
+            .ok_or("no input stack at destination!")?;
+        dst_stack.append(&mut moved_items);
     }
 
     Ok(())
 }
 
-pub fn starting_configuration(filename: String) -> Result<HashMap<u32, Vec<char>>, Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
+/// Moves the crates of each move one at a time, reversing their order
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// let mut stacks = HashMap::from([(1, vec!['A', 'B', 'C']), (2, vec![])]);
+/// let moves = day5::parse_moves("move 2 from 1 to 2").unwrap();
+/// day5::apply_moves_part_1(&mut stacks, &moves).unwrap();
+/// assert_eq!(stacks[&2], ['C', 'B']);
+/// // no stack 3 to move to
+/// assert!(day5::apply_moves_part_1(&mut stacks, &day5::parse_moves("move 1 from 1 to 3").unwrap()).is_err());
+/// ```
+pub fn apply_moves_part_1(
+    container_ship: &mut HashMap<u32, Vec<char>>,
+    moves: &[Move],
+) -> Result<(), Box<dyn Error>> {
+    for instruction in moves {
+        for _ in 0..instruction.quantity {
+            let src_stack = container_ship
+                .get_mut(&instruction.src)
+                .ok_or("no input stack at source!")?;
+            let elem = src_stack
+                .pop()
+                .ok_or("erronous instruction, source stack has too few elements!")?;
+            let dst_stack = container_ship
+                .get_mut(&instruction.dst)
+                .ok_or("no input stack at destination!")?;
+            dst_stack.push(elem);
+            //println!("moved elem : {}", elem);
+        }
+    }
+
+    Ok(())
+}
+
+/// Stacks of the drawing by their number, bottom crate first
+///
+/// ```
+/// let stacks = day5::parse_configuration("    [D]\n[N] [C]\n 1   2\n").unwrap();
+/// assert_eq!(stacks[&1], ['N']);
+/// assert_eq!(stacks[&2], ['C', 'D']);
+/// assert!(day5::parse_configuration("[N]\n x\n").is_err());
+/// ```
+pub fn parse_configuration(contents: &str) -> Result<HashMap<u32, Vec<char>>, Box<dyn Error>> {
     let component_lines = contents.lines().collect::<Vec<_>>();
 
     let mut indices: Vec<u32> = Vec::new();
     let mut container_ship: HashMap<u32, Vec<char>> = HashMap::new();
 
     let mut counted_number_stacks: bool = false;
-    for line in component_lines.iter().rev() {
+    for (lineidx, line) in component_lines.iter().enumerate().rev() {
         if line.is_empty() {
             // account for any extra newline at end
             continue;
         } else {
             let input: Vec<_> = line.split_whitespace().collect::<Vec<_>>();
             // count the number of stacks first
-            if counted_number_stacks == false {
+            if !counted_number_stacks {
                 for elem in input {
-                    let idx: u32 = elem.parse::<u32>().expect("expected indices on last line!");
+                    let idx: u32 = elem.parse::<u32>().map_err(|_| {
+                        ParseError::at(ErrorKind::InvalidStackNumber, lineidx, line, elem)
+                    })?;
                     indices.push(idx);
                 }
                 counted_number_stacks = true;
@@ -125,7 +237,7 @@ pub fn starting_configuration(filename: String) -> Result<HashMap<u32, Vec<char>
                 let iter = (line.chars().count() + 1) / 4;
 
                 for (elem, i) in line.chars().skip(1).step_by(4).zip(1..=iter) {
-                    if !(elem == ' ') {
+                    if elem != ' ' {
                         if let Some(stack) = container_ship.get_mut(&(i as u32)) {
                             (*stack).push(elem);
                         }
@@ -137,3 +249,24 @@ pub fn starting_configuration(filename: String) -> Result<HashMap<u32, Vec<char>
 
     Ok(container_ship)
 }
+
+/// Stacks of the drawing in the file `filename`
+///
+/// ```
+/// assert!(day5::starting_configuration("missing.txt".to_string()).is_err());
+/// ```
+pub fn starting_configuration(filename: String) -> Result<HashMap<u32, Vec<char>>, Box<dyn Error>> {
+    read_configuration(common::input::open(filename)?)
+}
+
+/// Stacks of the drawing read from `reader`
+///
+/// ```
+/// let stacks = day5::read_configuration("[Z] [M]\n 1   2\n".as_bytes()).unwrap();
+/// assert_eq!((stacks[&1][0], stacks[&2][0]), ('Z', 'M'));
+/// ```
+pub fn read_configuration<R: BufRead>(
+    reader: R,
+) -> Result<HashMap<u32, Vec<char>>, Box<dyn Error>> {
+    parse_configuration(&common::input::read(reader)?)
+}
@@ -1,7 +1,24 @@
-use fs_err as fs;
+use common::Solution;
 use itertools::Itertools;
 use std::collections::VecDeque;
 use std::error::Error;
+use std::fmt;
+
+/// What is wrong with the datastream
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    EmptyDatastream,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::EmptyDatastream => write!(f, "empty datastream"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<ErrorKind>;
 
 fn check_marker(length: usize, buffer: &VecDeque<char>) -> bool {
     // check that we are given the number of chars == marker size
@@ -25,47 +42,78 @@ fn check_marker(length: usize, buffer: &VecDeque<char>) -> bool {
     false
 }
 
-pub fn run(length: usize, filename: String) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines();
-
-    for line in component_lines {
-        if line.is_empty() {
-            break;
-        } else {
-            let mut buf: VecDeque<char> = VecDeque::with_capacity(length);
-            let mut input = line.chars().enumerate();
-            // fill the first 4 input characters
-            for _ in 0..length {
-                let (_, elem) = input
-                    .next()
-                    .expect("need at least as many characters as marker length!");
-                buf.push_back(elem);
-            }
-            // check if first few chars are same
-            if check_marker(length, &buf) == true {
-                println!("start of packet marker is at {}", length + 1);
-            }
+/// Position of the last character of the first window of `length`
+/// distinct characters, counting from 1
+///
+/// ```
+/// let line = "mjqjpqmgbljsphdztnvjfqwrcgnjlmzfvxwnhrfbjsdvtmm";
+/// assert_eq!(day6::find_marker(4, line).unwrap(), Some(7));
+/// assert_eq!(day6::find_marker(14, line).unwrap(), Some(19));
+/// assert_eq!(day6::find_marker(4, "aaaaaa").unwrap(), None);
+/// assert!(day6::find_marker(4, "abc").is_err());
+/// ```
+pub fn find_marker(length: usize, line: &str) -> Result<Option<usize>, Box<dyn Error>> {
+    let mut buf: VecDeque<char> = VecDeque::with_capacity(length);
+    let mut input = line.chars().enumerate();
+    // fill the first marker length input characters
+    for _ in 0..length {
+        let (_, elem) = input
+            .next()
+            .ok_or("need at least as many characters as marker length!")?;
+        buf.push_back(elem);
+    }
+    // check if first few chars are same
+    if check_marker(length, &buf) {
+        return Ok(Some(length));
+    }
 
-            loop {
-                match input.next() {
-                    Some((idx, elem)) => {
-                        buf.pop_front();
-                        buf.push_back(elem);
-                        if check_marker(length, &buf) == true {
-                            println!("start of packet marker is at {}", idx + 1);
-                            break;
-                        }
-                        continue;
-                    }
-                    None => {
-                        println!("No start of packet marker found!");
-                        break;
-                    }
-                }
-            }
+    for (idx, elem) in input {
+        buf.pop_front();
+        buf.push_back(elem);
+        if check_marker(length, &buf) {
+            return Ok(Some(idx + 1));
+        }
+    }
+
+    Ok(None)
+}
+
+pub struct Day6 {
+    pub part1_marker: usize,
+    pub part2_marker: usize,
+}
+
+impl Default for Day6 {
+    fn default() -> Self {
+        Day6 {
+            part1_marker: 4,
+            part2_marker: 14,
         }
     }
+}
 
-    Ok(())
+impl Solution for Day6 {
+    // the datastream buffer
+    type Model = String;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        let line = input
+            .lines()
+            .next()
+            .filter(|line| !line.is_empty())
+            .ok_or_else(|| ParseError::new(ErrorKind::EmptyDatastream, 1, 1, ""))?;
+        Ok(line.to_string())
+    }
+
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        find_marker(self.part1_marker, model)?
+            .ok_or_else(|| "No start of packet marker found!".into())
+    }
+
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        find_marker(self.part2_marker, model)?
+            .ok_or_else(|| "No start of message marker found!".into())
+    }
 }
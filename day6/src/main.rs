@@ -1,18 +1,29 @@
-use std::env;
+use clap::Parser;
+use common::cli::DayArgs;
 use std::process;
 
-const MARKER_SIZE: usize = 14;
+/// Day 6: Tuning Trouble
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    day: DayArgs,
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+    /// length of the marker of both parts, instead of 4 for the start of
+    /// packet and 14 for the start of message
+    #[arg(long)]
+    marker_size: Option<usize>,
+}
 
-    if args.len() != 2 {
-        eprintln!("Provide only one input, the filename of values!")
+fn main() {
+    let args = Args::parse();
+    let mut day6 = day6::Day6::default();
+    if let Some(marker_size) = args.marker_size {
+        day6.part1_marker = marker_size;
+        day6.part2_marker = marker_size;
     }
 
-    let filename_in: String = args[1].clone();
-
-    if let Err(e) = day6::run(MARKER_SIZE, filename_in) {
+    if let Err(e) = args.day.run(6, &day6) {
         eprintln!("Application error: {}", e);
         process::exit(1);
     }
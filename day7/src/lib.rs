@@ -1,24 +1,108 @@
-use fs_err as fs;
+use common::Solution;
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
 
 mod parse;
 mod types;
 use crate::types::types::Line;
 
-pub fn run(filename: String) -> Result<(), Box<dyn std::error::Error>> {
-    let contents = fs::read_to_string(filename)?;
+const DISK_SIZE: usize = 70000000;
+const REQUIRED_EMPTY_SIZE: usize = 30000000;
+const SMALL_DIR_SIZE: usize = 100000;
+
+/// What is wrong with a line of the terminal output
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // only `cd` and `ls` are known
+    UnknownCommand,
+    InvalidFileSize,
+    // neither a command, a directory nor a file
+    UnrecognisedOutput,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::UnknownCommand => write!(f, "unknown command"),
+            ErrorKind::InvalidFileSize => write!(f, "invalid file size"),
+            ErrorKind::UnrecognisedOutput => write!(f, "unrecognised terminal output"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<ErrorKind>;
+
+pub struct FileSystem {
+    pub total_filesize: usize,
+    // map of directory location to the total size of everything under it
+    pub dir_sizes: HashMap<String, usize>,
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Model = FileSystem;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        get_filesystem(input)
+    }
+
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        Ok(model
+            .dir_sizes
+            .values()
+            .filter(|&&size| size <= SMALL_DIR_SIZE)
+            .sum::<usize>())
+    }
+
+    // a size of 0 means the required space is already available
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        let curr_empty_size: usize = DISK_SIZE
+            .checked_sub(model.total_filesize)
+            .ok_or("files do not fit on the disk!")?;
+
+        let req_size: usize = REQUIRED_EMPTY_SIZE.saturating_sub(curr_empty_size);
+
+        if req_size == 0 {
+            return Ok(0);
+        }
+
+        model
+            .dir_sizes
+            .values()
+            .filter(|&&size| size >= req_size)
+            .min()
+            .copied()
+            .ok_or_else(|| "error when finding smallest viable dir to delete!".into())
+    }
+}
+
+/// Sizes of the directories listed in the terminal output, by their path
+/// from the root (`/` for the root itself)
+///
+/// ```
+/// let output = "$ cd /\n$ ls\ndir a\n14848514 b.txt\n$ cd a\n$ ls\n29116 f\n";
+/// let filesystem = day7::get_filesystem(output).unwrap();
+/// assert_eq!(filesystem.total_filesize, 14877630);
+/// assert_eq!(filesystem.dir_sizes["/"], 14877630);
+/// assert_eq!(filesystem.dir_sizes["a"], 29116);
+/// ```
+pub fn get_filesystem(contents: &str) -> Result<FileSystem, Box<dyn Error>> {
     let component_lines = contents.lines().collect::<Vec<_>>();
 
     // map of files from location string to size
     let mut files_map: HashMap<String, usize> = HashMap::new();
     let mut basename: Vec<String> = Vec::new();
 
-    for line in component_lines {
+    for (lineidx, line) in component_lines.into_iter().enumerate() {
         if line.is_empty() {
             break;
         } else {
             match parse::parse_line(line) {
-                Some(res) => match res {
+                Ok(res) => match res {
                     Line::LsCommand => {
                         //println!("ls command at: {}", basename.join("/"));
                     }
@@ -27,7 +111,7 @@ pub fn run(filename: String) -> Result<(), Box<dyn std::error::Error>> {
                         if location == ".." {
                             basename.pop();
                         } else if location == "/" {
-                            /* do nothing for this case, the join method will add a / */
+                            basename.clear();
                         } else {
                             basename.push(location.to_string());
                         }
@@ -43,15 +127,14 @@ pub fn run(filename: String) -> Result<(), Box<dyn std::error::Error>> {
                         basename.pop();
                     }
                 },
-                None => {
-                    println!("failed to parse");
+                Err((kind, text)) => {
+                    return Err(ParseError::at(kind, lineidx, line, text).into());
                 }
             }
         }
     }
 
     let total_filesize = files_map.values().sum::<usize>();
-    println!("total size of files is : {}", total_filesize);
 
     /* using the hash map of file-locations, file-sizes, create
      * a hashmap of directory-locations and directory-sizes */
@@ -69,7 +152,7 @@ pub fn run(filename: String) -> Result<(), Box<dyn std::error::Error>> {
 
         dirs_map
             .entry(dirname)
-            .and_modify(|e| *e = *e + *filesize)
+            .and_modify(|e| *e += *filesize)
             .or_insert(*filesize);
     }
 
@@ -88,9 +171,12 @@ pub fn run(filename: String) -> Result<(), Box<dyn std::error::Error>> {
 
     /* concatenate sub dir sizes into parent dirs! */
     for (key1, value1) in dirs_map_concatenated.iter_mut() {
+        // a sub dir is under its parent's path, not just any path containing
+        // the parent's name
+        let parent = format!("{}/", key1);
         for (key2, value2) in dirs_map.iter() {
-            if key2.contains(key1) & (key1 != key2) {
-                *value1 = *value1 + *value2;
+            if key2.starts_with(&parent) {
+                *value1 += *value2;
             }
         }
     }
@@ -99,37 +185,8 @@ pub fn run(filename: String) -> Result<(), Box<dyn std::error::Error>> {
     _ = dirs_map_concatenated.insert("/".to_string(), total_filesize);
     _ = dirs_map_concatenated.remove_entry("");
 
-    println!(
-        "total size of dirs is : {}",
-        dirs_map_concatenated
-            .values()
-            .filter(|&&size| size <= 100000)
-            .sum::<usize>()
-    );
-
-    let curr_empty_size: usize = 70000000 - total_filesize;
-    let req_empty_size: usize = 30000000;
-
-    let req_size: usize = if curr_empty_size > req_empty_size {
-        0
-    } else {
-        req_empty_size - curr_empty_size
-    };
-
-    if req_size != 0 {
-        println!(
-            "smallest dir that can be deleted has size is : {}",
-            dirs_map_concatenated
-                .values()
-                .filter(|&&size| size >= req_size)
-                .min()
-                .expect("error when finding smallest viable dir to delete!")
-        );
-    } else {
-        println!("required space is already available!");
-    }
-
-    //dbg!(dirs_map_concatenated);
-
-    Ok(())
+    Ok(FileSystem {
+        total_filesize,
+        dir_sizes: dirs_map_concatenated,
+    })
 }
@@ -9,6 +9,7 @@ use nom::IResult;
 
 use crate::types::types::Line;
 
+#[allow(clippy::module_inception)]
 mod parse {
 
     use crate::parse::digit1;
@@ -32,7 +33,20 @@ mod parse {
     }
 }
 
-pub fn parse_line(input: &str) -> Option<Line> {
+/// A line of the terminal output, or what is wrong with it and the offending text
+///
+/// ```
+/// use day7::ErrorKind;
+///
+/// let error = |line: &str| *day7::get_filesystem(line).err().unwrap().downcast::<day7::ParseError>().unwrap();
+/// let unknown = error("$ cd /\n$ rm -rf a");
+/// assert_eq!((unknown.kind, unknown.line, unknown.column), (ErrorKind::UnknownCommand, 2, 3));
+/// let unrecognised = error("$ ls\nfile a");
+/// assert_eq!((unrecognised.kind, unrecognised.column), (ErrorKind::UnrecognisedOutput, 1));
+/// let size = error("99999999999999999999999 a");
+/// assert_eq!((size.kind, size.text.as_str()), (ErrorKind::InvalidFileSize, "99999999999999999999999"));
+/// ```
+pub fn parse_line(input: &str) -> Result<Line, (crate::ErrorKind, &str)> {
     match parse::parse_command(input).finish() {
         Ok((str1, _)) => {
             //println!("str1:{}, str2:{}", str1, str2);
@@ -42,16 +56,14 @@ pub fn parse_line(input: &str) -> Option<Line> {
                         location: dst.trim().to_string(),
                     };
 
-                    return Some(cdcommand);
+                    Ok(cdcommand)
                 }
                 Err(e) => {
-                    if e == Error::new(&str1, ErrorKind::Tag) {
-                        //eprintln!("tag error when parsing cd command {}", e);
+                    if e == Error::new(str1, ErrorKind::Tag) && str1.trim() == "ls" {
                         //println!("ls command inferred, given input : '{}'", str1);
-                        return Some(Line::LsCommand);
+                        Ok(Line::LsCommand)
                     } else {
-                        eprintln!("generic error: {}", e);
-                        return None;
+                        Err((crate::ErrorKind::UnknownCommand, str1.trim()))
                     }
                 }
             }
@@ -62,19 +74,18 @@ pub fn parse_line(input: &str) -> Option<Line> {
                     let dirname = Line::DirOutput {
                         name: (str1.trim().to_string()),
                     };
-                    return Some(dirname);
+                    Ok(dirname)
                 } else {
                     let fileout = Line::FileOutput {
-                        size: (str2.parse::<usize>().expect("error parsing file size!")),
+                        size: (str2
+                            .parse::<usize>()
+                            .map_err(|_| (crate::ErrorKind::InvalidFileSize, str2))?),
                         name: (str1.trim().to_string()),
                     };
-                    return Some(fileout);
+                    Ok(fileout)
                 }
             }
-            Err(e) => {
-                eprintln!("generic error when parsing file output: {}", e);
-                return None;
-            }
+            Err(_) => Err((crate::ErrorKind::UnrecognisedOutput, input)),
         },
     }
 }
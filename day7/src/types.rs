@@ -1,5 +1,7 @@
+#[allow(clippy::module_inception)]
 pub mod types {
     #[derive(Debug)]
+    #[allow(dead_code)]
     pub enum Line {
         CdCommand { location: String },
         LsCommand,
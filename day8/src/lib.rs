@@ -1,159 +1,190 @@
-use fs_err as fs;
-use itertools::Itertools;
-use nalgebra::DMatrix;
+use common::Solution;
+use grid::{Adjacency, Grid};
+use log::info;
 use std::error::Error;
+use std::fmt;
+use std::io::BufRead;
+
+/// What is wrong with the map of tree heights
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // heights are single digits
+    InvalidHeight,
+    // a row of a different width than the first row
+    RaggedRow { width: usize },
+}
 
-pub fn get_tree_matrix(filename: String) -> Result<DMatrix<u32>, Box<dyn Error>> {
-    let mut contents = fs::read_to_string(filename)?;
-    contents = contents.replace("\r\n", "\n"); // cool bear windows!
-    contents = contents.replace("\n", "");
-
-    if (contents.len() as f64).sqrt().floor() != (contents.len() as f64).sqrt() {
-        return Err("Could not convert input to a square matrix!".into());
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidHeight => write!(f, "tree height is not a digit"),
+            ErrorKind::RaggedRow { width } => {
+                write!(f, "expected a row of {} trees", width)
+            }
+        }
     }
-
-    let dim: usize = (contents.len() as f64).sqrt().floor() as usize;
-
-    println!("dim is {}x{}", dim, dim);
-
-    let tree_matrix = DMatrix::<u32>::from_row_iterator(
-        dim,
-        dim,
-        contents
-            .chars()
-            .map(|x| x.to_digit(10).expect("parsing input sequence failed"))
-            .into_iter(),
-    );
-
-    Ok(tree_matrix)
 }
 
-pub fn run_treescore(filename: String) -> Result<(), Box<dyn Error>> {
-    let tree_matrix =
-        get_tree_matrix(filename).expect("could not convert input sequence to tree matrix");
+pub type ParseError = common::ParseError<ErrorKind>;
 
-    let (dim, _) = tree_matrix.shape();
+pub struct Day8;
 
-    let it = (1..dim - 1).cartesian_product(1..dim - 1);
+impl Solution for Day8 {
+    type Model = Grid<u32>;
+    type Answer1 = u64;
+    type Answer2 = u64;
 
-    let highest_treescore: u64 = it
-        .map(|(rowidx, colidx)| {
-            let tree_height = tree_matrix[(rowidx, colidx)];
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        parse_tree_matrix(input)
+    }
 
-            let mut score_left = 0;
-            for i in (0..colidx).rev() {
-                if tree_height > tree_matrix[(rowidx, i)] {
-                    //println!("left tree at {},{}", rowidx, i);
-                    score_left += 1;
-                } else {
-                    score_left += 1;
-                    break;
-                }
-            }
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        visible_trees(model)
+    }
 
-            let mut score_right = 0;
-            for i in colidx + 1..dim {
-                if tree_height > tree_matrix[(rowidx, i)] {
-                    score_right += 1;
-                } else {
-                    score_right += 1;
-                    break;
-                }
-            }
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        highest_treescore(model)
+    }
+}
 
-            let mut score_top = 0;
-            for i in (0..rowidx).rev() {
-                if tree_height > tree_matrix[(i, colidx)] {
-                    //println!("top tree at {},{}", rowidx, i);
-                    score_top += 1;
-                } else {
-                    score_top += 1;
-                    break;
-                }
-            }
+/// Tree heights in the file `filename`
+///
+/// ```
+/// assert!(day8::get_tree_matrix("missing.txt".to_string()).is_err());
+/// ```
+pub fn get_tree_matrix(filename: String) -> Result<Grid<u32>, Box<dyn Error>> {
+    read_tree_matrix(common::input::open(filename)?)
+}
 
-            let mut score_bottom = 0;
-            for i in rowidx + 1..dim {
-                if tree_height > tree_matrix[(i, colidx)] {
-                    score_bottom += 1;
-                } else {
-                    score_bottom += 1;
-                    break;
-                }
-            }
+/// Tree heights read from `reader`
+///
+/// ```
+/// let trees = day8::read_tree_matrix("123\n456\n".as_bytes()).unwrap();
+/// assert_eq!((trees.rows(), trees.cols(), trees[(1, 2)]), (2, 3, 6));
+/// ```
+pub fn read_tree_matrix<R: BufRead>(reader: R) -> Result<Grid<u32>, Box<dyn Error>> {
+    parse_tree_matrix(&common::input::read(reader)?)
+}
 
-            /*
-            println!(
-                "tree with height {} at {},{} has treescore is {},{},{},{} -> {}",
-                tree_height,
-                rowidx,
-                colidx,
-                score_left,
-                score_right,
-                score_top,
-                score_bottom,
-                score_left * score_right * score_top * score_bottom
-            );*/
-
-            score_left * score_right * score_top * score_bottom
+/// Tree heights, one digit per tree and one row of trees per line
+///
+/// ```
+/// let trees = day8::parse_tree_matrix("30373\n25512\n65332\n33549\n35390\n").unwrap();
+/// assert_eq!((trees.rows(), trees.cols(), trees[(2, 0)]), (5, 5, 6));
+/// assert!(day8::parse_tree_matrix("303\n2x5\n").is_err());
+/// assert!(day8::parse_tree_matrix("303\n25\n").is_err());
+/// ```
+pub fn parse_tree_matrix(contents: &str) -> Result<Grid<u32>, Box<dyn Error>> {
+    let tree_matrix = Grid::parse(contents, |x| x.to_digit(10)).map_err(|e| {
+        e.map_kind(|kind| match kind {
+            grid::ErrorKind::InvalidCell => ErrorKind::InvalidHeight,
+            grid::ErrorKind::RaggedRow { width } => ErrorKind::RaggedRow { width },
         })
-        .max()
-        .expect("error computation highest treescore from set of tree scores");
+    })?;
 
-    println!("highest treescore is {}", highest_treescore);
+    info!("dim is {}x{}", tree_matrix.rows(), tree_matrix.cols());
 
-    Ok(())
+    Ok(tree_matrix)
 }
 
-pub fn run_visibility(filename: String) -> Result<(), Box<dyn Error>> {
-    let tree_matrix =
-        get_tree_matrix(filename).expect("could not convert input sequence to tree matrix");
-
-    let (dim, _) = tree_matrix.shape();
-
-    let it = (1..dim - 1).cartesian_product(1..dim - 1);
-
-    let visible_trees: u64 = it
-        .map(|(rowidx, colidx)| {
-            let tree_height = tree_matrix[(rowidx, colidx)];
-
-            let slice_left = &tree_matrix.slice((rowidx, 0), (1, colidx));
-            let left: bool = tree_height > slice_left.max();
-
-            let slice_right = &tree_matrix.slice((rowidx, colidx + 1), (1, dim - colidx - 1));
-            let right: bool = tree_height > slice_right.max();
-
-            let slice_top = &tree_matrix.slice((0, colidx), (rowidx, 1));
-            let top: bool = tree_height > slice_top.max();
+/// Highest product of the viewing distances in the four directions
+///
+/// ```
+/// let trees = day8::parse_tree_matrix("30373\n25512\n65332\n33549\n35390\n").unwrap();
+/// assert_eq!(day8::highest_treescore(&trees).unwrap(), 8);
+/// ```
+pub fn highest_treescore(tree_matrix: &Grid<u32>) -> Result<u64, Box<dyn Error>> {
+    let highest_treescore: u64 = tree_matrix
+        .positions()
+        .map(|pos| treescore(tree_matrix, pos))
+        .max()
+        .ok_or("error computation highest treescore from set of tree scores")?;
 
-            let slice_bottom = &tree_matrix.slice((rowidx + 1, colidx), (dim - rowidx - 1, 1));
-            let bottom: bool = tree_height > slice_bottom.max();
+    Ok(highest_treescore)
+}
 
-            if left | right | top | bottom {
-                /*
-                println!(
-                    "tree height of {}   visible at {},{} due to reason {},{},{},{}",
-                    tree_height, rowidx, colidx, left, right, top, bottom
-                );*/
-                1
-            } else {
-                /*
-                println!(
-                    "tree height of {} invisible at {},{} due to reason {},{},{},{}",
-                    tree_height, rowidx, colidx, left, right, top, bottom
-                );*/
-                0
+fn treescore(tree_matrix: &Grid<u32>, pos: (usize, usize)) -> u64 {
+    let tree_height = tree_matrix[pos];
+    // number of trees seen in each direction, up to and including the first
+    // tree that is at least as high
+    Adjacency::Four
+        .offsets()
+        .iter()
+        .map(|offset| {
+            let mut score = 0;
+            for other in tree_matrix.walk(pos, *offset) {
+                score += 1;
+                if tree_matrix[other] >= tree_height {
+                    break;
+                }
             }
+            score
         })
-        .sum();
+        .product::<u64>()
+}
 
-    // account the trees on the edges!
-    let edge_trees: u64 = ((dim * dim) - ((dim - 2) * (dim - 2)))
-        .try_into()
-        .expect("tree edge count overflow!");
-    println!("number of visible trees is {}", visible_trees + edge_trees);
+/// Tree heights as digits, the tree with the highest scenic score as `*`
+///
+/// ```
+/// let trees = day8::parse_tree_matrix("30373\n25512\n65332\n33549\n35390\n").unwrap();
+/// let picture = day8::treescore_picture(&trees);
+/// assert_eq!(picture[(3, 2)], '*');
+/// assert_eq!(picture[(0, 0)], '3');
+/// ```
+pub fn treescore_picture(tree_matrix: &Grid<u32>) -> Grid<char> {
+    let best = tree_matrix
+        .positions()
+        .max_by_key(|pos| treescore(tree_matrix, *pos));
+    let mut picture = Grid::new(tree_matrix.rows(), tree_matrix.cols(), '.');
+    for (pos, tree_height) in tree_matrix.iter() {
+        picture[pos] = char::from_digit(*tree_height, 10).unwrap_or('?');
+    }
+    if let Some(best) = best {
+        picture[best] = '*';
+    }
+    picture
+}
 
-    //dbg!(tree_matrix);
+/// Number of trees visible from outside the grid
+///
+/// ```
+/// let trees = day8::parse_tree_matrix("30373\n25512\n65332\n33549\n35390\n").unwrap();
+/// assert_eq!(day8::visible_trees(&trees).unwrap(), 21);
+/// ```
+pub fn visible_trees(tree_matrix: &Grid<u32>) -> Result<u64, Box<dyn Error>> {
+    let visible_trees = tree_matrix
+        .positions()
+        .filter(|pos| visible(tree_matrix, *pos))
+        .count();
+
+    Ok(visible_trees.try_into()?)
+}
 
-    Ok(())
+// a tree is visible from a direction when all the trees up to the edge are
+// lower, the trees on the edges are always visible
+fn visible(tree_matrix: &Grid<u32>, pos: (usize, usize)) -> bool {
+    Adjacency::Four.offsets().iter().any(|offset| {
+        tree_matrix
+            .walk(pos, *offset)
+            .all(|other| tree_matrix[other] < tree_matrix[pos])
+    })
+}
+
+/// Trees visible from outside the grid as `#`, hidden ones as `.`
+///
+/// ```
+/// let trees = day8::parse_tree_matrix("30373\n25512\n65332\n33549\n35390\n").unwrap();
+/// let picture = day8::visibility_picture(&trees);
+/// assert_eq!(picture.iter().filter(|(_, cell)| **cell == '#').count(), 21);
+/// assert_eq!((picture[(1, 1)], picture[(1, 3)]), ('#', '.'));
+/// ```
+pub fn visibility_picture(tree_matrix: &Grid<u32>) -> Grid<char> {
+    let mut picture = Grid::new(tree_matrix.rows(), tree_matrix.cols(), '.');
+    for pos in tree_matrix
+        .positions()
+        .filter(|pos| visible(tree_matrix, *pos))
+    {
+        picture[pos] = '#';
+    }
+    picture
 }
@@ -1,33 +1,90 @@
-use fs_err as fs;
+use common::Solution;
+use log::{debug, trace};
 use std::collections::HashSet;
 use std::error::Error;
+use std::fmt;
 
-mod rope;
-use crate::rope::headtail::Direction;
-use crate::rope::headtail::Point;
+pub mod rope;
+use crate::rope::headtail::Knot;
+use geometry::{Direction, Point};
 
-pub fn run(filename: String, rope_length: usize) -> Result<(), Box<dyn Error>> {
-    let contents = fs::read_to_string(filename)?;
-    let component_lines = contents.lines().collect::<Vec<_>>();
+/// What is wrong with a move of the head of the rope
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // not of the form `R 4`
+    InvalidMove,
+    InvalidDirection,
+    InvalidDistance,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidMove => write!(f, "expected a direction and a distance"),
+            ErrorKind::InvalidDirection => write!(f, "direction is not R, L, U or D"),
+            ErrorKind::InvalidDistance => write!(f, "invalid distance"),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<ErrorKind>;
+
+pub struct Day9 {
+    pub part1_rope_length: usize,
+    pub part2_rope_length: usize,
+}
+
+impl Default for Day9 {
+    fn default() -> Self {
+        Day9 {
+            part1_rope_length: 2,
+            part2_rope_length: 10,
+        }
+    }
+}
+
+impl Solution for Day9 {
+    // moves of the head of the rope
+    type Model = Vec<(Direction, u32)>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Self::Model, Box<dyn Error>> {
+        parse_moves(input)
+    }
+
+    fn part1(&self, model: &Self::Model) -> Result<Self::Answer1, Box<dyn Error>> {
+        tail_locations(model, self.part1_rope_length)
+    }
 
-    let mut rope: Vec<Point> = Vec::with_capacity(rope_length);
-    for _ in 0..rope_length {
-        rope.push(Point::new(0, 0));
+    fn part2(&self, model: &Self::Model) -> Result<Self::Answer2, Box<dyn Error>> {
+        tail_locations(model, self.part2_rope_length)
     }
+}
 
-    let mut tail_state: HashSet<(i32, i32)> = HashSet::new();
+/// Moves of the head of the rope, a direction and a number of steps per
+/// line
+///
+/// ```
+/// use geometry::Direction;
+///
+/// let moves = day9::parse_moves("R 4\nU 12\n").unwrap();
+/// assert_eq!(moves, [(Direction::Right, 4), (Direction::Up, 12)]);
+/// assert!(day9::parse_moves("X 4").is_err());
+/// assert!(day9::parse_moves("R -4").is_err());
+/// ```
+pub fn parse_moves(contents: &str) -> Result<Vec<(Direction, u32)>, Box<dyn Error>> {
+    let mut moves: Vec<(Direction, u32)> = Vec::new();
 
-    for line in component_lines.iter() {
+    for (lineidx, line) in contents.lines().enumerate() {
         if line.is_empty() {
             // account for any extra newline at end
             continue;
         } else {
             let input_vec: Vec<_> = line.split_whitespace().collect();
-            assert_eq!(
-                input_vec.len(),
-                2,
-                "Each line in the input file should have only two characters!"
-            );
+            if input_vec.len() != 2 {
+                return Err(ParseError::line(ErrorKind::InvalidMove, lineidx, line).into());
+            }
 
             let dir = input_vec[0];
             let dir: Option<Direction> = match dir {
@@ -37,40 +94,68 @@ pub fn run(filename: String, rope_length: usize) -> Result<(), Box<dyn Error>> {
                 "D" => Some(Direction::Down),
                 _ => None,
             };
-            let dir = dir.expect("invalid direction given in input");
-
-            let dist = input_vec[1]
-                .parse::<u32>()
-                .expect("invalid distance in input");
-
-            println!("moving {} distance in direction {:?}", dist, dir);
-
-            for _ in 0..dist {
-                rope[0].update(dir);
-                for idx in 0..rope_length - 1 {
-                    let possibly_move_tail = rope[idx].to_move_tail(&rope[idx + 1]);
-                    match possibly_move_tail {
-                        Some(tail_move_dir) => {
-                            println!("at idx {}, tail is moving along : {:?}", idx, tail_move_dir);
-                            rope[idx + 1].update(tail_move_dir);
-                        }
-                        None => {}
-                    }
-                }
-                for elem in &rope {
-                    println!("rope element at {}", elem);
+            let dir = dir.ok_or_else(|| {
+                ParseError::at(ErrorKind::InvalidDirection, lineidx, line, input_vec[0])
+            })?;
+
+            let dist = input_vec[1].parse::<u32>().map_err(|_| {
+                ParseError::at(ErrorKind::InvalidDistance, lineidx, line, input_vec[1])
+            })?;
+
+            moves.push((dir, dist));
+        }
+    }
+
+    Ok(moves)
+}
+
+/// Number of unique locations covered by the tail of the rope
+///
+/// ```
+/// use geometry::Direction;
+///
+/// let moves = [(Direction::Right, 4), (Direction::Up, 4)];
+/// // the tail of a single knot rope is the head
+/// assert_eq!(day9::tail_locations(&moves, 1).unwrap(), 9);
+/// assert_eq!(day9::tail_locations(&moves, 2).unwrap(), 7);
+/// // the tail of a long rope does not move at all
+/// assert_eq!(day9::tail_locations(&moves, 10).unwrap(), 1);
+/// assert!(day9::tail_locations(&moves, 0).is_err());
+/// ```
+pub fn tail_locations(
+    moves: &[(Direction, u32)],
+    rope_length: usize,
+) -> Result<usize, Box<dyn Error>> {
+    if rope_length == 0 {
+        return Err("rope needs at least one knot!".into());
+    }
+
+    let mut rope: Vec<Point> = vec![Point::ORIGIN; rope_length];
+
+    // the tail visits its starting location, even if the head pulls it
+    // away on the first step
+    let mut tail_state: HashSet<Point> = HashSet::from([Point::ORIGIN]);
+
+    for (dir, dist) in moves {
+        debug!("moving {} distance in direction {:?}", dist, dir);
+
+        for _ in 0..*dist {
+            rope[0] += dir.vector();
+            for idx in 0..rope_length - 1 {
+                let possibly_move_tail = rope[idx].to_move_tail(&rope[idx + 1]);
+                if let Some(tail_move_dir) = possibly_move_tail {
+                    trace!("at idx {}, tail is moving along : {:?}", idx, tail_move_dir);
+                    rope[idx + 1] += tail_move_dir.vector();
                 }
-                _ = tail_state.insert(rope[rope_length - 1].get_coords());
             }
-        };
+            for elem in &rope {
+                trace!("rope element at {}", elem);
+            }
+            _ = tail_state.insert(rope[rope_length - 1]);
+        }
     }
 
-    println!(
-        "number of unique locations tail has covered: {}",
-        tail_state.len()
-    );
-
     //dbg!(tail_state);
 
-    Ok(())
+    Ok(tail_state.len())
 }
@@ -1,17 +1,29 @@
-use std::env;
+use clap::Parser;
+use common::cli::DayArgs;
 use std::process;
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
+/// Day 9: Rope Bridge
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    #[command(flatten)]
+    day: DayArgs,
 
-    if args.len() != 2 {
-        eprintln!("Provide only one input, the filename move instructions for head!")
-    }
+    /// knots in the rope of both parts, instead of 2 for part 1 and 10 for
+    /// part 2
+    #[arg(long)]
+    rope_length: Option<usize>,
+}
 
-    let filename_in: String = args[1].clone();
-    let rope_length: usize = 10;
+fn main() {
+    let args = Args::parse();
+    let mut day9 = day9::Day9::default();
+    if let Some(rope_length) = args.rope_length {
+        day9.part1_rope_length = rope_length;
+        day9.part2_rope_length = rope_length;
+    }
 
-    if let Err(e) = day9::run(filename_in, rope_length) {
+    if let Err(e) = args.day.run(9, &day9) {
         eprintln!("Application error: {}", e);
         process::exit(1);
     }
@@ -0,0 +1,76 @@
+use crate::point::Point;
+
+/// Smallest rectangle holding a set of points, bounds are inclusive
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Bounds {
+    pub x_min: i64,
+    pub x_max: i64,
+    pub y_min: i64,
+    pub y_max: i64,
+}
+
+impl Bounds {
+    pub fn new(pos: Point) -> Bounds {
+        Bounds {
+            x_min: pos.x,
+            x_max: pos.x,
+            y_min: pos.y,
+            y_max: pos.y,
+        }
+    }
+
+    /// Bounds of all the points, None if there are none
+    ///
+    /// ```
+    /// use geometry::{Bounds, Point};
+    ///
+    /// let bounds = Bounds::of([Point::new(498, 4), Point::new(503, 9)]).unwrap();
+    /// assert_eq!((bounds.width(), bounds.height()), (6, 6));
+    /// assert!(bounds.contains(Point::new(500, 4)));
+    /// assert!(Bounds::of([]).is_none());
+    /// ```
+    pub fn of<I: IntoIterator<Item = Point>>(points: I) -> Option<Bounds> {
+        let mut points = points.into_iter();
+        let mut bounds = Bounds::new(points.next()?);
+        for pos in points {
+            bounds.extend(pos);
+        }
+        Some(bounds)
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        (self.x_min..=self.x_max).contains(&pos.x) && (self.y_min..=self.y_max).contains(&pos.y)
+    }
+
+    /// Grow the bounds to hold pos
+    pub fn extend(&mut self, pos: Point) {
+        self.x_min = self.x_min.min(pos.x);
+        self.x_max = self.x_max.max(pos.x);
+        self.y_min = self.y_min.min(pos.y);
+        self.y_max = self.y_max.max(pos.y);
+    }
+
+    /// Smallest bounds holding both
+    pub fn union(&self, other: Bounds) -> Bounds {
+        let mut bounds = *self;
+        bounds.extend(other.top_left());
+        bounds.extend(other.bottom_right());
+        bounds
+    }
+
+    pub fn top_left(&self) -> Point {
+        Point::new(self.x_min, self.y_min)
+    }
+
+    pub fn bottom_right(&self) -> Point {
+        Point::new(self.x_max, self.y_max)
+    }
+
+    pub fn width(&self) -> u64 {
+        self.x_min.abs_diff(self.x_max) + 1
+    }
+
+    pub fn height(&self) -> u64 {
+        self.y_min.abs_diff(self.y_max) + 1
+    }
+}
@@ -0,0 +1,69 @@
+use crate::point::Vector;
+
+/// A unit step on the plane, along an axis or diagonally
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+    UpLeft,
+    UpRight,
+    DownLeft,
+    DownRight,
+}
+
+impl Direction {
+    /// Steps along x or y
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+    ];
+
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::Down,
+        Direction::Left,
+        Direction::Right,
+        Direction::UpLeft,
+        Direction::UpRight,
+        Direction::DownLeft,
+        Direction::DownRight,
+    ];
+
+    /// The step as a vector, up being towards smaller y
+    pub fn vector(&self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::Down => Vector::new(0, 1),
+            Direction::Left => Vector::new(-1, 0),
+            Direction::Right => Vector::new(1, 0),
+            Direction::UpLeft => Vector::new(-1, -1),
+            Direction::UpRight => Vector::new(1, -1),
+            Direction::DownLeft => Vector::new(-1, 1),
+            Direction::DownRight => Vector::new(1, 1),
+        }
+    }
+
+    /// Direction of a single step, None for the zero vector and vectors
+    /// longer than one step
+    ///
+    /// ```
+    /// use geometry::{Direction, Vector};
+    ///
+    /// assert_eq!(Direction::from_vector(Vector::new(1, -1)), Some(Direction::UpRight));
+    /// assert_eq!(Direction::from_vector(Vector::new(0, 0)), None);
+    /// assert_eq!(Direction::from_vector(Vector::new(2, 0)), None);
+    /// ```
+    pub fn from_vector(step: Vector) -> Option<Direction> {
+        Direction::ALL
+            .into_iter()
+            .find(|direction| direction.vector() == step)
+    }
+
+    pub fn opposite(&self) -> Direction {
+        Direction::from_vector(-self.vector()).expect("every step can be reversed")
+    }
+}
@@ -0,0 +1,6 @@
+mod bounds;
+mod direction;
+mod point;
+pub use crate::bounds::Bounds;
+pub use crate::direction::Direction;
+pub use crate::point::{Point, Vector};
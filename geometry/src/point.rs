@@ -0,0 +1,148 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A position on the integer plane. y grows downwards as in the puzzle
+/// drawings.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// The displacement between two points
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Vector {
+    pub dx: i64,
+    pub dy: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub fn new(x: i64, y: i64) -> Point {
+        Point { x, y }
+    }
+
+    /// Number of steps between two points moving along x or y only
+    ///
+    /// ```
+    /// use geometry::Point;
+    ///
+    /// assert_eq!(Point::new(2, 18).manhattan(Point::new(-2, 15)), 7);
+    /// assert_eq!(Point::new(2, 18).chebyshev(Point::new(-2, 15)), 4);
+    /// ```
+    pub fn manhattan(&self, other: Point) -> u64 {
+        (*self - other).manhattan()
+    }
+
+    /// Number of steps between two points when diagonal steps are allowed
+    pub fn chebyshev(&self, other: Point) -> u64 {
+        (*self - other).chebyshev()
+    }
+}
+
+impl Vector {
+    pub fn new(dx: i64, dy: i64) -> Vector {
+        Vector { dx, dy }
+    }
+
+    pub fn manhattan(&self) -> u64 {
+        self.dx.unsigned_abs() + self.dy.unsigned_abs()
+    }
+
+    pub fn chebyshev(&self) -> u64 {
+        self.dx.unsigned_abs().max(self.dy.unsigned_abs())
+    }
+
+    /// The vector with each component reduced to -1, 0 or 1, i.e. a single
+    /// step towards where the vector points
+    pub fn signum(&self) -> Vector {
+        Vector::new(self.dx.signum(), self.dy.signum())
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from(pos: (i64, i64)) -> Point {
+        Point::new(pos.0, pos.1)
+    }
+}
+
+impl From<(i64, i64)> for Vector {
+    fn from(offset: (i64, i64)) -> Vector {
+        Vector::new(offset.0, offset.1)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Point {
+        Point::new(self.x + rhs.dx, self.y + rhs.dy)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Point {
+        self + -rhs
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector::new(self.dx + rhs.dx, self.dy + rhs.dy)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        self + -rhs
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.dx, -self.dy)
+    }
+}
+
+impl Mul<i64> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i64) -> Vector {
+        Vector::new(self.dx * rhs, self.dy * rhs)
+    }
+}
@@ -0,0 +1,58 @@
+use geometry::{Bounds, Direction, Point, Vector};
+
+#[test]
+fn points_and_vectors() {
+    let a = Point::new(2, 18);
+    let b = Point::new(-2, 15);
+    assert_eq!(a - b, Vector::new(4, 3));
+    assert_eq!(b + (a - b), a);
+    assert_eq!(a - Vector::new(1, 1) * 2, Point::new(0, 16));
+    assert_eq!(-(a - b), b - a);
+    assert_eq!(a.to_string(), "(2, 18)");
+}
+
+#[test]
+fn distances() {
+    let a = Point::new(8, 7);
+    let b = Point::new(2, 10);
+    assert_eq!(a.manhattan(b), 9);
+    assert_eq!(a.chebyshev(b), 6);
+    assert_eq!(b.manhattan(a), a.manhattan(b));
+    assert_eq!(a.manhattan(a), 0);
+}
+
+#[test]
+fn directions_are_unit_steps() {
+    for direction in Direction::ALL {
+        let step = direction.vector();
+        assert_eq!(step.chebyshev(), 1);
+        assert_eq!(Direction::from_vector(step), Some(direction));
+        assert_eq!(direction.opposite().vector(), -step);
+    }
+    for direction in Direction::CARDINAL {
+        assert_eq!(direction.vector().manhattan(), 1);
+    }
+    assert_eq!(Point::ORIGIN + Direction::Up.vector(), Point::new(0, -1));
+    assert_eq!(Direction::from_vector(Vector::new(2, 0)), None);
+    assert_eq!(
+        Direction::from_vector(Vector::new(5, -3).signum()),
+        Some(Direction::UpRight)
+    );
+}
+
+#[test]
+fn bounds_hold_every_point() {
+    let points = [Point::new(498, 4), Point::new(503, 9), Point::new(500, 0)];
+    let bounds = Bounds::of(points).unwrap();
+    assert_eq!(bounds.top_left(), Point::new(498, 0));
+    assert_eq!(bounds.bottom_right(), Point::new(503, 9));
+    assert_eq!((bounds.width(), bounds.height()), (6, 10));
+    assert!(points.iter().all(|pos| bounds.contains(*pos)));
+    assert!(!bounds.contains(Point::new(497, 4)));
+    assert_eq!(Bounds::of([]), None);
+
+    let other = Bounds::new(Point::new(510, -1));
+    let union = bounds.union(other);
+    assert_eq!(union.top_left(), Point::new(498, -1));
+    assert_eq!(union.bottom_right(), Point::new(510, 9));
+}
@@ -0,0 +1,215 @@
+use crate::{Adjacency, ErrorKind, ParseError};
+use std::ops::{Index, IndexMut};
+
+/// A rectangular grid holding a value in every cell, stored row by row.
+/// Cells are addressed by (row, col), counting from the top left.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(rows: usize, cols: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            rows,
+            cols,
+            cells: vec![value; rows * cols],
+        }
+    }
+
+    /// Grid from its cells, row by row. None if there are not exactly
+    /// rows * cols of them.
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Option<Grid<T>> {
+        if rows.checked_mul(cols)? != cells.len() {
+            return None;
+        }
+        Some(Grid { rows, cols, cells })
+    }
+
+    /// Grid from a character map, one row per line. `cell` gives the value
+    /// of each character, None for a character that is not allowed. Empty
+    /// lines are skipped.
+    ///
+    /// ```
+    /// use grid::{ErrorKind, Grid};
+    ///
+    /// let grid = Grid::parse("30373\n25512\n", |x| x.to_digit(10)).unwrap();
+    /// assert_eq!((grid.rows(), grid.cols()), (2, 5));
+    /// assert_eq!(grid[(1, 2)], 5);
+    /// assert_eq!(grid.render(|x| char::from_digit(*x, 10).unwrap()), "30373\n25512");
+    ///
+    /// let error = Grid::parse("303\n2x5\n", |x| x.to_digit(10)).unwrap_err();
+    /// assert_eq!((error.kind, error.line, error.column), (ErrorKind::InvalidCell, 2, 2));
+    /// let error = Grid::parse("303\n25\n", |x| x.to_digit(10)).unwrap_err();
+    /// assert_eq!(error.kind, ErrorKind::RaggedRow { width: 3 });
+    /// ```
+    pub fn parse<F: FnMut(char) -> Option<T>>(
+        contents: &str,
+        mut cell: F,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut cells: Vec<T> = Vec::with_capacity(contents.len());
+        let mut rows: usize = 0;
+        let mut width: Option<usize> = None;
+
+        for (lineidx, line) in contents.lines().enumerate() {
+            // lines() leaves the \r of windows line endings
+            let line = line.trim_end_matches('\r');
+            if line.is_empty() {
+                continue;
+            }
+            let mut cols: usize = 0;
+            for (idx, x) in line.char_indices() {
+                let value = cell(x).ok_or_else(|| {
+                    let text = &line[idx..idx + x.len_utf8()];
+                    ParseError::at(ErrorKind::InvalidCell, lineidx, line, text)
+                })?;
+                cells.push(value);
+                cols += 1;
+            }
+            match width {
+                None => width = Some(cols),
+                Some(width) if width != cols => {
+                    return Err(ParseError::line(
+                        ErrorKind::RaggedRow { width },
+                        lineidx,
+                        line,
+                    ));
+                }
+                Some(_) => {}
+            }
+            rows += 1;
+        }
+
+        Ok(Grid {
+            rows,
+            cols: width.unwrap_or(0),
+            cells,
+        })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn contains(&self, pos: (usize, usize)) -> bool {
+        pos.0 < self.rows && pos.1 < self.cols
+    }
+
+    /// Index of the cell in row by row order, also the order of `iter`
+    pub fn index_of(&self, pos: (usize, usize)) -> Option<usize> {
+        if self.contains(pos) {
+            Some(pos.0 * self.cols + pos.1)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: (usize, usize)) -> Option<&T> {
+        self.index_of(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: (usize, usize)) -> Option<&mut T> {
+        self.index_of(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let cols = self.cols;
+        (0..self.rows * cols).map(move |idx| (idx / cols, idx % cols))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Position of the first cell, row by row, for which `pred` holds
+    pub fn position<P: FnMut(&T) -> bool>(&self, mut pred: P) -> Option<(usize, usize)> {
+        self.iter().find(|(_, x)| pred(x)).map(|(pos, _)| pos)
+    }
+
+    // position one step from pos, if it is inside the grid
+    fn step(&self, pos: (usize, usize), offset: (i64, i64)) -> Option<(usize, usize)> {
+        let row = pos.0.checked_add_signed(offset.0 as isize)?;
+        let col = pos.1.checked_add_signed(offset.1 as isize)?;
+        if self.contains((row, col)) {
+            Some((row, col))
+        } else {
+            None
+        }
+    }
+
+    /// Neighbours of a cell that lie inside the grid
+    ///
+    /// ```
+    /// use grid::{Adjacency, Grid};
+    ///
+    /// let grid = Grid::new(3, 3, 0);
+    /// assert_eq!(grid.neighbours((0, 0), Adjacency::Four).count(), 2);
+    /// assert_eq!(grid.neighbours((0, 1), Adjacency::Eight).count(), 5);
+    /// assert_eq!(grid.neighbours((1, 1), Adjacency::Eight).count(), 8);
+    /// ```
+    pub fn neighbours(
+        &self,
+        pos: (usize, usize),
+        adjacency: Adjacency,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        adjacency
+            .offsets()
+            .iter()
+            .filter_map(move |offset| self.step(pos, *offset))
+    }
+
+    /// Cells passed when walking from a cell (excluded) in steps of
+    /// `offset` until the edge of the grid
+    ///
+    /// ```
+    /// let grid = grid::Grid::new(3, 4, '.');
+    /// let right: Vec<_> = grid.walk((1, 1), (0, 1)).collect();
+    /// assert_eq!(right, [(1, 2), (1, 3)]);
+    /// assert_eq!(grid.walk((0, 3), (-1, -1)).count(), 0);
+    /// ```
+    pub fn walk(
+        &self,
+        pos: (usize, usize),
+        offset: (i64, i64),
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::successors(self.step(pos, offset), move |pos| self.step(*pos, offset))
+    }
+
+    /// The grid as a character map, the inverse of `parse`
+    pub fn render<F: FnMut(&T) -> char>(&self, mut cell: F) -> String {
+        let mut map = String::with_capacity(self.rows * (self.cols + 1));
+        for (idx, row) in self.cells.chunks(self.cols.max(1)).enumerate() {
+            if idx > 0 {
+                map.push('\n');
+            }
+            map.extend(row.iter().map(&mut cell));
+        }
+        map
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: (usize, usize)) -> &T {
+        let idx = self.index_of(pos).expect("position outside of the grid!");
+        &self.cells[idx]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, pos: (usize, usize)) -> &mut T {
+        let idx = self.index_of(pos).expect("position outside of the grid!");
+        &mut self.cells[idx]
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+mod dense;
+mod sparse;
+pub use crate::dense::Grid;
+pub use crate::sparse::SparseGrid;
+
+/// Which cells count as the neighbours of a cell
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Adjacency {
+    // sharing an edge
+    Four,
+    // sharing an edge or a corner
+    Eight,
+}
+
+const FOUR: [(i64, i64); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+const EIGHT: [(i64, i64); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+impl Adjacency {
+    /// Offsets from a cell to its neighbours, applied to both coordinates
+    /// of a position, i.e. (row, col) of a `Grid` and (x, y) of a
+    /// `SparseGrid`.
+    pub fn offsets(&self) -> &'static [(i64, i64)] {
+        match self {
+            Adjacency::Four => &FOUR,
+            Adjacency::Eight => &EIGHT,
+        }
+    }
+}
+
+/// What is wrong with a character map
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ErrorKind {
+    // a character that does not stand for any cell
+    InvalidCell,
+    // a row of a different width than the first row
+    RaggedRow { width: usize },
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorKind::InvalidCell => write!(f, "invalid cell"),
+            ErrorKind::RaggedRow { width } => write!(f, "expected a row of {} cells", width),
+        }
+    }
+}
+
+pub type ParseError = common::ParseError<ErrorKind>;
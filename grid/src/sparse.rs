@@ -0,0 +1,111 @@
+use crate::Adjacency;
+use geometry::{Bounds, Point, Vector};
+use std::collections::HashMap;
+
+/// An unbounded grid where only some cells hold a value. Cells are
+/// addressed by (x, y), y growing downwards as in the puzzle drawings. The
+/// bounds of the filled cells are kept up to date.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    /// Grid from a character map with the top left character at (0, 0).
+    /// Characters for which `cell` gives None are left empty.
+    pub fn parse<F: FnMut(char) -> Option<T>>(contents: &str, mut cell: F) -> SparseGrid<T> {
+        let mut grid = SparseGrid::new();
+        for (y, line) in contents.lines().enumerate() {
+            for (x, elem) in line.trim_end_matches('\r').chars().enumerate() {
+                if let Some(value) = cell(elem) {
+                    grid.insert(Point::new(x as i64, y as i64), value);
+                }
+            }
+        }
+        grid
+    }
+
+    /// Fill a cell, returning the value it held before
+    pub fn insert(&mut self, pos: Point, value: T) -> Option<T> {
+        match &mut self.bounds {
+            None => self.bounds = Some(Bounds::new(pos)),
+            Some(bounds) => bounds.extend(pos),
+        }
+        self.cells.insert(pos, value)
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    /// Number of filled cells
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Bounds of the filled cells, None while the grid is empty
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    /// Filled cells with their position, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(pos, value)| (*pos, value))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.values()
+    }
+
+    /// Neighbouring positions of a cell, whether they are filled or not
+    pub fn neighbours(pos: Point, adjacency: Adjacency) -> impl Iterator<Item = Point> {
+        adjacency
+            .offsets()
+            .iter()
+            .map(move |offset| pos + Vector::from(*offset))
+    }
+
+    /// The cells within `bounds` as a character map, `empty` standing for
+    /// the cells without a value
+    pub fn render_within<F: FnMut(&T) -> char>(
+        &self,
+        bounds: Bounds,
+        empty: char,
+        mut cell: F,
+    ) -> String {
+        let mut map = String::new();
+        for y in bounds.y_min..=bounds.y_max {
+            if y > bounds.y_min {
+                map.push('\n');
+            }
+            for x in bounds.x_min..=bounds.x_max {
+                map.push(self.get(Point::new(x, y)).map_or(empty, &mut cell));
+            }
+        }
+        map
+    }
+
+    /// The filled part of the grid as a character map
+    pub fn render<F: FnMut(&T) -> char>(&self, empty: char, cell: F) -> String {
+        match self.bounds {
+            None => String::new(),
+            Some(bounds) => self.render_within(bounds, empty, cell),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use geometry::{Bounds, Point};
+use grid::{Adjacency, ErrorKind, Grid, SparseGrid};
+
+#[test]
+fn parse_and_render_round_trip() {
+    let map = "Sabq\nabcr\naccs";
+    let grid = Grid::parse(map, Some).unwrap();
+    assert_eq!((grid.rows(), grid.cols()), (3, 4));
+    assert_eq!(grid[(1, 3)], 'r');
+    assert_eq!(grid.position(|x| *x == 's'), Some((2, 3)));
+    assert_eq!(grid.render(|x| *x), map);
+}
+
+#[test]
+fn parse_errors_point_at_the_cell() {
+    let e = Grid::parse("123\n1x3\n", |x| x.to_digit(10)).unwrap_err();
+    assert_eq!((e.kind, e.line, e.column), (ErrorKind::InvalidCell, 2, 2));
+
+    let e = Grid::parse("123\n12\n", |x| x.to_digit(10)).unwrap_err();
+    assert_eq!(e.kind, ErrorKind::RaggedRow { width: 3 });
+    assert_eq!(e.line, 2);
+}
+
+#[test]
+fn neighbours_stay_inside_the_grid() {
+    let grid = Grid::new(3, 3, 0);
+    let corner: Vec<_> = grid.neighbours((0, 0), Adjacency::Four).collect();
+    assert_eq!(corner, vec![(0, 1), (1, 0)]);
+    assert_eq!(grid.neighbours((0, 0), Adjacency::Eight).count(), 3);
+    assert_eq!(grid.neighbours((1, 1), Adjacency::Eight).count(), 8);
+}
+
+#[test]
+fn walk_goes_to_the_edge() {
+    let grid = Grid::new(3, 4, 0);
+    let right: Vec<_> = grid.walk((1, 1), (0, 1)).collect();
+    assert_eq!(right, vec![(1, 2), (1, 3)]);
+    assert_eq!(grid.walk((0, 1), (-1, 0)).count(), 0);
+}
+
+#[test]
+fn sparse_grid_tracks_bounds() {
+    let mut grid = SparseGrid::new();
+    assert_eq!(grid.bounds(), None);
+    grid.insert(Point::new(2, 0), '#');
+    grid.insert(Point::new(0, 1), 'o');
+    assert_eq!(
+        grid.bounds(),
+        Some(Bounds {
+            x_min: 0,
+            x_max: 2,
+            y_min: 0,
+            y_max: 1
+        })
+    );
+    assert_eq!(grid.render('.', |x| *x), "..#\no..");
+    assert_eq!(
+        SparseGrid::parse("..#\no..", |x| (x != '.').then_some(x)),
+        grid
+    );
+}
@@ -0,0 +1,134 @@
+use crate::palette::Palette;
+use grid::Grid;
+use std::str::FromStr;
+
+/// Image file format of a picture
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Encoding {
+    // binary portable pixmap, read by most image tools
+    #[default]
+    Ppm,
+    Svg,
+}
+
+impl Encoding {
+    /// Extension of the files in this format
+    ///
+    /// ```
+    /// let encoding: picture::Encoding = "svg".parse().unwrap();
+    /// assert_eq!(encoding.extension(), "svg");
+    /// assert_eq!(picture::Encoding::default().extension(), "ppm");
+    /// assert!("png".parse::<picture::Encoding>().is_err());
+    /// ```
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Encoding::Ppm => "ppm",
+            Encoding::Svg => "svg",
+        }
+    }
+
+    /// The picture in this format, see `ppm` and `svg`
+    ///
+    /// ```
+    /// use picture::{Encoding, Palette};
+    ///
+    /// let picture = grid::Grid::parse("#.\n", Some).unwrap();
+    /// let ppm = Encoding::Ppm.encode(&picture, &Palette::default(), 1);
+    /// assert!(ppm.starts_with(b"P6\n2 1\n255\n"));
+    /// let svg = Encoding::Svg.encode(&picture, &Palette::default(), 1);
+    /// assert!(svg.starts_with(b"<svg"));
+    /// ```
+    pub fn encode(&self, picture: &Grid<char>, palette: &Palette, scale: usize) -> Vec<u8> {
+        match self {
+            Encoding::Ppm => ppm(picture, palette, scale),
+            Encoding::Svg => svg(picture, palette, scale).into_bytes(),
+        }
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ppm" => Ok(Encoding::Ppm),
+            "svg" => Ok(Encoding::Svg),
+            _ => Err(format!("unknown image format {}, expected ppm or svg!", s)),
+        }
+    }
+}
+
+/// Binary PPM of the picture, each cell a square of scale by scale pixels
+///
+/// ```
+/// use picture::{Palette, Rgb};
+///
+/// let picture = grid::Grid::parse("#.\n", Some).unwrap();
+/// let palette = Palette::new(Rgb::new(0, 0, 0)).with('#', Rgb::new(255, 0, 0));
+/// let image = picture::ppm(&picture, &palette, 2);
+/// let header = b"P6\n4 2\n255\n";
+/// assert_eq!(&image[..header.len()], header);
+/// // two rows of two red and two black pixels
+/// let row = [255, 0, 0, 255, 0, 0, 0, 0, 0, 0, 0, 0];
+/// assert_eq!(&image[header.len()..], [row, row].concat());
+/// ```
+pub fn ppm(picture: &Grid<char>, palette: &Palette, scale: usize) -> Vec<u8> {
+    let scale = scale.max(1);
+    let (width, height) = (picture.cols() * scale, picture.rows() * scale);
+    let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+    image.reserve(width * height * 3);
+    for row in 0..picture.rows() {
+        let mut line = Vec::with_capacity(width * 3);
+        for col in 0..picture.cols() {
+            let colour = palette.colour(picture[(row, col)]);
+            for _ in 0..scale {
+                line.extend([colour.r, colour.g, colour.b]);
+            }
+        }
+        for _ in 0..scale {
+            image.extend(&line);
+        }
+    }
+    image
+}
+
+/// SVG of the picture, each cell a square of scale by scale units. Runs of
+/// cells of the same colour in a row are drawn as one rectangle.
+///
+/// ```
+/// use picture::{Palette, Rgb};
+///
+/// let picture = grid::Grid::parse("##.\n", Some).unwrap();
+/// let palette = Palette::new(Rgb::new(0, 0, 0)).with('#', Rgb::new(255, 0, 0));
+/// let image = picture::svg(&picture, &palette, 10);
+/// assert!(image.contains("width=\"30\" height=\"10\""));
+/// assert!(image.contains("<rect x=\"0\" y=\"0\" width=\"20\" height=\"10\" fill=\"#ff0000\"/>"));
+/// assert!(image.contains("<rect x=\"20\" y=\"0\" width=\"10\" height=\"10\" fill=\"#000000\"/>"));
+/// ```
+pub fn svg(picture: &Grid<char>, palette: &Palette, scale: usize) -> String {
+    let scale = scale.max(1);
+    let (width, height) = (picture.cols() * scale, picture.rows() * scale);
+    let mut image = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" shape-rendering=\"crispEdges\">\n",
+        width, height
+    );
+    for row in 0..picture.rows() {
+        let mut col = 0;
+        while col < picture.cols() {
+            let colour = palette.colour(picture[(row, col)]);
+            let start = col;
+            while col < picture.cols() && palette.colour(picture[(row, col)]) == colour {
+                col += 1;
+            }
+            image += &format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                start * scale,
+                row * scale,
+                (col - start) * scale,
+                scale,
+                colour
+            );
+        }
+    }
+    image + "</svg>\n"
+}
@@ -0,0 +1,84 @@
+use crate::encode::Encoding;
+use crate::palette::Palette;
+use fs_err as fs;
+use grid::Grid;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Numbered image files of a sequence of pictures, e.g. to be turned into
+/// an animation by ffmpeg
+pub struct Frames {
+    dir: PathBuf,
+    encoding: Encoding,
+    palette: Palette,
+    scale: usize,
+    count: usize,
+}
+
+impl Frames {
+    /// Frames written to `dir`, which is created if needed
+    ///
+    /// ```
+    /// use picture::{Encoding, Frames, Palette};
+    ///
+    /// let dir = std::env::temp_dir().join("picture-frames-new");
+    /// let frames = Frames::new(&dir, Encoding::Svg, Palette::default(), 1).unwrap();
+    /// assert!(dir.is_dir());
+    /// assert_eq!(frames.count(), 0);
+    /// ```
+    pub fn new(
+        dir: &Path,
+        encoding: Encoding,
+        palette: Palette,
+        scale: usize,
+    ) -> Result<Frames, Box<dyn Error>> {
+        fs::create_dir_all(dir)?;
+        Ok(Frames {
+            dir: dir.to_path_buf(),
+            encoding,
+            palette,
+            scale,
+            count: 0,
+        })
+    }
+
+    /// Write the next frame, as frame-00001.ppm, frame-00002.ppm, ...
+    ///
+    /// ```
+    /// use picture::{Encoding, Frames, Palette};
+    ///
+    /// let dir = std::env::temp_dir().join("picture-frames-push");
+    /// let mut frames = Frames::new(&dir, Encoding::Ppm, Palette::default(), 1).unwrap();
+    /// let picture = grid::Grid::parse("#.\n", Some).unwrap();
+    /// assert_eq!(frames.push(&picture).unwrap(), dir.join("frame-00001.ppm"));
+    /// assert_eq!(frames.push(&picture).unwrap(), dir.join("frame-00002.ppm"));
+    /// assert!(std::fs::read(dir.join("frame-00002.ppm")).unwrap().starts_with(b"P6"));
+    /// ```
+    pub fn push(&mut self, picture: &Grid<char>) -> Result<PathBuf, Box<dyn Error>> {
+        self.count += 1;
+        let filename = self.dir.join(format!(
+            "frame-{:05}.{}",
+            self.count,
+            self.encoding.extension()
+        ));
+        fs::write(
+            &filename,
+            self.encoding.encode(picture, &self.palette, self.scale),
+        )?;
+        Ok(filename)
+    }
+
+    /// Number of frames written so far
+    ///
+    /// ```
+    /// use picture::{Encoding, Frames, Palette};
+    ///
+    /// let dir = std::env::temp_dir().join("picture-frames-count");
+    /// let mut frames = Frames::new(&dir, Encoding::Svg, Palette::default(), 1).unwrap();
+    /// frames.push(&grid::Grid::parse(".\n", Some).unwrap()).unwrap();
+    /// assert_eq!(frames.count(), 1);
+    /// ```
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
@@ -0,0 +1,6 @@
+mod encode;
+mod frames;
+mod palette;
+pub use crate::encode::{ppm, svg, Encoding};
+pub use crate::frames::Frames;
+pub use crate::palette::{Palette, Rgb};
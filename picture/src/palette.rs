@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// A colour, one byte per channel
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Rgb {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl Rgb {
+    /// ```
+    /// let colour = picture::Rgb::new(0xff, 0x33, 0x00);
+    /// assert_eq!(colour.to_string(), "#ff3300");
+    /// assert_eq!("ff3300".parse(), Ok(colour));
+    /// assert!("#ff33".parse::<picture::Rgb>().is_err());
+    /// ```
+    pub const fn new(r: u8, g: u8, b: u8) -> Rgb {
+        Rgb { r, g, b }
+    }
+
+    // step out of steps of the way from self to other
+    fn towards(self, other: Rgb, step: u32, steps: u32) -> Rgb {
+        let channel = |from: u8, to: u8| {
+            let (from, to) = (i64::from(from), i64::from(to));
+            (from + (to - from) * i64::from(step) / i64::from(steps.max(1))) as u8
+        };
+        Rgb::new(
+            channel(self.r, other.r),
+            channel(self.g, other.g),
+            channel(self.b, other.b),
+        )
+    }
+}
+
+// #rrggbb, as used by SVG
+impl fmt::Display for Rgb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+    }
+}
+
+impl FromStr for Rgb {
+    type Err = String;
+
+    // rrggbb, with or without a leading #
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        let channel = |idx: usize| {
+            hex.get(idx..idx + 2)
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+        };
+        match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Rgb::new(r, g, b)),
+            _ => Err(format!("invalid colour {}, expected rrggbb!", s)),
+        }
+    }
+}
+
+/// Colour of each character of a picture
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    colours: BTreeMap<char, Rgb>,
+    // characters without a colour of their own
+    other: Rgb,
+}
+
+const BACKGROUND: Rgb = Rgb::new(0x0f, 0x0f, 0x23);
+const GOLD: Rgb = Rgb::new(0xff, 0xff, 0x66);
+const RED: Rgb = Rgb::new(0xff, 0x33, 0x33);
+
+impl Palette {
+    /// Palette giving `other` to every character
+    ///
+    /// ```
+    /// use picture::{Palette, Rgb};
+    ///
+    /// let palette = Palette::new(Rgb::new(1, 2, 3));
+    /// assert_eq!(palette.colour('#'), Rgb::new(1, 2, 3));
+    /// ```
+    pub fn new(other: Rgb) -> Palette {
+        Palette {
+            colours: BTreeMap::new(),
+            other,
+        }
+    }
+
+    /// The same palette with `symbol` in `colour`
+    ///
+    /// ```
+    /// use picture::{Palette, Rgb};
+    ///
+    /// let palette = Palette::new(Rgb::new(0, 0, 0)).with('#', Rgb::new(255, 255, 255));
+    /// assert_eq!(palette.colour('#'), Rgb::new(255, 255, 255));
+    /// assert_eq!(palette.colour('.'), Rgb::new(0, 0, 0));
+    /// ```
+    pub fn with(mut self, symbol: char, colour: Rgb) -> Palette {
+        self.colours.insert(symbol, colour);
+        self
+    }
+
+    /// Colour of `symbol`, or of the characters without a colour of their own
+    ///
+    /// ```
+    /// use picture::{Palette, Rgb};
+    ///
+    /// let palette: Palette = "#=ff0000".parse().unwrap();
+    /// assert_eq!(palette.colour('#'), Rgb::new(255, 0, 0));
+    /// assert_eq!(palette.colour('.'), Palette::default().colour('.'));
+    /// assert!("#ff0000".parse::<Palette>().is_err());
+    /// ```
+    pub fn colour(&self, symbol: char) -> Rgb {
+        self.colours.get(&symbol).copied().unwrap_or(self.other)
+    }
+}
+
+impl Default for Palette {
+    // the symbols of the text drawings of the days: lit pixels, rocks and
+    // visible trees are `#`, sand `o` below its source `+`, tree heights
+    // digits, elevations letters between `S` and `E` and a path `*`
+    fn default() -> Self {
+        let mut palette = Palette::new(Rgb::new(0xff, 0x00, 0xff))
+            .with('.', BACKGROUND)
+            .with('#', GOLD)
+            .with('o', Rgb::new(0xc2, 0xb2, 0x80))
+            .with('+', RED)
+            .with('*', RED)
+            .with('S', Rgb::new(0x33, 0xcc, 0x33))
+            .with('E', RED);
+        let (low, high) = (Rgb::new(0x10, 0x40, 0x10), Rgb::new(0x80, 0xff, 0x80));
+        for (step, digit) in ('0'..='9').enumerate() {
+            palette = palette.with(digit, low.towards(high, step as u32, 9));
+        }
+        let (low, high) = (Rgb::new(0x20, 0x30, 0x20), Rgb::new(0xe0, 0xe0, 0xe0));
+        for (step, letter) in ('a'..='z').enumerate() {
+            palette = palette.with(letter, low.towards(high, step as u32, 25));
+        }
+        palette
+    }
+}
+
+impl FromStr for Palette {
+    type Err = String;
+
+    // the default palette with the colours of some symbols replaced, as
+    // `#=ff0000,.=000000`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut palette = Palette::default();
+        for entry in s.split(',').filter(|entry| !entry.is_empty()) {
+            let mut chars = entry.chars();
+            let (Some(symbol), Some('=')) = (chars.next(), chars.next()) else {
+                return Err(format!(
+                    "invalid palette entry {}, expected c=rrggbb!",
+                    entry
+                ));
+            };
+            palette = palette.with(symbol, chars.as_str().parse()?);
+        }
+        Ok(palette)
+    }
+}
@@ -0,0 +1,66 @@
+use grid::Grid;
+use picture::{ppm, svg, Encoding, Frames, Palette, Rgb};
+
+fn palette() -> Palette {
+    Palette::new(Rgb::new(0, 0, 255))
+        .with('#', Rgb::new(255, 255, 255))
+        .with('.', Rgb::new(0, 0, 0))
+}
+
+#[test]
+fn ppm_scales_each_cell() {
+    let picture = Grid::parse("#.\n.x", Some).unwrap();
+    let image = ppm(&picture, &palette(), 2);
+    let header = b"P6\n4 4\n255\n";
+    assert_eq!(&image[..header.len()], header);
+    let pixels = &image[header.len()..];
+    assert_eq!(pixels.len(), 4 * 4 * 3);
+    // first row: two white pixels, then two black ones
+    assert_eq!(
+        &pixels[..12],
+        &[255, 255, 255, 255, 255, 255, 0, 0, 0, 0, 0, 0]
+    );
+    // the unknown symbol in the bottom right corner
+    assert_eq!(&pixels[pixels.len() - 3..], &[0, 0, 255]);
+}
+
+#[test]
+fn svg_joins_runs_of_a_colour() {
+    let picture = Grid::parse("##.#", Some).unwrap();
+    let image = svg(&picture, &palette(), 3);
+    assert!(image.starts_with("<svg"));
+    assert!(image.contains("width=\"12\" height=\"3\""));
+    assert_eq!(image.matches("<rect").count(), 3);
+    assert!(image.contains("<rect x=\"0\" y=\"0\" width=\"6\" height=\"3\" fill=\"#ffffff\"/>"));
+    assert!(image.trim_end().ends_with("</svg>"));
+}
+
+#[test]
+fn palette_overrides_the_default_colours() {
+    let palette: Palette = "#=ff0000,.=#000000".parse().unwrap();
+    assert_eq!(palette.colour('#'), Rgb::new(255, 0, 0));
+    assert_eq!(palette.colour('.'), Rgb::new(0, 0, 0));
+    assert_eq!(palette.colour('o'), Palette::default().colour('o'));
+    assert_ne!(palette.colour('a'), palette.colour('z'));
+
+    assert!("#=ff00".parse::<Palette>().is_err());
+    assert!("#ff0000".parse::<Palette>().is_err());
+    assert_eq!("".parse::<Palette>().unwrap(), Palette::default());
+    assert_eq!(Rgb::new(255, 136, 0).to_string(), "#ff8800");
+}
+
+#[test]
+fn frames_are_numbered() {
+    let dir = std::env::temp_dir().join(format!("picture-frames-{}", std::process::id()));
+    let mut frames = Frames::new(&dir, Encoding::Svg, palette(), 1).unwrap();
+    let picture = Grid::parse("#.", Some).unwrap();
+    frames.push(&picture).unwrap();
+    let second = frames.push(&picture).unwrap();
+    assert_eq!(second, dir.join("frame-00002.svg"));
+    assert_eq!(frames.count(), 2);
+    assert!(std::fs::read_to_string(second).unwrap().contains("<svg"));
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!("svg".parse::<Encoding>(), Ok(Encoding::Svg));
+    assert!("gif".parse::<Encoding>().is_err());
+}